@@ -0,0 +1,245 @@
+//! Bearer-token gate in front of Bevy's HTTP server, used by [`BrpToolPlugin::with_token`]
+//!
+//! `RemoteHttpPlugin` has no hook for inspecting request headers, so when a token is set the
+//! plugin moves Bevy's server to a private loopback port and this proxy listens on the public
+//! address instead. Each connection carries one request: its headers are checked, then the
+//! request is forwarded with `Connection: close` and the response is copied back until Bevy
+//! closes it, so watch streams pass through unchanged. At most [`MAX_CONNECTIONS`] are
+//! relayed at once; further connections get `503 Service Unavailable` until one ends.
+//!
+//! [`BrpToolPlugin::with_token`]: crate::BrpToolPlugin::with_token
+
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use bevy::log::debug;
+
+/// Largest request head accepted before the connection is dropped
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// How long a client may take to send its request head, so idle connections don't hold
+/// one of the [`MAX_CONNECTIONS`] slots
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections relayed at once, each holding two threads while it's open
+const MAX_CONNECTIONS: usize = 64;
+
+/// Sent instead of forwarding a request without the right token
+const UNAUTHORIZED_RESPONSE: &[u8] = b"HTTP/1.1 401 Unauthorized\r\n\
+    WWW-Authenticate: Bearer\r\n\
+    Content-Length: 0\r\n\
+    Connection: close\r\n\r\n";
+
+/// Sent instead of relaying a connection while [`MAX_CONNECTIONS`] are already open
+const BUSY_RESPONSE: &[u8] = b"HTTP/1.1 503 Service Unavailable\r\n\
+    Content-Length: 0\r\n\
+    Connection: close\r\n\r\n";
+
+/// Accept connections on `listener` in a background thread, forwarding those that carry
+/// `token` to Bevy's server on `upstream_port`
+pub(crate) fn spawn_auth_proxy(
+    listener: TcpListener,
+    upstream_port: u16,
+    token: String,
+) -> io::Result<()> {
+    let token: Arc<str> = token.into();
+    let open = Arc::new(AtomicUsize::new(0));
+    thread::Builder::new()
+        .name("brp-auth-proxy".to_string())
+        .spawn(move || {
+            for mut client in listener.incoming().flatten() {
+                let Some(slot) = ConnectionSlot::claim(&open) else {
+                    debug!("Remote control connection refused: too many open connections");
+                    let _ = client.write_all(BUSY_RESPONSE);
+                    continue;
+                };
+                let token = Arc::clone(&token);
+                let spawned = thread::Builder::new().spawn(move || {
+                    let _slot = slot;
+                    if let Err(error) = proxy_connection(client, upstream_port, &token) {
+                        debug!("Remote control connection ended: {}", error);
+                    }
+                });
+                if let Err(error) = spawned {
+                    debug!("Remote control connection dropped: {}", error);
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// One of the [`MAX_CONNECTIONS`] relay slots, given back when dropped
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn claim(open: &Arc<AtomicUsize>) -> Option<Self> {
+        open.fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+            (count < MAX_CONNECTIONS).then_some(count + 1)
+        })
+        .ok()
+        .map(|_| Self(Arc::clone(open)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Check one request's token and relay it and its response
+fn proxy_connection(mut client: TcpStream, upstream_port: u16, token: &str) -> io::Result<()> {
+    let (head, body_start) = read_request_head(&mut client, REQUEST_HEAD_TIMEOUT)?;
+    if !is_authorized(&head, token) {
+        client.write_all(UNAUTHORIZED_RESPONSE)?;
+        return client.shutdown(Shutdown::Both);
+    }
+
+    let mut upstream = TcpStream::connect((Ipv4Addr::LOCALHOST, upstream_port))?;
+    upstream.write_all(close_after_response(&head).as_bytes())?;
+    upstream.write_all(&body_start)?;
+
+    // The rest of the body streams up while the response streams back
+    let mut client_reader = client.try_clone()?;
+    let mut upstream_writer = upstream.try_clone()?;
+    thread::Builder::new().spawn(move || io::copy(&mut client_reader, &mut upstream_writer))?;
+    io::copy(&mut upstream, &mut client)?;
+    client.shutdown(Shutdown::Both)
+}
+
+/// Read up to the blank line ending the request head, returning the head and any body
+/// bytes that arrived with it
+///
+/// Fails if the head doesn't arrive within `timeout`. The timeout is lifted once it has,
+/// so a long-lived response such as a watch stream isn't cut off.
+fn read_request_head(stream: &mut TcpStream, timeout: Duration) -> io::Result<(String, Vec<u8>)> {
+    stream.set_read_timeout(Some(timeout))?;
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 4096];
+    loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            stream.set_read_timeout(None)?;
+            let body_start = buffer.split_off(end + 4);
+            let head = String::from_utf8(buffer)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "non-UTF-8 headers"))?;
+            return Ok((head, body_start));
+        }
+        if buffer.len() > MAX_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request headers too large",
+            ));
+        }
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Whether the request head has an `Authorization: Bearer` header matching `token`
+fn is_authorized(head: &str, token: &str) -> bool {
+    head.lines().skip(1).any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("authorization")
+                && value
+                    .trim()
+                    .strip_prefix("Bearer ")
+                    .is_some_and(|given| constant_time_eq(given.trim(), token))
+        })
+    })
+}
+
+/// Compare secrets without returning early at the first differing byte
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (x, y)| difference | (x ^ y))
+            == 0
+}
+
+/// The request head with its `Connection` headers replaced by `Connection: close`, so Bevy
+/// ends the connection after one response
+fn close_after_response(head: &str) -> String {
+    let mut lines: Vec<&str> = head
+        .split("\r\n")
+        .filter(|line| {
+            !line.is_empty()
+                && !line
+                    .split_once(':')
+                    .is_some_and(|(name, _)| name.trim().eq_ignore_ascii_case("connection"))
+        })
+        .collect();
+    lines.push("Connection: close");
+    format!("{}\r\n\r\n", lines.join("\r\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEAD: &str = "POST / HTTP/1.1\r\n\
+        host: localhost:15702\r\n\
+        authorization: Bearer s3cret\r\n\
+        connection: keep-alive\r\n\r\n";
+
+    #[test]
+    fn test_is_authorized_checks_bearer_token() {
+        assert!(is_authorized(HEAD, "s3cret"));
+        assert!(!is_authorized(HEAD, "s3cre"));
+        assert!(!is_authorized(HEAD, "other!"));
+        assert!(!is_authorized(
+            "POST / HTTP/1.1\r\nhost: x\r\n\r\n",
+            "s3cret"
+        ));
+    }
+
+    #[test]
+    fn test_connection_slots_are_capped_and_returned() {
+        let open = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| ConnectionSlot::claim(&open).expect("slot under the cap"))
+            .collect();
+        assert!(ConnectionSlot::claim(&open).is_none());
+
+        drop(slots);
+        assert_eq!(open.load(Ordering::Acquire), 0);
+        assert!(ConnectionSlot::claim(&open).is_some());
+    }
+
+    #[test]
+    fn test_read_request_head_times_out_on_idle_client() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut server, _) = listener.accept().unwrap();
+
+        let error = read_request_head(&mut server, Duration::from_millis(50)).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ));
+
+        client.write_all(HEAD.as_bytes()).unwrap();
+        let (head, body_start) = read_request_head(&mut server, Duration::from_secs(5)).unwrap();
+        assert_eq!(head, HEAD);
+        assert!(body_start.is_empty());
+        assert_eq!(server.read_timeout().unwrap(), None);
+    }
+
+    #[test]
+    fn test_close_after_response_replaces_connection_header() {
+        assert_eq!(
+            close_after_response(HEAD),
+            "POST / HTTP/1.1\r\n\
+             host: localhost:15702\r\n\
+             authorization: Bearer s3cret\r\n\
+             Connection: close\r\n\r\n"
+        );
+    }
+}
@@ -4,16 +4,27 @@ use anyhow::Result;
 use tokio::time::sleep;
 
 use super::client::RemoteClient;
-use super::commands::{Commands, execute_standalone_command, parse_command_string};
-use super::support::{is_connection_error, poll_until_ready};
+use super::commands::{
+    Commands, ExecutionOptions, execute_standalone_command, parse_command_string,
+    split_command_list,
+};
+use super::constants::WAIT_FOR_POLL_INTERVAL_MS;
+use super::support::{
+    ReadyWhen, is_connection_error, poll_until_ready, set_command_echo, wait_for_port_connectable,
+};
 use crate::DEFAULT_REMOTE_PORT;
 
 /// Detect running instances on common ports
-pub async fn detect_running_instances(requested_port: u16) -> Result<Vec<u16>> {
+///
+/// `token` is sent with each probe, since an app that requires one rejects probes without it.
+pub async fn detect_running_instances(
+    requested_port: u16,
+    token: Option<&str>,
+) -> Result<Vec<u16>> {
     let mut running_ports = Vec::new();
 
     // Check the requested port first
-    if is_port_responsive(requested_port).await {
+    if is_port_responsive(requested_port, token).await {
         running_ports.push(requested_port);
     }
 
@@ -21,7 +32,7 @@ pub async fn detect_running_instances(requested_port: u16) -> Result<Vec<u16>> {
     if requested_port == DEFAULT_REMOTE_PORT {
         for offset in 1..=5 {
             let port = DEFAULT_REMOTE_PORT + offset;
-            if is_port_responsive(port).await {
+            if is_port_responsive(port, token).await {
                 running_ports.push(port);
             }
         }
@@ -31,16 +42,86 @@ pub async fn detect_running_instances(requested_port: u16) -> Result<Vec<u16>> {
 }
 
 /// Check if a port has a responsive BRP-enabled instance
-async fn is_port_responsive(port: u16) -> bool {
-    let client = RemoteClient::new(port);
+async fn is_port_responsive(port: u16, token: Option<&str>) -> bool {
+    let client = match token {
+        Some(token) => RemoteClient::new(port).with_token(token),
+        None => RemoteClient::new(port),
+    };
     // Try to connect with a BRP command - this is a quick check
     client.is_ready().await.unwrap_or(false)
 }
 
 /// Wait for the app to be ready by polling with BRP commands
-pub async fn wait_for_app_ready(client: &RemoteClient) -> Result<()> {
-    let port = client.port();
+///
+/// Polls every 50 ms unless `poll_interval` overrides it.
+pub async fn wait_for_app_ready(
+    client: &RemoteClient,
+    poll_interval: Option<Duration>,
+) -> Result<()> {
+    poll_app_ready(
+        client,
+        Duration::from_secs(5),
+        poll_interval,
+        format!(
+            "No app is running at {}. Start the app first or use --managed mode.",
+            client.base_url()
+        ),
+    )
+    .await
+}
+
+/// Wait for an app this tool launched to be ready by the `--ready-when` strategy
+///
+/// Each stage (BRP readiness, then query results for `query:COMPONENT`) gets the full
+/// `timeout`, so asset-heavy apps have as long to load as they had to open their port.
+pub async fn wait_for_launched_app(
+    client: &RemoteClient,
+    ready_when: &ReadyWhen,
+    timeout: Duration,
+    poll_interval: Option<Duration>,
+) -> Result<()> {
+    let brp_timeout_message = format!(
+        "App did not answer BRP requests within {} seconds",
+        timeout.as_secs()
+    );
+    match ready_when {
+        ReadyWhen::Port => wait_for_port_connectable(client.port(), timeout).await,
+        ReadyWhen::Brp => poll_app_ready(client, timeout, poll_interval, brp_timeout_message).await,
+        ReadyWhen::Query(component) => {
+            poll_app_ready(client, timeout, poll_interval, brp_timeout_message).await?;
+            poll_until_ready(
+                || async {
+                    match client.query_entities(vec![component.as_str()]).await {
+                        Ok(result)
+                            if result
+                                .as_array()
+                                .is_some_and(|entities| !entities.is_empty()) =>
+                        {
+                            Ok(())
+                        }
+                        _ => anyhow::bail!("No entities with {} yet", component),
+                    }
+                },
+                timeout,
+                poll_interval.unwrap_or(Duration::from_millis(WAIT_FOR_POLL_INTERVAL_MS)),
+                format!(
+                    "App did not become ready within {} seconds: no entity has {}",
+                    timeout.as_secs(),
+                    component
+                ),
+            )
+            .await
+        }
+    }
+}
 
+/// Poll the BRP readiness check until it passes or `timeout` runs out
+async fn poll_app_ready(
+    client: &RemoteClient,
+    timeout: Duration,
+    poll_interval: Option<Duration>,
+    timeout_message: String,
+) -> Result<()> {
     poll_until_ready(
         || async {
             match client.is_ready().await {
@@ -57,18 +138,39 @@ pub async fn wait_for_app_ready(client: &RemoteClient) -> Result<()> {
                 }
             }
         },
-        Duration::from_secs(5),
-        Duration::from_millis(50),
-        format!(
-            "No app is running on port {}. Start the app first or use --managed mode.",
-            port
-        ),
+        timeout,
+        poll_interval.unwrap_or(Duration::from_millis(50)),
+        timeout_message,
     )
     .await
 }
 
-/// Execute a single command
-pub async fn execute_command(client: &RemoteClient, command: &str) -> Result<()> {
+/// Execute a single command from a command list
+///
+/// With `--emit-command-echo`, each JSON result is wrapped with the command that produced it.
+pub async fn execute_command(
+    client: &RemoteClient,
+    command: &str,
+    options: ExecutionOptions,
+) -> Result<()> {
+    if !options.echo_commands {
+        return execute_command_string(client, command, options).await;
+    }
+
+    // Restore the outer label afterwards, so a replay inside a command list labels the
+    // commands it replays and the list's own commands keep theirs
+    let outer_command = set_command_echo(Some(command.trim().to_string()));
+    let result = execute_command_string(client, command, options).await;
+    set_command_echo(outer_command);
+    result
+}
+
+/// Parse and run one command string, falling back to a raw BRP call for method names
+async fn execute_command_string(
+    client: &RemoteClient,
+    command: &str,
+    options: ExecutionOptions,
+) -> Result<()> {
     // Handle special wait command
     if let Some(duration_str) = command.strip_prefix("wait:") {
         let seconds: u64 = duration_str.parse()?;
@@ -81,7 +183,7 @@ pub async fn execute_command(client: &RemoteClient, command: &str) -> Result<()>
     match parse_command_string(command) {
         Ok(cmd) => {
             // Delegate to the standalone command executor
-            execute_standalone_command(client, cmd).await
+            execute_standalone_command(client, cmd, options).await
         }
         Err(parse_error) => {
             // If parsing fails, check if it's a raw command with method syntax
@@ -93,7 +195,13 @@ pub async fn execute_command(client: &RemoteClient, command: &str) -> Result<()>
                 // Try as a raw command
                 let raw_args: Vec<String> =
                     command.split_whitespace().map(|s| s.to_string()).collect();
-                execute_standalone_command(client, Commands::Raw { args: raw_args }).await
+                let raw = Commands::Raw {
+                    stream: false,
+                    params_file: None,
+                    params_stdin: false,
+                    args: raw_args,
+                };
+                execute_standalone_command(client, raw, options).await
             } else {
                 // Return the parse error
                 Err(parse_error)
@@ -101,3 +209,51 @@ pub async fn execute_command(client: &RemoteClient, command: &str) -> Result<()>
         }
     }
 }
+
+/// Execute a comma-separated list of commands against a running app
+///
+/// Supports the same syntax as `--managed-commands`, including `wait:N` delays and
+/// commas inside JSON objects. Execution stops at the first failing command unless
+/// `options.continue_on_error` is set, in which case all failures are reported at the end.
+pub async fn execute_command_list(
+    client: &RemoteClient,
+    commands: &str,
+    options: ExecutionOptions,
+) -> Result<()> {
+    let commands = split_command_list(commands);
+    let total = commands.len();
+    let mut failures = Vec::new();
+
+    for (index, command) in commands.iter().enumerate() {
+        if let Err(e) = execute_command(client, command, options).await {
+            let failure = format!(
+                "Command {} of {} ('{}') failed: {}",
+                index + 1,
+                total,
+                command,
+                e
+            );
+            if !options.continue_on_error {
+                anyhow::bail!(failure);
+            }
+            eprintln!("Error: {}", failure);
+            failures.push(failure);
+        }
+    }
+
+    summarize_failures(&failures, total)
+}
+
+/// Turn the failures collected during a `--continue-on-error` run into a summary error
+pub fn summarize_failures(failures: &[String], total: usize) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    anyhow::bail!(
+        "{} of {} commands failed:\n  {}",
+        failures.len(),
+        total,
+        failures.join("\n  ")
+    )
+}
@@ -1,19 +1,56 @@
 //! Client for controlling Bevy apps remotely
 
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
+use bytes::Bytes;
+use clap::ValueEnum;
 use serde_json::{Value, json};
+use tokio::sync::mpsc;
 use tokio_stream::Stream;
+use tokio_stream::wrappers::ReceiverStream;
 
 use super::constants::{
-    BEVY_DESTROY, BEVY_GET, BEVY_INSERT, BEVY_INSERT_RESOURCE, BEVY_LIST, BEVY_MUTATE_COMPONENT,
-    BEVY_MUTATE_RESOURCE, BEVY_QUERY, BEVY_REMOVE, BEVY_SPAWN, BRP_TOOL_SCREENSHOT,
-    BRP_TOOL_SHUTDOWN,
+    BATCH_REQUEST_SIZE, BEVY_CHILD_OF_COMPONENT, BEVY_CHILDREN_COMPONENT, BEVY_DESTROY, BEVY_GET,
+    BEVY_GET_RESOURCE, BEVY_INSERT, BEVY_INSERT_RESOURCE, BEVY_LIST, BEVY_MUTATE_COMPONENT,
+    BEVY_MUTATE_RESOURCE, BEVY_QUERY, BEVY_REGISTRY_SCHEMA, BEVY_REMOVE, BEVY_SPAWN,
+    BRP_TOOL_SCREENSHOT, BRP_TOOL_SCREENSHOT_DATA, BRP_TOOL_SHUTDOWN,
 };
-use super::rpc_params_builder::RpcParamsBuilder;
+use super::rpc_params_builder::{BrpVersion, RpcParamsBuilder};
 use super::sse::parse_sse_stream;
-use super::support::is_connection_error;
+use super::support::{is_connection_error, patch_mutations};
+
+/// URL scheme used to reach a Bevy app
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum UrlScheme {
+    /// Plain HTTP (what `RemoteHttpPlugin` serves)
+    #[default]
+    Http,
+    /// HTTPS, for apps behind a TLS-terminating proxy
+    Https,
+}
+
+impl fmt::Display for UrlScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlScheme::Http => write!(f, "http"),
+            UrlScheme::Https => write!(f, "https"),
+        }
+    }
+}
+
+/// Responses that don't change while an app runs, fetched once and shared by every clone
+#[derive(Default)]
+struct ResponseCache {
+    /// Result of `bevy/list` without an entity: the registered component types
+    component_types: Option<Value>,
+    /// Results of `bevy/registry/schema`, keyed by their serialized params
+    schemas: HashMap<String, Value>,
+}
 
 /// Client for sending remote control commands to a Bevy application.
 ///
@@ -23,7 +60,10 @@ use super::support::is_connection_error;
 pub struct RemoteClient {
     base_url: String,
     port: u16,
+    brp_version: BrpVersion,
+    token: Option<String>,
     client: reqwest::Client,
+    cache: Arc<Mutex<ResponseCache>>,
 }
 
 impl RemoteClient {
@@ -32,15 +72,69 @@ impl RemoteClient {
         Self {
             base_url: format!("http://localhost:{}", port),
             port,
+            brp_version: BrpVersion::default(),
+            token: None,
             client: reqwest::Client::new(),
+            cache: Arc::default(),
         }
     }
 
+    /// Create a remote client for an app at `base_url` (e.g. `https://my-host:15702`)
+    ///
+    /// Use this for apps on other machines or behind TLS; [`RemoteClient::new`] always
+    /// connects to `http://localhost`.
+    pub fn with_url(base_url: &str) -> Result<Self> {
+        let url = reqwest::Url::parse(base_url)
+            .map_err(|e| anyhow::anyhow!("Invalid URL '{}': {}", base_url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("Invalid URL '{}': scheme must be http or https", base_url);
+        }
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| anyhow::anyhow!("Invalid URL '{}': missing port", base_url))?;
+
+        Ok(Self {
+            base_url: base_url.to_string(),
+            port,
+            brp_version: BrpVersion::default(),
+            token: None,
+            client: reqwest::Client::new(),
+            cache: Arc::default(),
+        })
+    }
+
+    /// Shape request parameters for a specific BRP version instead of the newest
+    pub fn with_brp_version(mut self, brp_version: BrpVersion) -> Self {
+        self.brp_version = brp_version;
+        self
+    }
+
+    /// Send `token` as an `Authorization: Bearer` header, for apps whose plugin requires one
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
     /// Get the port this client is connected to
     pub fn port(&self) -> u16 {
         self.port
     }
 
+    /// Get the URL requests are sent to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get the BRP version request parameters are shaped for
+    pub fn brp_version(&self) -> BrpVersion {
+        self.brp_version
+    }
+
+    /// Start a parameter builder for this client's BRP version
+    pub fn params(&self) -> RpcParamsBuilder {
+        RpcParamsBuilder::for_version(self.brp_version)
+    }
+
     /// Generate a unique request ID using current timestamp
     ///
     /// We use timestamp-based IDs instead of a counter to avoid needing mutable
@@ -54,6 +148,27 @@ impl RemoteClient {
             .as_micros() as u64
     }
 
+    /// POST a JSON body to the app, with the bearer token if one is set
+    async fn post(&self, body: &Value) -> Result<reqwest::Response> {
+        let mut request = self.client.post(&self.base_url).json(body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            anyhow::bail!(
+                "The app at {} rejected the request as unauthorized (HTTP 401): {}",
+                self.base_url,
+                if self.token.is_some() {
+                    "the token does not match"
+                } else {
+                    "it requires a token (--token or BRP_TOKEN)"
+                }
+            );
+        }
+        Ok(response)
+    }
+
     /// Send a JSON-RPC request
     pub async fn request(&self, method: &str, params: Value) -> Result<Value> {
         let request_id = Self::generate_request_id();
@@ -65,16 +180,65 @@ impl RemoteClient {
             "params": params
         });
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await?;
+        let response = self.post(&request).await?;
 
         let result: Value = response.json().await?;
+        Self::response_result(&result)
+    }
 
-        if let Some(error) = result.get("error") {
+    /// Send several JSON-RPC requests in a single HTTP round-trip
+    ///
+    /// Results come back in the same order as `requests`. If the server does not
+    /// answer with a batch array, the requests are sent one at a time instead.
+    pub async fn request_batch(&self, requests: Vec<(&str, Value)>) -> Result<Vec<Result<Value>>> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let base_id = Self::generate_request_id();
+        let body: Vec<Value> = requests
+            .iter()
+            .enumerate()
+            .map(|(index, (method, params))| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": method,
+                    "id": base_id + index as u64,
+                    "params": params
+                })
+            })
+            .collect();
+
+        let response = self.post(&Value::Array(body)).await?;
+        let result: Value = response.json().await?;
+
+        let Some(responses) = result.as_array() else {
+            let mut results = Vec::with_capacity(requests.len());
+            for (method, params) in requests {
+                results.push(self.request(method, params).await);
+            }
+            return Ok(results);
+        };
+
+        // Responses may arrive in any order, so match them back up by id
+        let mut by_id: HashMap<u64, &Value> = responses
+            .iter()
+            .filter_map(|response| Some((response.get("id")?.as_u64()?, response)))
+            .collect();
+        Ok((0..requests.len() as u64)
+            .map(|index| match by_id.remove(&(base_id + index)) {
+                Some(response) => Self::response_result(response),
+                None => Err(anyhow::anyhow!(
+                    "No response for batched request {}",
+                    requests[index as usize].0
+                )),
+            })
+            .collect())
+    }
+
+    /// Extract the result from a JSON-RPC response, turning an error object into an error
+    fn response_result(response: &Value) -> Result<Value> {
+        if let Some(error) = response.get("error") {
             // Try to extract error code and message for better error handling
             if let Some(error_obj) = error.as_object() {
                 let code = error_obj.get("code").and_then(|c| c.as_i64()).unwrap_or(0);
@@ -88,23 +252,53 @@ impl RemoteClient {
             }
         }
 
-        Ok(result["result"].clone())
+        Ok(response["result"].clone())
     }
 
     /// Query entities with specific components
     pub async fn query_entities(&self, components: Vec<&str>) -> Result<Value> {
-        self.request(
-            BEVY_QUERY,
-            RpcParamsBuilder::new()
-                .field("data", json!({ "components": components }))
-                .build(),
-        )
-        .await
+        self.request(BEVY_QUERY, self.params().query(components).build())
+            .await
     }
 
-    /// Get all entities
+    /// Get all registered component types
+    ///
+    /// The result is fetched once and then served from the cache shared by every clone of
+    /// this client, until [`RemoteClient::invalidate_cache`] is called.
     pub async fn list_entities(&self) -> Result<Value> {
-        self.request(BEVY_LIST, serde_json::Value::Null).await
+        if let Some(component_types) = self.lock_cache().component_types.clone() {
+            return Ok(component_types);
+        }
+
+        let component_types = self.request(BEVY_LIST, serde_json::Value::Null).await?;
+        self.lock_cache().component_types = Some(component_types.clone());
+        Ok(component_types)
+    }
+
+    /// Get the type registry schema, filtered by `params` (e.g. `{"with_crates": [...]}`)
+    ///
+    /// Cached per distinct `params` like [`RemoteClient::list_entities`].
+    pub async fn registry_schema(&self, params: Value) -> Result<Value> {
+        let key = params.to_string();
+        if let Some(schema) = self.lock_cache().schemas.get(&key).cloned() {
+            return Ok(schema);
+        }
+
+        let schema = self.request(BEVY_REGISTRY_SCHEMA, params).await?;
+        self.lock_cache().schemas.insert(key, schema.clone());
+        Ok(schema)
+    }
+
+    /// Forget cached component types and schemas, e.g. after the app was restarted
+    pub fn invalidate_cache(&self) {
+        *self.lock_cache() = ResponseCache::default();
+    }
+
+    /// Lock the response cache, recovering it if a panic poisoned the lock
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, ResponseCache> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     /// Get all component data for a single entity
@@ -123,14 +317,31 @@ impl RemoteClient {
             }
         }
 
-        // Now get data for each component type that exists on this entity
+        // Now get data for each component type that exists on this entity, batching the
+        // per-component gets to keep the number of HTTP round-trips small
         let mut components = serde_json::Map::new();
 
-        for component_type in &component_types {
-            if let Ok(component_result) = self.get_component(entity, component_type).await {
+        for chunk in component_types.chunks(BATCH_REQUEST_SIZE) {
+            let requests = chunk
+                .iter()
+                .map(|component_type| {
+                    let params = self
+                        .params()
+                        .entity(entity)
+                        .component_list(vec![*component_type])
+                        .build();
+                    (BEVY_GET, params)
+                })
+                .collect();
+            let results = self.request_batch(requests).await?;
+
+            for (component_type, component_result) in chunk.iter().zip(results) {
+                let Ok(component_result) = component_result else {
+                    continue;
+                };
                 // Extract the component data if it exists
                 if let Some(components_obj) = component_result.get("components") {
-                    if let Some(component_data) = components_obj.get(component_type) {
+                    if let Some(component_data) = components_obj.get(*component_type) {
                         // Only include if the component actually exists (not null)
                         if !component_data.is_null() {
                             components.insert(component_type.to_string(), component_data.clone());
@@ -141,51 +352,91 @@ impl RemoteClient {
         }
 
         // Check if entity exists (has any components)
-        if components.is_empty() {
-            // Try to query for this specific entity to see if it exists at all
-            let mut entity_exists = false;
-            for component_type in &component_types {
-                if let Ok(query_result) = self.query_entities(vec![component_type]).await {
-                    if let Some(query_array) = query_result.as_array() {
-                        for entity_data in query_array {
-                            if let Some(entity_id) =
-                                entity_data.get("entity").and_then(|e| e.as_u64())
-                            {
-                                if entity_id == entity {
-                                    entity_exists = true;
-                                    break;
-                                }
-                            }
-                        }
-                        if entity_exists {
-                            break;
-                        }
-                    }
-                }
-            }
-
-            if !entity_exists {
-                anyhow::bail!("Entity {} does not exist", entity);
-            }
+        if components.is_empty() && !self.appears_in_query(entity, &component_types).await {
+            anyhow::bail!("Entity {} does not exist", entity);
         }
 
         // Calculate generation from entity ID (upper 32 bits)
         let generation = (entity >> 32) as u32;
 
+        // Surface the hierarchy relationships so callers don't have to dig them out
+        let parent = components
+            .get(BEVY_CHILD_OF_COMPONENT)
+            .cloned()
+            .unwrap_or(Value::Null);
+        let children = components
+            .get(BEVY_CHILDREN_COMPONENT)
+            .cloned()
+            .unwrap_or_else(|| json!([]));
+
         Ok(json!({
             "entity": entity,
             "generation": generation,
+            "parent": parent,
+            "children": children,
             "components": components
         }))
     }
 
+    /// Check whether an entity exists, without fetching any component data
+    pub async fn entity_exists(&self, entity: u64) -> Result<bool> {
+        let component_types_result = self.list_entities().await?;
+        let component_types: Vec<&str> = component_types_result
+            .as_array()
+            .map(|types| types.iter().filter_map(|t| t.as_str()).collect())
+            .unwrap_or_default();
+
+        Ok(self.appears_in_query(entity, &component_types).await)
+    }
+
+    /// Find every entity whose `Name` component equals `name`
+    pub async fn entities_named(&self, name: &str) -> Result<Vec<u64>> {
+        let name_component = self.brp_version.name_component();
+        let query_result = self.query_entities(vec![name_component]).await?;
+        Ok(query_result
+            .as_array()
+            .map(|entities| {
+                entities
+                    .iter()
+                    .filter(|entity_data| {
+                        entity_data["components"][name_component].as_str() == Some(name)
+                    })
+                    .filter_map(|entity_data| entity_data.get("entity").and_then(|e| e.as_u64()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Query each component type in turn, stopping as soon as one returns the entity
+    async fn appears_in_query(&self, entity: u64, component_types: &[&str]) -> bool {
+        for component_type in component_types {
+            let Ok(query_result) = self.query_entities(vec![*component_type]).await else {
+                continue;
+            };
+            let found = query_result.as_array().is_some_and(|entities| {
+                entities.iter().any(|entity_data| {
+                    entity_data.get("entity").and_then(|e| e.as_u64()) == Some(entity)
+                })
+            });
+            if found {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Get component data for an entity
     pub async fn get_component(&self, entity: u64, component: &str) -> Result<Value> {
+        self.get_components(entity, vec![component]).await
+    }
+
+    /// Get data for several components of an entity in a single request
+    pub async fn get_components(&self, entity: u64, components: Vec<&str>) -> Result<Value> {
         self.request(
             BEVY_GET,
-            RpcParamsBuilder::new()
+            self.params()
                 .entity(entity)
-                .component_list(vec![component])
+                .component_list(components)
                 .build(),
         )
         .await
@@ -200,7 +451,7 @@ impl RemoteClient {
     ) -> Result<Value> {
         self.request(
             BEVY_INSERT,
-            RpcParamsBuilder::new()
+            self.params()
                 .entity(entity)
                 .component_data(component, data)
                 .build(),
@@ -212,22 +463,91 @@ impl RemoteClient {
     pub async fn spawn_entity(&self, components: Value) -> Result<Value> {
         self.request(
             BEVY_SPAWN,
-            RpcParamsBuilder::new().components(components).build(),
+            self.params().insert_components(components).build(),
         )
         .await
     }
 
+    /// Spawn a copy of an entity from a `list_entity` component map
+    /// Tries a single spawn with every component first; if that fails, spawns an empty entity
+    /// and inserts components one at a time, returning the new entity ID along with the
+    /// components that could not be inserted
+    pub async fn spawn_clone(
+        &self,
+        components: &serde_json::Map<String, Value>,
+    ) -> Result<(u64, Vec<String>)> {
+        if let Ok(result) = self.spawn_entity(Value::Object(components.clone())).await {
+            return Ok((Self::spawned_entity_id(&result)?, Vec::new()));
+        }
+
+        let result = self.spawn_entity(json!({})).await?;
+        let entity = Self::spawned_entity_id(&result)?;
+
+        let mut skipped = Vec::new();
+        for (component_type, component_data) in components {
+            if self
+                .insert_component(entity, component_type, component_data.clone())
+                .await
+                .is_err()
+            {
+                skipped.push(component_type.clone());
+            }
+        }
+
+        Ok((entity, skipped))
+    }
+
+    /// Extract the new entity ID from a `bevy/spawn` response
+    fn spawned_entity_id(result: &Value) -> Result<u64> {
+        result
+            .get("entity")
+            .and_then(|e| e.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Spawn response did not contain an entity ID"))
+    }
+
     /// Destroy an entity
     pub async fn destroy_entity(&self, entity: u64) -> Result<Value> {
-        self.request(BEVY_DESTROY, RpcParamsBuilder::new().entity(entity).build())
+        self.request(BEVY_DESTROY, self.params().entity(entity).build())
             .await
     }
 
     /// Take a screenshot (requires custom method on server)
-    pub async fn take_screenshot(&self, path: &str) -> Result<Value> {
+    /// `region` is `[x, y, width, height]` in physical pixels; `quality` only applies to jpg.
+    /// With `inline`, the response carries a `capture_id` for `screenshot_data`
+    pub async fn take_screenshot(
+        &self,
+        path: Option<&str>,
+        region: Option<&[u32]>,
+        format: Option<&str>,
+        quality: Option<u8>,
+        inline: bool,
+    ) -> Result<Value> {
+        let mut params = self.params();
+        if let Some(path) = path {
+            params = params.path(path);
+        }
+        if inline {
+            params = params.field("inline", json!(true));
+        }
+        if let Some(region) = region {
+            params = params.field("region", json!(region));
+        }
+        if let Some(format) = format {
+            params = params.field("format", json!(format));
+        }
+        if let Some(quality) = quality {
+            params = params.field("quality", json!(quality));
+        }
+
+        self.request(BRP_TOOL_SCREENSHOT, params.build()).await
+    }
+
+    /// Collect the base64 bytes of an inline screenshot (requires custom method on server)
+    /// Returns `{"ready": false}` until the frame has been captured
+    pub async fn screenshot_data(&self, capture_id: u64) -> Result<Value> {
         self.request(
-            BRP_TOOL_SCREENSHOT,
-            RpcParamsBuilder::new().path(path).build(),
+            BRP_TOOL_SCREENSHOT_DATA,
+            self.params().field("capture_id", json!(capture_id)).build(),
         )
         .await
     }
@@ -270,7 +590,7 @@ impl RemoteClient {
     pub async fn remove_component(&self, entity: u64, component: &str) -> Result<Value> {
         self.request(
             BEVY_REMOVE,
-            RpcParamsBuilder::new()
+            self.params()
                 .entity(entity)
                 .component_list(vec![component])
                 .build(),
@@ -288,42 +608,38 @@ impl RemoteClient {
     ) -> Result<Value> {
         self.request(
             BEVY_MUTATE_COMPONENT,
-            RpcParamsBuilder::new()
+            self.params()
                 .entity(entity)
                 .component(component)
-                .field("path", json!(path))
-                .field("value", value)
+                .mutation(path, value)
                 .build(),
         )
         .await
     }
 
     /// Mutate multiple component fields (convenience method)
-    /// Takes a JSON object and applies each field as a separate mutation
+    /// Takes a JSON object and applies each field as a separate mutation.
+    /// Keys may be nested or indexed paths such as `translation.x` or `data[2]`
     pub async fn mutate_component(
         &self,
         entity: u64,
         component: &str,
         patch: Value,
     ) -> Result<Value> {
-        if let Some(obj) = patch.as_object() {
-            let mut last_result = json!(null);
-            for (field_name, field_value) in obj {
-                last_result = self
-                    .mutate_component_field(entity, component, field_name, field_value.clone())
-                    .await?;
-            }
-            Ok(last_result)
-        } else {
-            anyhow::bail!("Patch must be a JSON object with field names and values");
+        let mut last_result = json!(null);
+        for (path, value) in patch_mutations(&patch)? {
+            last_result = self
+                .mutate_component_field(entity, component, &path, value)
+                .await?;
         }
+        Ok(last_result)
     }
 
     /// Insert or update a resource
     pub async fn insert_resource(&self, resource_type: &str, data: Value) -> Result<Value> {
         self.request(
             BEVY_INSERT_RESOURCE,
-            RpcParamsBuilder::new()
+            self.params()
                 .resource(resource_type)
                 .field("value", data)
                 .build(),
@@ -340,37 +656,38 @@ impl RemoteClient {
     ) -> Result<Value> {
         self.request(
             BEVY_MUTATE_RESOURCE,
-            RpcParamsBuilder::new()
+            self.params()
                 .resource(resource)
-                .field("path", json!(path))
-                .field("value", value)
+                .mutation(path, value)
                 .build(),
         )
         .await
     }
 
     /// Mutate multiple resource fields (convenience method)
-    /// Takes a JSON object and applies each field as a separate mutation
+    /// Takes a JSON object and applies each field as a separate mutation.
+    /// Keys may be nested or indexed paths such as `settings.audio.volume` or `levels[1]`
     pub async fn mutate_resource(&self, resource: &str, patch: Value) -> Result<Value> {
-        if let Some(obj) = patch.as_object() {
-            let mut last_result = json!(null);
-            for (field_name, field_value) in obj {
-                last_result = self
-                    .mutate_resource_field(resource, field_name, field_value.clone())
-                    .await?;
-            }
-            Ok(last_result)
-        } else {
-            anyhow::bail!("Patch must be a JSON object with field names and values");
+        let mut last_result = json!(null);
+        for (path, value) in patch_mutations(&patch)? {
+            last_result = self.mutate_resource_field(resource, &path, value).await?;
         }
+        Ok(last_result)
     }
 
     /// Send a streaming JSON-RPC request (for SSE endpoints)
+    ///
+    /// With `keepalive`, the stream fails once that long passes without any bytes from the
+    /// server. `:` comment lines count, so a server sending them as heartbeats stays alive
+    /// while idle; Bevy's server sends none, so with Bevy only updates keep the stream alive.
     pub async fn stream_request(
         &self,
         method: &str,
         params: Value,
+        keepalive: Option<Duration>,
     ) -> Result<impl Stream<Item = Result<Value>>> {
+        use tokio_stream::StreamExt;
+
         let request_id = Self::generate_request_id();
 
         let request = json!({
@@ -380,12 +697,7 @@ impl RemoteClient {
             "params": params
         });
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await?;
+        let response = self.post(&request).await?;
 
         // Check for non-2xx status codes
         if !response.status().is_success() {
@@ -398,9 +710,281 @@ impl RemoteClient {
         }
 
         // Convert response to byte stream
-        let stream = response.bytes_stream();
+        let stream = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(anyhow::Error::from));
+        let stream: Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>> = match keepalive {
+            Some(interval) => Box::pin(stream.timeout(interval).map(move |chunk| {
+                chunk.unwrap_or_else(|_| {
+                    Err(anyhow::anyhow!(
+                        "no data within {} s, assuming the connection is dead",
+                        interval.as_secs()
+                    ))
+                })
+            })),
+            None => Box::pin(stream),
+        };
 
         // Parse SSE events from the stream
         Ok(parse_sse_stream(stream))
     }
+
+    /// Watch a resource by polling `bevy/get_resource`, since BRP has no resource watch method
+    ///
+    /// The first value is emitted immediately and later values only when they differ from
+    /// the last one emitted. The stream ends after the first failed poll, and polling stops
+    /// once the stream is dropped.
+    pub fn poll_resource(
+        &self,
+        resource: &str,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<Value>> + Unpin {
+        let (sender, receiver) = mpsc::channel(1);
+        let client = self.clone();
+        let params = self.params().resource(resource).build();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            let mut last = None;
+            loop {
+                ticker.tick().await;
+                match client.request(BEVY_GET_RESOURCE, params.clone()).await {
+                    Ok(value) if last.as_ref() == Some(&value) => {}
+                    Ok(value) => {
+                        last = Some(value.clone());
+                        if sender.send(Ok(value)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = sender.send(Err(e)).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        ReceiverStream::new(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    const COMPONENT_TYPES: [&str; 3] = ["test::A", "test::B", "test::C"];
+    /// The only component present on the mock entity
+    const PRESENT: &str = "test::B";
+
+    /// Answer a single JSON-RPC request the way a BRP server would
+    fn mock_response(request: &Value) -> Value {
+        let id = request["id"].clone();
+        let result = match request["method"].as_str() {
+            Some(BEVY_LIST) => json!(COMPONENT_TYPES),
+            Some(BEVY_GET_RESOURCE) => json!({ "value": { "score": 7 } }),
+            Some(BEVY_QUERY) if request["params"]["data"]["components"][0] == PRESENT => {
+                json!([{ "entity": 42, "components": { PRESENT: { "value": 1 } } }])
+            }
+            Some(BEVY_GET) if request["params"]["components"][0] == PRESENT => {
+                json!({ "components": { PRESENT: { "value": 1 } }, "errors": {} })
+            }
+            _ => {
+                return json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -23402, "message": "Component not present" }
+                });
+            }
+        };
+        json!({ "jsonrpc": "2.0", "id": id, "result": result })
+    }
+
+    /// Read one HTTP request from the stream and return its body
+    async fn read_http_body(stream: &mut TcpStream) -> Option<Vec<u8>> {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buffer.extend_from_slice(&chunk[..n]);
+        };
+
+        let headers = String::from_utf8_lossy(&buffer[..header_end]).to_lowercase();
+        let length = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        let mut body = buffer[header_end..].to_vec();
+        while body.len() < length {
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        Some(body)
+    }
+
+    /// Serve BRP over HTTP on an ephemeral port, counting HTTP requests
+    async fn spawn_mock_server(supports_batch: bool) -> (u16, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    while let Some(body) = read_http_body(&mut stream).await {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                        let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                        let response = match &request {
+                            // Answer out of order to exercise matching by id
+                            Value::Array(batch) if supports_batch => {
+                                Value::Array(batch.iter().rev().map(mock_response).collect())
+                            }
+                            Value::Array(_) => json!({
+                                "jsonrpc": "2.0",
+                                "id": null,
+                                "error": { "code": -32600, "message": "Invalid request" }
+                            }),
+                            single => mock_response(single),
+                        };
+                        let body = response.to_string();
+                        let reply = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        if stream.write_all(reply.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (port, calls)
+    }
+
+    #[tokio::test]
+    async fn test_list_entity_batches_component_gets() {
+        let (batch_port, batch_calls) = spawn_mock_server(true).await;
+        let (sequential_port, sequential_calls) = spawn_mock_server(false).await;
+
+        let batched = RemoteClient::new(batch_port).list_entity(42).await.unwrap();
+        let sequential = RemoteClient::new(sequential_port)
+            .list_entity(42)
+            .await
+            .unwrap();
+
+        assert_eq!(batched, sequential);
+        assert_eq!(batched["components"], json!({ PRESENT: { "value": 1 } }));
+        // bevy/list, then every bevy/get in one batch
+        assert_eq!(batch_calls.load(Ordering::SeqCst), 2);
+        // bevy/list, the rejected batch, then one bevy/get per component type
+        assert_eq!(
+            sequential_calls.load(Ordering::SeqCst),
+            2 + COMPONENT_TYPES.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_poll_resource_emits_only_changes() {
+        use tokio_stream::StreamExt;
+
+        let (port, calls) = spawn_mock_server(true).await;
+        let client = RemoteClient::new(port);
+        let mut stream = client.poll_resource("test::Score", Duration::from_millis(10));
+
+        let first = stream.next().await.unwrap().unwrap();
+        let second = tokio::time::timeout(Duration::from_millis(200), stream.next()).await;
+
+        assert_eq!(first, json!({ "value": { "score": 7 } }));
+        // The mock value never changes, so later polls are not emitted
+        assert!(second.is_err());
+        assert!(calls.load(Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn test_entity_exists() {
+        let (port, _) = spawn_mock_server(true).await;
+        let client = RemoteClient::new(port);
+
+        assert!(client.entity_exists(42).await.unwrap());
+        assert!(!client.entity_exists(7).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_request_batch_keeps_request_order() {
+        let (port, _) = spawn_mock_server(true).await;
+        let client = RemoteClient::new(port);
+
+        let results = client
+            .request_batch(vec![
+                (BEVY_LIST, Value::Null),
+                (BEVY_GET, json!({ "entity": 42, "components": [PRESENT] })),
+                (BEVY_GET, json!({ "entity": 42, "components": ["test::A"] })),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &json!(COMPONENT_TYPES));
+        assert!(results[1].as_ref().unwrap()["components"][PRESENT].is_object());
+        assert!(results[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_url_connects_to_given_host() {
+        let (port, calls) = spawn_mock_server(true).await;
+        let client = RemoteClient::with_url(&format!("http://127.0.0.1:{}", port)).unwrap();
+
+        assert_eq!(client.port(), port);
+        assert_eq!(
+            client.list_entities().await.unwrap(),
+            json!(COMPONENT_TYPES)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_component_types_are_cached_across_clones() {
+        let (port, calls) = spawn_mock_server(true).await;
+        let client = RemoteClient::new(port);
+
+        client.list_entities().await.unwrap();
+        let clone = client.clone();
+        assert_eq!(clone.list_entities().await.unwrap(), json!(COMPONENT_TYPES));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        client.invalidate_cache();
+        clone.list_entities().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_with_url_validates_url() {
+        assert_eq!(
+            RemoteClient::with_url("https://example.com")
+                .unwrap()
+                .port(),
+            443
+        );
+        assert!(RemoteClient::with_url("example.com:15702").is_err());
+        assert!(RemoteClient::with_url("ftp://example.com:15702").is_err());
+    }
 }
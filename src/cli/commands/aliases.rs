@@ -0,0 +1,122 @@
+//! Expansion of `--aliases` short names in the type arguments of a command
+//!
+//! Runs before anything else looks at the type names, so strict-type checks and the
+//! request itself only ever see full paths. Names that aren't aliases pass through unchanged.
+
+use serde_json::Value;
+
+use super::types::Commands;
+use crate::cli::support::expand_alias;
+
+/// Replace every aliased type name in the arguments of `command` with its full path
+///
+/// Covers component and resource arguments, the component half of `--select`, and the
+/// top-level keys of spawn and insert payloads. Payloads that don't parse are left as-is
+/// for the command itself to report.
+pub fn expand_type_aliases(command: &mut Commands) {
+    match command {
+        Commands::Query {
+            components, select, ..
+        } => {
+            expand_all(components);
+            for selection in select.iter_mut() {
+                *selection = match selection.split_once('.') {
+                    Some((component, path)) => format!("{}.{}", expand_alias(component), path),
+                    None => expand_alias(selection),
+                };
+            }
+        }
+        Commands::Get { components, .. }
+        | Commands::GetWatch { components, .. }
+        | Commands::WaitFor { components, .. } => expand_all(components),
+        Commands::GetResource { resource, .. }
+        | Commands::GetResourceWatch { resource, .. }
+        | Commands::MutateResource { resource, .. }
+        | Commands::RemoveResource { resource } => *resource = expand_alias(resource),
+        Commands::MutateComponent { component, .. } | Commands::Remove { component, .. } => {
+            *component = expand_alias(component);
+        }
+        Commands::Describe { type_name } => *type_name = expand_alias(type_name),
+        Commands::Spawn { components, .. } | Commands::Insert { components, .. } => {
+            expand_payload_keys(components);
+        }
+        Commands::InsertResource { data } => expand_payload_keys(data),
+        _ => {}
+    }
+}
+
+/// Expand each name in place
+fn expand_all(names: &mut [String]) {
+    for name in names {
+        *name = expand_alias(name);
+    }
+}
+
+/// Expand aliased top-level keys of a JSON object payload, keeping the payload text when none are
+fn expand_payload_keys(json: &mut String) {
+    let Ok(obj) = serde_json::from_str::<serde_json::Map<String, Value>>(json) else {
+        return;
+    };
+    if obj.keys().all(|key| expand_alias(key) == *key) {
+        return;
+    }
+
+    let expanded: serde_json::Map<String, Value> = obj
+        .into_iter()
+        .map(|(key, value)| (expand_alias(&key), value))
+        .collect();
+    *json = Value::Object(expanded).to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::cli::support::set_component_aliases;
+
+    #[test]
+    fn test_expand_type_aliases() {
+        set_component_aliases(HashMap::from([(
+            "transform".to_string(),
+            "bevy_transform::components::transform::Transform".to_string(),
+        )]));
+
+        let mut query = Commands::Query {
+            components: vec!["transform".to_string(), "my_game::Player".to_string()],
+            limit: None,
+            offset: None,
+            select: vec!["transform.translation.x".to_string()],
+            fail_if_empty: false,
+        };
+        expand_type_aliases(&mut query);
+        let Commands::Query {
+            components, select, ..
+        } = query
+        else {
+            unreachable!()
+        };
+        assert_eq!(
+            components,
+            [
+                "bevy_transform::components::transform::Transform",
+                "my_game::Player"
+            ]
+        );
+        assert_eq!(
+            select,
+            ["bevy_transform::components::transform::Transform.translation.x"]
+        );
+
+        let mut insert = Commands::InsertResource {
+            data: r#"{"transform": {"scale": [1, 1, 1]}, "unknown": 1}"#.to_string(),
+        };
+        expand_type_aliases(&mut insert);
+        let Commands::InsertResource { data } = insert else {
+            unreachable!()
+        };
+        let payload: Value = serde_json::from_str(&data).unwrap();
+        assert!(payload["bevy_transform::components::transform::Transform"].is_object());
+        assert_eq!(payload["unknown"], 1);
+    }
+}
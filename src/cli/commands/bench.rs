@@ -0,0 +1,78 @@
+//! Round-trip latency measurement for the `bench` command
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde_json::{Value, json};
+
+use crate::cli::client::RemoteClient;
+
+/// Call `method` `iterations` times after one warm-up call and summarize the latencies
+pub async fn bench_method(client: &RemoteClient, method: &str, iterations: u32) -> Result<Value> {
+    if iterations == 0 {
+        anyhow::bail!("bench requires at least one iteration");
+    }
+
+    // The first call pays for connection setup, which would skew the minimum and mean
+    client.call_brp_method(method, Value::Null).await?;
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        client.call_brp_method(method, Value::Null).await?;
+        samples.push(start.elapsed());
+    }
+
+    Ok(json!({
+        "method": method,
+        "iterations": iterations,
+        "latency_ms": latency_stats(&mut samples),
+    }))
+}
+
+/// Summarize latency samples as min/max/mean/p50/p95 in milliseconds
+fn latency_stats(samples: &mut [Duration]) -> Value {
+    samples.sort();
+    let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let total: Duration = samples.iter().sum();
+
+    json!({
+        "min": millis(samples[0]),
+        "max": millis(samples[samples.len() - 1]),
+        "mean": millis(total) / samples.len() as f64,
+        "p50": millis(percentile(samples, 50)),
+        "p95": millis(percentile(samples, 95)),
+    })
+}
+
+/// Nearest-rank percentile of sorted, non-empty samples
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        let mut samples: Vec<Duration> = (1..=20).rev().map(Duration::from_millis).collect();
+
+        let stats = latency_stats(&mut samples);
+
+        assert_eq!(stats["min"], json!(1.0));
+        assert_eq!(stats["max"], json!(20.0));
+        assert_eq!(stats["mean"], json!(10.5));
+        assert_eq!(stats["p50"], json!(10.0));
+        assert_eq!(stats["p95"], json!(19.0));
+    }
+
+    #[test]
+    fn test_percentile_single_sample() {
+        let samples = [Duration::from_millis(7)];
+
+        assert_eq!(percentile(&samples, 50), Duration::from_millis(7));
+        assert_eq!(percentile(&samples, 95), Duration::from_millis(7));
+    }
+}
@@ -0,0 +1,90 @@
+//! Classification of `rpc.discover` results for the `capabilities` command
+
+use serde_json::{Map, Value};
+
+use crate::cli::constants::{
+    BEVY_DESTROY, BEVY_GET, BEVY_GET_RESOURCE, BEVY_GET_WATCH, BEVY_INSERT, BEVY_INSERT_RESOURCE,
+    BEVY_LIST, BEVY_LIST_RESOURCES, BEVY_LIST_WATCH, BEVY_MUTATE_COMPONENT, BEVY_MUTATE_RESOURCE,
+    BEVY_QUERY, BEVY_REGISTRY_SCHEMA, BEVY_REMOVE, BEVY_REMOVE_RESOURCE, BEVY_REPARENT, BEVY_SPAWN,
+    BRP_TOOL_SCREENSHOT, BRP_TOOL_SCREENSHOT_DATA, BRP_TOOL_SHUTDOWN,
+};
+
+/// Capability flag and the method that must be discovered for it to be set
+const CAPABILITIES: [(&str, &str); 20] = [
+    ("has_query", BEVY_QUERY),
+    ("has_list", BEVY_LIST),
+    ("has_list_resources", BEVY_LIST_RESOURCES),
+    ("has_get", BEVY_GET),
+    ("has_get_resource", BEVY_GET_RESOURCE),
+    ("has_insert", BEVY_INSERT),
+    ("has_insert_resource", BEVY_INSERT_RESOURCE),
+    ("has_spawn", BEVY_SPAWN),
+    ("has_destroy", BEVY_DESTROY),
+    ("has_remove", BEVY_REMOVE),
+    ("has_remove_resource", BEVY_REMOVE_RESOURCE),
+    ("has_mutate_component", BEVY_MUTATE_COMPONENT),
+    ("has_mutate_resource", BEVY_MUTATE_RESOURCE),
+    ("has_reparent", BEVY_REPARENT),
+    ("has_registry_schema", BEVY_REGISTRY_SCHEMA),
+    ("has_get_watch", BEVY_GET_WATCH),
+    ("has_list_watch", BEVY_LIST_WATCH),
+    ("has_screenshot", BRP_TOOL_SCREENSHOT),
+    ("has_screenshot_data", BRP_TOOL_SCREENSHOT_DATA),
+    ("has_shutdown", BRP_TOOL_SHUTDOWN),
+];
+
+/// Turn an `rpc.discover` result into a `{has_*: bool}` object
+///
+/// Methods are listed either as OpenRPC `{"name": ...}` objects or as plain strings.
+pub fn classify_methods(discovered: &Value) -> Value {
+    let names: Vec<&str> = discovered
+        .get("methods")
+        .and_then(|m| m.as_array())
+        .map(|methods| {
+            methods
+                .iter()
+                .filter_map(|method| {
+                    method
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .or_else(|| method.as_str())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let flags: Map<String, Value> = CAPABILITIES
+        .iter()
+        .map(|(flag, method)| (flag.to_string(), Value::Bool(names.contains(method))))
+        .collect();
+    Value::Object(flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_classify_plain_remote_app() {
+        let discovered = json!({
+            "methods": [{ "name": BEVY_QUERY }, { "name": BEVY_GET }, { "name": BEVY_GET_WATCH }]
+        });
+
+        let flags = classify_methods(&discovered);
+
+        assert_eq!(flags["has_query"], json!(true));
+        assert_eq!(flags["has_get_watch"], json!(true));
+        assert_eq!(flags["has_screenshot"], json!(false));
+        assert_eq!(flags["has_shutdown"], json!(false));
+        assert_eq!(flags.as_object().unwrap().len(), CAPABILITIES.len());
+    }
+
+    #[test]
+    fn test_classify_string_method_list() {
+        let discovered = json!({ "methods": [BRP_TOOL_SCREENSHOT] });
+
+        assert_eq!(classify_methods(&discovered)["has_screenshot"], json!(true));
+    }
+}
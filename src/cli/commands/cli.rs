@@ -1,6 +1,17 @@
-use clap::Parser;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use super::types::Commands;
+use clap::{ArgGroup, CommandFactory, Parser};
+use regex::Regex;
+
+use super::types::{Commands, find_command_by_name};
+use crate::cli::client::UrlScheme;
+use crate::cli::rpc_params_builder::BrpVersion;
+use crate::cli::support::{
+    Assertion, IdBase, OutputFormat, ReadyWhen, ServerLogLevel, parse_assertion, parse_duration,
+    parse_ready_regex, parse_ready_when,
+};
 use crate::{DEFAULT_REMOTE_PORT, include_help};
 
 #[derive(Parser)]
@@ -20,20 +31,131 @@ Use --list-commands to see all available commands.
 Use --brp to see BRP configuration requirements."
 )]
 #[command(disable_help_subcommand = true)]
+#[command(group(ArgGroup::new("json_target").args(["detect", "list_commands"]).multiple(true)))]
 pub struct Cli {
     /// Port to connect to [default: 15702]
     #[arg(short, long, default_value_t = DEFAULT_REMOTE_PORT, hide_default_value = true, long_help = include_help!("port"))]
     pub port: u16,
 
+    /// Host of the app to connect to [default: localhost]
+    #[arg(long, default_value = "localhost", hide_default_value = true, long_help = include_help!("host"))]
+    pub host: String,
+
+    /// Scheme used to connect to the app
+    #[arg(long, value_enum, default_value_t, long_help = include_help!("host"))]
+    pub scheme: UrlScheme,
+
+    /// Full base URL of the app (e.g., https://my-host:15702), instead of --host/--scheme/--port
+    #[arg(long, conflicts_with_all = ["host", "scheme", "port"], long_help = include_help!("host"))]
+    pub url: Option<String>,
+
+    /// Shared secret sent as an `Authorization: Bearer` header, for apps that require one
+    #[arg(long, value_name = "TOKEN", long_help = include_help!("token"))]
+    pub token: Option<String>,
+
+    /// When several app instances are running, connect to the most recently started detached one
+    #[arg(long = "connect-to-newest", conflicts_with = "url", long_help = include_help!("connect_to_newest"))]
+    pub connect_to_newest: bool,
+
+    /// Connect straight to --port without first scanning for running app instances
+    #[arg(long = "no-instance-check", conflicts_with = "connect_to_newest", long_help = include_help!("no_instance_check"))]
+    pub no_instance_check: bool,
+
     /// Start app and execute commands directly (comma-separated)
     #[arg(short = 'm', long, long_help = include_help!("managed_commands"))]
     pub managed_commands: Option<String>,
 
+    /// Stop the app and fail if managed commands take longer than this many seconds
+    #[arg(long = "managed-timeout", value_name = "SECONDS", long_help = include_help!("managed_timeout"))]
+    pub managed_timeout: Option<u64>,
+
+    /// Leave the app running as a detached session after managed commands succeed
+    #[arg(long = "keep-alive", alias = "managed-keep-alive", long_help = include_help!("keep_alive"))]
+    pub keep_alive: bool,
+
+    /// Commands to run before --managed-commands, e.g. to insert test data
+    #[arg(long, value_name = "COMMANDS", long_help = include_help!("before"))]
+    pub before: Option<String>,
+
+    /// Commands to run after --managed-commands, even if they failed
+    #[arg(long, value_name = "COMMANDS", long_help = include_help!("after"))]
+    pub after: Option<String>,
+
+    /// Print managed command results as one JSON array once the commands finish
+    #[arg(long = "managed-json", long_help = include_help!("managed_json"))]
+    pub managed_json: bool,
+
+    /// After the managed commands, prompt for more commands until exit, then stop the app
+    #[arg(long, conflicts_with_all = ["detached", "commands", "managed_json"], long_help = include_help!("interactive"))]
+    pub interactive: bool,
+
+    /// When an app started by --managed-commands or --detached is ready: port, brp or
+    /// query:COMPONENT [default: brp]
+    #[arg(long = "ready-when", value_name = "STRATEGY", value_parser = parse_ready_when, default_value = "brp", hide_default_value = true, long_help = include_help!("ready_when"))]
+    pub ready_when: ReadyWhen,
+
+    /// Seconds an app started by --managed-commands or --detached gets to become ready
+    /// [default: 30]
+    #[arg(long = "wait-ready-timeout", value_name = "SECONDS", long_help = include_help!("wait_ready_timeout"))]
+    pub wait_ready_timeout: Option<u64>,
+
+    /// With --detached, also wait until the app logs a line matching this regex
+    #[arg(long = "app-ready-regex", value_name = "PATTERN", value_parser = parse_ready_regex, long_help = include_help!("app_ready_regex"))]
+    pub app_ready_regex: Option<Regex>,
+
+    /// Execute commands against an already running app (comma-separated)
+    #[arg(short = 'C', long, long_help = include_help!("commands"))]
+    pub commands: Option<String>,
+
+    /// Keep executing a command list after a command fails, then report all failures
+    #[arg(long = "continue-on-error")]
+    pub continue_on_error: bool,
+
+    /// Wrap each result of a command list as {"command": ..., "result": ...}
+    #[arg(long = "emit-command-echo", global = true, long_help = include_help!("emit_command_echo"))]
+    pub emit_command_echo: bool,
+
+    /// Skip the readiness wait before each command and fail fast if the app is unreachable
+    #[arg(long = "no-ready-check", global = true)]
+    pub no_ready_check: bool,
+
+    /// Milliseconds between readiness checks and other polls, overriding their defaults
+    #[arg(long = "poll-interval", value_name = "MS", value_parser = clap::value_parser!(u64).range(1..), global = true, long_help = include_help!("poll_interval"))]
+    pub poll_interval: Option<u64>,
+
+    /// Maximum number of requests sent at once by commands that fan out, like list_entities
+    #[arg(long = "max-concurrency", visible_alias = "concurrency", value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), global = true, long_help = include_help!("max_concurrency"))]
+    pub max_concurrency: Option<usize>,
+
+    /// Treat a watch stream as dead after this many seconds without data [default: 300]
+    #[arg(long = "keepalive-interval", value_name = "SECONDS", num_args = 0..=1, default_missing_value = "300", value_parser = clap::value_parser!(u64).range(1..), global = true, long_help = include_help!("keepalive_interval"))]
+    pub keepalive_interval: Option<u64>,
+
+    /// Reject component and resource names that aren't fully qualified type paths
+    #[arg(long = "strict-types", global = true, long_help = include_help!("strict_types"))]
+    pub strict_types: bool,
+
+    /// Bevy Remote Protocol version of the target app, used to shape request parameters
+    #[arg(long = "brp-version", value_enum, default_value_t, global = true)]
+    pub brp_version: BrpVersion,
+
     /// App binary to run in managed or detached mode.
     /// If not specified, will attempt to detect a Bevy app in the current workspace.
     #[arg(short, long, long_help = include_help!("app"))]
     pub app: Option<String>,
 
+    /// Extra arguments passed to the app in managed or detached mode (shell-style quoting)
+    #[arg(long = "app-args", value_name = "ARGS", allow_hyphen_values = true, long_help = include_help!("app_args"))]
+    pub app_args: Option<String>,
+
+    /// Environment variable for the app in managed or detached mode (repeatable)
+    #[arg(long = "env", value_name = "KEY=VALUE", value_parser = parse_env_var, long_help = include_help!("env"))]
+    pub env: Vec<(String, String)>,
+
+    /// Log level for the app in managed or detached mode, passed to it as RUST_LOG
+    #[arg(long = "server-log-level", value_name = "LEVEL", long_help = include_help!("server_log_level"))]
+    pub server_log_level: Option<ServerLogLevel>,
+
     /// Build profile to use [default: debug]
     #[arg(short = 'P', long, long_help = include_help!("profile"))]
     pub profile: Option<String>,
@@ -46,7 +168,7 @@ pub struct Cli {
     #[arg(short = 'f', long = "help-for", value_name = "COMMAND")]
     pub help_for: Option<String>,
 
-    /// List all known commands without connecting to an app
+    /// List all known commands without connecting to an app (with --json, as a catalog)
     #[arg(short, long = "list-commands")]
     pub list_commands: bool,
 
@@ -75,10 +197,139 @@ pub struct Cli {
     #[arg(short = 'c', long = "cleanup-logs", long_help = include_help!("cleanup_logs"))]
     pub cleanup_logs: bool,
 
+    /// With --cleanup-logs, only remove inactive sessions whose log is older than this (e.g., 24h)
+    #[arg(long = "older-than", value_name = "DURATION", value_parser = parse_duration, requires = "cleanup_logs")]
+    pub older_than: Option<Duration>,
+
+    /// Print the events parsed from a recorded SSE byte stream, for working on the watch parser
+    #[arg(long = "replay-sse", value_name = "FILE", hide = true)]
+    pub replay_sse: Option<PathBuf>,
+
+    /// Disable colored error output (also respects the NO_COLOR environment variable)
+    #[arg(long = "no-color")]
+    pub no_color: bool,
+
+    /// Print command output as single-line JSON, for piping into other tools
+    #[arg(long, global = true, conflicts_with = "pretty")]
+    pub compact: bool,
+
+    /// Print command output as indented JSON (the default)
+    #[arg(long, global = true)]
+    pub pretty: bool,
+
+    /// Render output as json or, for commands that support it (methods), an aligned table
+    #[arg(long = "format", alias = "output", value_name = "FORMAT", value_enum, default_value_t, global = true, long_help = include_help!("format"))]
+    pub output_format: OutputFormat,
+
+    /// Write command output JSON to this file instead of stdout, replacing it atomically
+    #[arg(long = "output-file", value_name = "PATH", global = true, long_help = include_help!("output_file"))]
+    pub output_file: Option<PathBuf>,
+
+    /// Append every executed command and its outcome to this file, for use with `replay`
+    #[arg(long, value_name = "PATH", global = true, long_help = include_help!("journal"))]
+    pub journal: Option<PathBuf>,
+
+    /// File mapping short type names to fully qualified paths, expanded in type arguments
+    #[arg(long, alias = "component-alias", value_name = "PATH", global = true, long_help = include_help!("aliases"))]
+    pub aliases: Option<PathBuf>,
+
+    /// Show entity IDs in command output as {raw, index, generation} objects
+    #[arg(long = "decode-entity-ids", global = true)]
+    pub decode_entity_ids: bool,
+
+    /// Number base for entity IDs in command output; hex IDs are "0x"-prefixed strings
+    #[arg(long = "id-base", value_enum, default_value_t, global = true, conflicts_with = "decode_entity_ids", long_help = include_help!("id_base"))]
+    pub id_base: IdBase,
+
+    /// Round floats in command output to this many decimal places, for reading by eye
+    #[arg(long, alias = "pretty-floats", value_name = "DIGITS", value_parser = clap::value_parser!(u32).range(0..=15), global = true, long_help = include_help!("round"))]
+    pub round: Option<u32>,
+
+    /// Fail with exit code 4 unless the command's JSON result satisfies POINTER OP VALUE
+    #[arg(long, value_name = "POINTER OP VALUE", value_parser = parse_assertion, global = true, long_help = include_help!("assert"))]
+    pub assert: Option<Assertion>,
+
+    /// Fail with exit code 4 unless the command's JSON result matches this golden file
+    #[arg(long, value_name = "PATH", global = true, long_help = include_help!("expect"))]
+    pub expect: Option<PathBuf>,
+
+    /// Run shutdown and destroy --recursive without asking for confirmation at a terminal
+    #[arg(short = 'y', long, global = true, long_help = include_help!("yes"))]
+    pub yes: bool,
+
+    /// Show the full error chain in addition to the friendly error message
+    #[arg(short, long)]
+    pub verbose: bool,
+
     /// Show detected Bevy app in current workspace
-    #[arg(short = 'D', long = "detect")]
+    #[arg(short = 'D', long = "detect", long_help = include_help!("detect"))]
     pub detect: bool,
 
+    /// Print --detect or --list-commands results as JSON instead of text
+    #[arg(long, requires = "json_target")]
+    pub json: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
+
+impl Cli {
+    /// Build the clap command with each subcommand's detailed help as its `--help` epilogue
+    ///
+    /// `brp <command> --help` then shows the same text as `--help-for <command>`, while
+    /// `-h` keeps the short argument summary.
+    pub fn command_with_detailed_help() -> clap::Command {
+        let command = Self::command();
+        let names: Vec<String> = command
+            .get_subcommands()
+            .map(|subcommand| subcommand.get_name().to_string())
+            .collect();
+
+        names
+            .into_iter()
+            .fold(command, |command, name| match find_command_by_name(&name) {
+                Some(template) => command.mut_subcommand(&name, |subcommand| {
+                    subcommand.after_long_help(template.detailed_help())
+                }),
+                None => command,
+            })
+    }
+
+    /// Build the clap command for parsing `args`
+    ///
+    /// clap can only leave out a second-to-last positional, so `mutate_component
+    /// --from-stdin` couldn't drop the first of its three. When `--from-stdin` is given, the
+    /// entity becomes a hidden option and the component and patch move up a position.
+    pub fn command_for_args(args: &[OsString]) -> clap::Command {
+        let command = Self::command_with_detailed_help();
+        if !args.iter().any(|arg| arg == "--from-stdin") {
+            return command;
+        }
+        command.mut_subcommand("mutate_component", |subcommand| {
+            subcommand.mut_arg("entity", |arg| arg.long("entity").hide(true))
+        })
+    }
+
+    /// Let `--interactive` on its own start the app and go straight to the prompt
+    ///
+    /// Runs before the config file and environment are applied, since both only fill in
+    /// `app` and the managed timeout when the app is started here.
+    pub fn apply_interactive_default(&mut self) {
+        if self.interactive && self.managed_commands.is_none() {
+            self.managed_commands = Some(String::new());
+        }
+    }
+}
+
+/// Parse a `KEY=VALUE` environment variable assignment
+fn parse_env_var(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.is_empty() && !key.contains(char::is_whitespace) => {
+            Ok((key.to_string(), value.to_string()))
+        }
+        _ => Err(format!(
+            "invalid environment variable '{}': expected KEY=VALUE",
+            input
+        )),
+    }
+}
@@ -0,0 +1,143 @@
+//! Single-type schema lookup for the `describe` command
+//!
+//! `bevy/registry/schema` has no way to ask for one type, so the schema is fetched
+//! narrowed to the type's crate and the entry is picked out by its full type path.
+
+use anyhow::{Result, bail};
+use serde_json::{Map, Value, json};
+
+use crate::cli::client::RemoteClient;
+
+/// Maximum number of suggestions listed when a type isn't registered
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Fetch the schema entry for `type_name` along with a field -> type summary
+pub async fn describe_type(client: &RemoteClient, type_name: &str) -> Result<Value> {
+    // The crate prefix usually matches the schema's `crateName`; when it doesn't (or the
+    // type isn't registered) fall back to the full schema so suggestions cover everything
+    let crate_name = type_name.split("::").next().unwrap_or(type_name);
+    let narrowed = client
+        .registry_schema(json!({ "with_crates": [crate_name] }))
+        .await?;
+    if let Some(entry) = narrowed.get(type_name) {
+        return Ok(describe_entry(type_name, entry));
+    }
+
+    let schema = client.registry_schema(json!({})).await?;
+    if let Some(entry) = schema.get(type_name) {
+        return Ok(describe_entry(type_name, entry));
+    }
+
+    let suggestions = similar_types(&schema, type_name);
+    if suggestions.is_empty() {
+        bail!(
+            "Type '{}' is not registered. Use 'schema' or 'list' to see registered types",
+            type_name
+        );
+    }
+    bail!(
+        "Type '{}' is not registered. Did you mean:\n  {}",
+        type_name,
+        suggestions.join("\n  ")
+    )
+}
+
+/// Build the `describe` output for one schema entry
+fn describe_entry(type_name: &str, entry: &Value) -> Value {
+    let fields: Map<String, Value> = entry
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(field, property)| (field.clone(), field_type(property)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    json!({
+        "type": type_name,
+        "kind": entry.get("kind").cloned().unwrap_or(Value::Null),
+        "fields": fields,
+        "schema": entry,
+    })
+}
+
+/// Type of a schema property, with `$ref` pointers reduced to the referenced type path
+fn field_type(property: &Value) -> Value {
+    match property.get("type") {
+        Some(Value::Object(type_ref)) => match type_ref.get("$ref").and_then(Value::as_str) {
+            Some(reference) => json!(reference.trim_start_matches("#/$defs/")),
+            None => Value::Object(type_ref.clone()),
+        },
+        Some(other) => other.clone(),
+        None => property.clone(),
+    }
+}
+
+/// Registered types whose path ends with the same short name as `type_name`
+fn similar_types(schema: &Value, type_name: &str) -> Vec<String> {
+    let short_name = type_name.rsplit("::").next().unwrap_or(type_name);
+    let suffix = format!("::{}", short_name);
+
+    let mut matches: Vec<String> = schema
+        .as_object()
+        .map(|types| {
+            types
+                .keys()
+                .filter(|key| key.as_str() == short_name || key.ends_with(&suffix))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    matches.sort();
+    matches.truncate(MAX_SUGGESTIONS);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_entry_resolves_field_refs() {
+        let entry = json!({
+            "kind": "Struct",
+            "properties": {
+                "translation": { "type": { "$ref": "#/$defs/glam::Vec3" } },
+                "enabled": { "type": "boolean" }
+            }
+        });
+
+        let described = describe_entry("my_game::Thing", &entry);
+
+        assert_eq!(described["type"], "my_game::Thing");
+        assert_eq!(described["kind"], "Struct");
+        assert_eq!(described["fields"]["translation"], "glam::Vec3");
+        assert_eq!(described["fields"]["enabled"], "boolean");
+        assert_eq!(described["schema"], entry);
+    }
+
+    #[test]
+    fn test_similar_types_match_by_suffix() {
+        let schema = json!({
+            "bevy_transform::components::transform::Transform": {},
+            "bevy_transform::components::global_transform::GlobalTransform": {},
+            "my_game::Transform": {},
+        });
+
+        assert_eq!(
+            similar_types(&schema, "Transform"),
+            vec![
+                "bevy_transform::components::transform::Transform".to_string(),
+                "my_game::Transform".to_string(),
+            ]
+        );
+        assert_eq!(
+            similar_types(&schema, "wrong::path::Transform").len(),
+            2,
+            "Suffix matching should ignore the requested module path"
+        );
+        assert!(similar_types(&schema, "Velocity").is_empty());
+    }
+}
@@ -1,138 +1,1419 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{IsTerminal, Read};
 use std::path::Path;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde_json::json;
 use tokio::fs;
 use tokio::time::{sleep, timeout};
 use tokio_stream::StreamExt;
 
-use super::types::Commands;
-use crate::cli::cli_client::wait_for_app_ready;
+use super::parsing::format_command;
+use super::types::{Commands, ConfigAction};
+use super::{aliases, bench, capabilities, describe, undo, validate};
+use crate::cli::cli_client::{execute_command, summarize_failures, wait_for_app_ready};
 use crate::cli::client::RemoteClient;
+use crate::cli::config::config_path;
 use crate::cli::constants::{
-    BEVY_GET_RESOURCE, BEVY_GET_WATCH, BEVY_LIST_RESOURCES, BEVY_LIST_WATCH, BEVY_REGISTRY_SCHEMA,
-    BEVY_REMOVE_RESOURCE, BEVY_REPARENT,
+    BENCH_DEFAULT_ITERATIONS, BEVY_CHILD_OF_COMPONENT, BEVY_CHILDREN_COMPONENT, BEVY_GET_RESOURCE,
+    BEVY_GET_WATCH, BEVY_LIST, BEVY_LIST_RESOURCES, BEVY_LIST_WATCH, BEVY_REMOVE_RESOURCE,
+    BEVY_REPARENT, LIST_ENTITIES_DEFAULT_CONCURRENCY, RESOURCE_WATCH_DEFAULT_INTERVAL_MS,
+    RPC_DISCOVER, SSE_REPLAY_CHUNK_BYTES, WAIT_FOR_DEFAULT_TIMEOUT_SECS, WAIT_FOR_POLL_INTERVAL_MS,
+    WATCH_RECONNECT_BASE_DELAY_MS, WATCH_RECONNECT_DEFAULT_MAX, WATCH_RECONNECT_MAX_DELAY_MS,
 };
-use crate::cli::rpc_params_builder::RpcParamsBuilder;
-use crate::cli::support::{parse_json_object, parse_json_value, print_json};
+use crate::cli::detached;
+use crate::cli::rpc_params_builder::BrpVersion;
+use crate::cli::sse::parse_sse_stream;
+use crate::cli::support::{
+    Assertion, ENTITY_NAME_PREFIX, capture_output, check_expected_output, json_diff, merge_json,
+    methods_table, parse_entity_ref, parse_json_object, parse_json_value, poll_until_ready,
+    print_json, print_json_line, print_text, read_entity_ids_from_stdin, read_journal,
+    record_command, table_output, take_assertion, take_captured_output, take_expected_output,
+    value_at_path, write_json_file,
+};
+
+/// Options that change how commands are executed, shared by standalone, list and managed modes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecutionOptions {
+    /// Keep going after a failure and report every failure at the end
+    pub continue_on_error: bool,
+    /// Skip the readiness wait before each command
+    pub skip_ready_check: bool,
+    /// BRP version to shape request parameters for
+    pub brp_version: BrpVersion,
+    /// Capture state before destructive commands so `undo` can reverse them
+    pub record_undo: bool,
+    /// Poll cadence for readiness checks, screenshot waits and `wait_for`, instead of their defaults
+    pub poll_interval: Option<Duration>,
+    /// Number of requests fanned out at once by `list_entities`, `subtree` and `snapshot`,
+    /// instead of the default
+    pub max_concurrency: Option<usize>,
+    /// Refuse type-name arguments that aren't fully qualified before sending anything
+    pub strict_types: bool,
+    /// Wrap each result of a command list as `{"command", "result"}`
+    pub echo_commands: bool,
+    /// Treat a watch stream as dead when no update arrives for this long
+    pub keepalive_interval: Option<Duration>,
+}
+
+/// Returned by `query --fail-if-empty` when no entities matched, so the process can exit
+/// with its own code instead of the generic failure code
+#[derive(Debug)]
+pub struct EmptyQueryError;
+
+impl std::fmt::Display for EmptyQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No entities matched the query")
+    }
+}
+
+impl std::error::Error for EmptyQueryError {}
+
+/// Pick the value at JSON pointer `field` out of a `bevy/get_resource` result
+///
+/// The pointer applies to the resource's `value` payload and its leading `/` is optional.
+fn resource_field(
+    result: &serde_json::Value,
+    resource: &str,
+    field: &str,
+) -> Result<serde_json::Value> {
+    let value = result.get("value").unwrap_or(result);
+    let pointer = if field.starts_with('/') {
+        field.to_string()
+    } else {
+        format!("/{}", field)
+    };
+    if let Some(found) = value.pointer(&pointer) {
+        return Ok(found.clone());
+    }
+
+    let keys = value
+        .as_object()
+        .map(|obj| obj.keys().cloned().collect::<Vec<_>>().join(", "))
+        .filter(|keys| !keys.is_empty())
+        .unwrap_or_else(|| "none".to_string());
+    anyhow::bail!(
+        "Field '{}' not found in resource {}. Available top-level keys: {}",
+        field,
+        resource,
+        keys
+    )
+}
+
+/// Slice a query result client-side, since BRP has no pagination of its own
+fn paginate(
+    result: serde_json::Value,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> serde_json::Value {
+    let entities = match result {
+        serde_json::Value::Array(entities) => entities,
+        other => vec![other],
+    };
+    let total = entities.len();
+    let offset = offset.unwrap_or(0);
+    let page: Vec<serde_json::Value> = entities
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    json!({
+        "total": total,
+        "offset": offset,
+        "limit": limit,
+        "entities": page,
+    })
+}
+
+/// Split each `--select COMPONENT.PATH` into its component and field path
+///
+/// The component must be one of the queried components, since BRP only returns those.
+/// A bare component name selects the whole component.
+fn parse_selections<'a>(
+    select: &'a [String],
+    components: &[String],
+) -> Result<Vec<(&'a str, &'a str)>> {
+    select
+        .iter()
+        .map(|selection| {
+            // Type paths separate segments with `::`, so the first `.` starts the field path
+            let (component, path) = selection
+                .split_once('.')
+                .unwrap_or((selection.as_str(), ""));
+            if !components.iter().any(|c| c == component) {
+                anyhow::bail!(
+                    "--select '{}' refers to '{}', which is not one of the queried components",
+                    selection,
+                    component
+                );
+            }
+            Ok((component, path))
+        })
+        .collect()
+}
+
+/// Reduce each entity's components in a query result to the selected fields
+///
+/// Each selected component becomes a `{path: value}` map (or its full data for a bare
+/// component name); unselected components are dropped. Missing fields are `null`.
+fn project_query_result(result: &mut serde_json::Value, selections: &[(&str, &str)]) {
+    let entities = match result {
+        serde_json::Value::Array(entities) => entities,
+        serde_json::Value::Object(envelope) => match envelope.get_mut("entities") {
+            Some(serde_json::Value::Array(entities)) => entities,
+            _ => return,
+        },
+        _ => return,
+    };
+
+    for entity in entities {
+        let Some(found) = entity.get("components") else {
+            continue;
+        };
+        let mut projected = serde_json::Map::new();
+        for (component, path) in selections {
+            let data = found.get(*component);
+            let field = data
+                .and_then(|data| value_at_path(data, path))
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            if path.is_empty() {
+                projected.insert(component.to_string(), field);
+            } else if let serde_json::Value::Object(fields) = projected
+                .entry(component.to_string())
+                .or_insert_with(|| json!({}))
+            {
+                fields.insert(path.to_string(), field);
+            }
+        }
+        entity["components"] = serde_json::Value::Object(projected);
+    }
+}
+
+/// Sort a list of type names alphabetically, since BRP returns them in registration order
+fn sort_type_names(mut result: serde_json::Value, sort: bool) -> serde_json::Value {
+    if let (true, Some(names)) = (sort, result.as_array_mut()) {
+        names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    }
+    result
+}
+
+/// Filter the `rpc.discover` method list by substring and optionally group it by namespace
+///
+/// The namespace is the part of the method name before the first `/` or `.`,
+/// e.g. `bevy` for `bevy/get` and `rpc` for `rpc.discover`.
+fn filter_methods(
+    methods: serde_json::Value,
+    grep: Option<&str>,
+    group: bool,
+) -> serde_json::Value {
+    let serde_json::Value::Array(methods) = methods else {
+        return methods;
+    };
+    let name_of = |method: &serde_json::Value| -> String {
+        method
+            .get("name")
+            .and_then(|n| n.as_str())
+            .or_else(|| method.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let methods = methods
+        .into_iter()
+        .filter(|method| grep.is_none_or(|grep| name_of(method).contains(grep)));
+    if !group {
+        return serde_json::Value::Array(methods.collect());
+    }
+
+    let mut groups = serde_json::Map::new();
+    for method in methods {
+        let name = name_of(&method);
+        let namespace = name
+            .split(['/', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if let serde_json::Value::Array(entries) = groups
+            .entry(namespace)
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+        {
+            entries.push(method);
+        }
+    }
+    serde_json::Value::Object(groups)
+}
+
+/// A `list_entities` entry for an entity and the component types it was found with
+fn entity_row(entity_id: u64, component_names: Vec<String>) -> serde_json::Value {
+    // Calculate generation from entity ID (upper 32 bits)
+    let generation = (entity_id >> 32) as u32;
+
+    json!({
+        "entity": entity_id,
+        "generation": generation,
+        "components": component_names
+    })
+}
+
+/// The `data` of a `list_entities --with-data` entry from its `list_entity` result; an entity
+/// despawned since it was listed gets null data
+fn entity_data(fetched: Option<Result<serde_json::Value>>) -> serde_json::Value {
+    match fetched {
+        Some(Ok(mut result)) => result["components"].take(),
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// Run `request` for every item, `concurrency` at a time, with results in item order
+///
+/// A concurrency of 1 awaits each request in turn on the current task instead of spawning,
+/// so requests reach the app strictly one after another. `None` marks a request whose
+/// spawned task panicked.
+async fn fan_out<T, R, F, Fut>(items: Vec<T>, concurrency: usize, request: F) -> Vec<Option<R>>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = R> + Send + 'static,
+    R: Send + 'static,
+{
+    let mut results = Vec::with_capacity(items.len());
+    if concurrency <= 1 {
+        for item in items {
+            results.push(Some(request(item).await));
+        }
+        return results;
+    }
+
+    let mut items = items.into_iter().peekable();
+    while items.peek().is_some() {
+        let tasks: Vec<_> = items
+            .by_ref()
+            .take(concurrency)
+            .map(|item| tokio::spawn(request(item)))
+            .collect();
+        for task in tasks {
+            results.push(task.await.ok());
+        }
+    }
+    results
+}
+
+/// Run an operation against each entity and print a per-entity report
+///
+/// Every entity is attempted. Fails with a summary if any entity failed, unless
+/// `continue_on_error` is set, in which case the summary is only printed as a warning.
+async fn run_for_each_entity<F, Fut>(
+    entities: &[u64],
+    command_name: &str,
+    options: ExecutionOptions,
+    operation: F,
+) -> Result<()>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<serde_json::Value>>,
+{
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for &entity in entities {
+        match operation(entity).await {
+            Ok(result) => results.push(json!({
+                "entity": entity,
+                "status": "ok",
+                "result": result,
+            })),
+            Err(e) => {
+                failed += 1;
+                results.push(json!({
+                    "entity": entity,
+                    "status": "error",
+                    "error": e.to_string(),
+                }));
+            }
+        }
+    }
+    print_json(&json!({ "results": results }))?;
+
+    if failed > 0 {
+        let message = format!(
+            "{} failed on {} of {} entities",
+            command_name,
+            failed,
+            entities.len()
+        );
+        if !options.continue_on_error {
+            anyhow::bail!(message);
+        }
+        eprintln!("Warning: {}", message);
+    }
+
+    Ok(())
+}
+
+/// Turn one part of a health check into a count, recording the error if it failed
+fn health_count(
+    name: &str,
+    result: Result<serde_json::Value>,
+    count: impl FnOnce(&serde_json::Value) -> Option<usize>,
+    errors: &mut serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    match result {
+        Ok(value) => json!(count(&value)),
+        Err(e) => {
+            errors.insert(name.to_string(), json!(e.to_string()));
+            serde_json::Value::Null
+        }
+    }
+}
+
+/// Extract the requested component data from a `bevy/get` result
+///
+/// A single component unwraps to just its data and several become a `{component: data}`
+/// map. If any component is missing, the full result is returned so its errors are shown.
+fn component_data(result: serde_json::Value, components: &[&str]) -> serde_json::Value {
+    let Some(found) = result.get("components").and_then(|c| c.as_object()) else {
+        return result;
+    };
+    if !components
+        .iter()
+        .all(|component| found.contains_key(*component))
+    {
+        return result;
+    }
+
+    match components {
+        [component] => found[*component].clone(),
+        _ => json!(found),
+    }
+}
+
+/// Poll `bevy/get` until none of `components` is reported under `errors`
+///
+/// Used by `get --retry-on-empty`. A failed request, such as for an entity that doesn't
+/// exist, ends the wait at once instead of being retried until the timeout.
+async fn get_when_present(
+    client: &RemoteClient,
+    entity: u64,
+    components: &[&str],
+    timeout: Option<u64>,
+    poll_interval: Option<Duration>,
+) -> Result<serde_json::Value> {
+    let timeout_secs = timeout.unwrap_or(WAIT_FOR_DEFAULT_TIMEOUT_SECS);
+
+    // Keep the response that ended the wait so we don't have to fetch it again
+    let outcome = std::sync::Mutex::new(None);
+
+    poll_until_ready(
+        || async {
+            let result = client.get_components(entity, components.to_vec()).await;
+            let missing = result.as_ref().is_ok_and(|result| {
+                components
+                    .iter()
+                    .any(|component| result["errors"].get(*component).is_some())
+            });
+            if missing {
+                anyhow::bail!("Components not present yet");
+            }
+            *outcome.lock().unwrap() = Some(result);
+            Ok(())
+        },
+        Duration::from_secs(timeout_secs),
+        poll_interval.unwrap_or(Duration::from_millis(WAIT_FOR_POLL_INTERVAL_MS)),
+        format!(
+            "Timed out after {} seconds waiting for entity {} to have: {}",
+            timeout_secs,
+            entity,
+            components.join(", ")
+        ),
+    )
+    .await?;
+
+    match outcome.into_inner().unwrap() {
+        Some(result) => result,
+        None => unreachable!("polling only succeeds after a response is stored"),
+    }
+}
+
+/// How `insert` treats a component the entity already has
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InsertMode {
+    /// Replace it with the given data
+    Replace,
+    /// Leave it alone (`--if-missing`)
+    IfMissing,
+    /// Deep-merge the given data into it (`--merge`)
+    Merge,
+}
+
+/// Current data of a component on an entity, or `None` if it is absent or can't be read
+async fn existing_component(
+    client: &RemoteClient,
+    entity: u64,
+    component: &str,
+) -> Option<serde_json::Value> {
+    client
+        .get_component(entity, component)
+        .await
+        .ok()
+        .and_then(|result| result.get("components")?.get(component).cloned())
+        .filter(|value| !value.is_null())
+}
+
+/// The chain from `entity` up to its root as `{entity, name}` objects, following `ChildOf`
+///
+/// Each step is one `bevy/get` of `ChildOf` and `Name`; `name` is left out for unnamed
+/// entities, and an entity without `ChildOf` ends the chain as the root. Bevy doesn't allow
+/// `ChildOf` cycles, but one would otherwise loop forever, so it is reported as an error.
+async fn ancestors(client: &RemoteClient, entity: u64) -> Result<serde_json::Value> {
+    let name_component = client.brp_version().name_component();
+    let mut chain = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if !visited.insert(entity) {
+            anyhow::bail!("ChildOf cycle: entity {} is its own ancestor", entity);
+        }
+        let mut result = client
+            .get_components(entity, vec![BEVY_CHILD_OF_COMPONENT, name_component])
+            .await?;
+        let components = &mut result["components"];
+
+        let mut link = json!({ "entity": entity });
+        let name = components[name_component].take();
+        if !name.is_null() {
+            link["name"] = name;
+        }
+        chain.push(link);
+        current = components[BEVY_CHILD_OF_COMPONENT].as_u64();
+    }
+    Ok(json!(chain))
+}
+
+/// `entity` and all of its descendants as nested `{entity, components, children}` objects
+///
+/// Each level of the tree is fetched with `list_entity`, `concurrency` entities at a time,
+/// before the next level is requested. An entity already in the tree is not fetched or
+/// nested again, so a `Children` cycle can't recurse forever.
+async fn subtree(
+    client: &RemoteClient,
+    entity: u64,
+    concurrency: usize,
+) -> Result<serde_json::Value> {
+    let mut nodes = HashMap::new();
+    let mut visited = HashSet::from([entity]);
+    let mut level = vec![entity];
+    while !level.is_empty() {
+        let fetched = fan_out(level.clone(), concurrency, |id| {
+            let client = client.clone();
+            async move { client.list_entity(id).await }
+        })
+        .await;
+
+        let mut next_level = Vec::new();
+        for (id, node) in level.into_iter().zip(fetched) {
+            let node = node
+                .ok_or_else(|| anyhow::anyhow!("Fetching entity {} panicked", id))?
+                .with_context(|| format!("Failed to fetch entity {} of the subtree", id))?;
+            for child in node["children"].as_array().into_iter().flatten() {
+                if let Some(child) = child.as_u64().filter(|child| visited.insert(*child)) {
+                    next_level.push(child);
+                }
+            }
+            nodes.insert(id, node);
+        }
+        level = next_level;
+    }
+    Ok(subtree_node(entity, &mut nodes))
+}
+
+/// Nest the fetched `list_entity` result of `entity` and, recursively, its children
+///
+/// Each node is taken out of `nodes` as it is placed, so it appears in the tree only once.
+fn subtree_node(entity: u64, nodes: &mut HashMap<u64, serde_json::Value>) -> serde_json::Value {
+    let Some(mut node) = nodes.remove(&entity) else {
+        return serde_json::Value::Null;
+    };
+    let child_ids: Vec<u64> = node["children"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|child| child.as_u64())
+        .collect();
+    let mut children = Vec::new();
+    for child in child_ids {
+        if nodes.contains_key(&child) {
+            children.push(subtree_node(child, nodes));
+        }
+    }
+    json!({
+        "entity": entity,
+        "components": node["components"].take(),
+        "children": children
+    })
+}
+
+/// Destroy an entity, and with `recursive` every descendant first, deepest first
+///
+/// Descendants are found by walking `Children` before anything is destroyed, so the result is
+/// the same whatever the server does with the children of a destroyed parent.
+async fn destroy_entity(
+    client: &RemoteClient,
+    entity: u64,
+    recursive: bool,
+    record_undo: bool,
+) -> Result<serde_json::Value> {
+    if !recursive {
+        return undo::destroy(client, entity, record_undo).await;
+    }
+
+    // Breadth-first, so every entity comes after its parent; destroying in reverse is bottom-up
+    let mut tree = vec![entity];
+    let mut seen = HashSet::from([entity]);
+    let mut next = 0;
+    while next < tree.len() {
+        let children = existing_component(client, tree[next], BEVY_CHILDREN_COMPONENT).await;
+        next += 1;
+        let children = children
+            .as_ref()
+            .and_then(|children| children.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|child| child.as_u64());
+        for child in children {
+            if seen.insert(child) {
+                tree.push(child);
+            }
+        }
+    }
+
+    let mut destroyed = Vec::new();
+    for &target in tree.iter().rev() {
+        undo::destroy(client, target, record_undo)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to destroy {} after destroying {:?}: {}",
+                    target,
+                    destroyed,
+                    e
+                )
+            })?;
+        destroyed.push(target);
+    }
+    Ok(json!({ "destroyed": destroyed }))
+}
+
+/// Insert a component, handling an existing one according to `mode`
+async fn insert_component(
+    client: &RemoteClient,
+    entity: u64,
+    component: &str,
+    data: serde_json::Value,
+    mode: InsertMode,
+) -> Result<serde_json::Value> {
+    let data = match mode {
+        InsertMode::Replace => data,
+        InsertMode::IfMissing => {
+            // A failed lookup counts as absent so the insert reports the real error
+            if existing_component(client, entity, component)
+                .await
+                .is_some()
+            {
+                return Ok(json!({
+                    "entity": entity,
+                    "component": component,
+                    "status": "already present",
+                }));
+            }
+            data
+        }
+        InsertMode::Merge => {
+            let Some(mut current) = existing_component(client, entity, component).await else {
+                anyhow::bail!(
+                    "Cannot merge into {}: entity {} does not have it",
+                    component,
+                    entity
+                );
+            };
+            merge_json(&mut current, data);
+            current
+        }
+    };
+    client.insert_component(entity, component, data).await
+}
+
+/// Resolve the entity ID for a command that can also read its entities from stdin
+fn require_entity(entity: Option<u64>, command_name: &str) -> Result<u64> {
+    entity.ok_or_else(|| anyhow::anyhow!("{} requires an entity ID or --from-stdin", command_name))
+}
+
+/// Resolve an entity argument already checked by `parse_entity_ref` to an entity ID
+///
+/// `name:<NAME>` must match exactly one entity's `Name`; otherwise the error lists
+/// what was found so the caller can pick an ID instead.
+async fn resolve_entity_ref(client: &RemoteClient, entity_ref: &str) -> Result<u64> {
+    let Some(name) = entity_ref.strip_prefix(ENTITY_NAME_PREFIX) else {
+        return Ok(entity_ref.parse()?);
+    };
+    let matches = client.entities_named(name).await?;
+    match matches.as_slice() {
+        [entity] => Ok(*entity),
+        [] => anyhow::bail!(
+            "No entity is named '{}' (query {} to see the names in use)",
+            name,
+            client.brp_version().name_component()
+        ),
+        _ => {
+            let ids: Vec<String> = matches.iter().map(|id| id.to_string()).collect();
+            anyhow::bail!(
+                "Name '{}' is ambiguous: entities {} all have it; use an entity ID instead",
+                name,
+                ids.join(", ")
+            )
+        }
+    }
+}
+
+/// Pick an unused `screenshot_<unix_millis>.<ext>` path in `dir`, creating `dir` if needed
+///
+/// The path is made absolute so the app writes the file where this process polls for it,
+/// even when the two run from different working directories.
+async fn timestamped_screenshot_path(dir: &str, format: Option<&str>) -> Result<String> {
+    let dir = std::path::absolute(dir)?;
+    fs::create_dir_all(&dir).await?;
+    let extension = match format {
+        Some("jpg" | "jpeg") => "jpg",
+        _ => "png",
+    };
+    let mut millis = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    loop {
+        let candidate = dir.join(format!("screenshot_{}.{}", millis, extension));
+        if !fs::try_exists(&candidate).await? {
+            return Ok(candidate.to_string_lossy().into_owned());
+        }
+        // Two captures in the same millisecond - take the next free name
+        millis += 1;
+    }
+}
+
+/// Load `raw` params JSON from `path`, or from stdin when no path is given
+async fn read_raw_params(path: Option<&str>) -> Result<serde_json::Value> {
+    let (input, source) = match path {
+        Some(path) => (
+            fs::read_to_string(path)
+                .await
+                .with_context(|| format!("Failed to read params file '{}'", path))?,
+            path,
+        ),
+        None => {
+            // Stdin reads block, so keep them off the runtime's worker threads
+            let input = tokio::task::spawn_blocking(|| {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input).map(|_| input)
+            })
+            .await?
+            .context("Failed to read params from stdin")?;
+            (input, "stdin")
+        }
+    };
+    serde_json::from_str(&input)
+        .with_context(|| format!("Params from {} are not valid JSON", source))
+}
+
+/// How `watch` handles and prints a stream
+#[derive(Debug, Clone, Default)]
+struct WatchOptions {
+    /// Request a stream that ends or fails again, up to this many times in total
+    reconnect_max: Option<u32>,
+    /// Skip the initial snapshot each stream starts with
+    changes_only: bool,
+    /// Print updates as NDJSON and leave the banners out
+    line: bool,
+    /// Consider the stream dead when nothing, not even an SSE comment, arrives for this long
+    keepalive: Option<Duration>,
+    /// Only print updates that satisfy this predicate
+    filter: Option<Assertion>,
+    /// Stop after this many updates, over all reconnects
+    max_events: Option<u64>,
+    /// Count only the updates that pass `filter` toward `max_events`
+    count_matching_only: bool,
+}
+
+/// Why a stream handled by `handle_stream_response` stopped
+#[derive(Debug, PartialEq)]
+enum StreamEnd {
+    /// The user pressed Ctrl+C
+    Interrupted,
+    /// The stream ended or failed on its own
+    Dropped,
+    /// `max_events` updates have been received
+    Finished,
+}
+
+/// Every entity in the world with the names of its component types
+///
+/// BRP doesn't have a direct "get all components for entity" method, so every registered
+/// component type is queried for the entities that have it, in batches of `concurrency` so a
+/// large registry doesn't overwhelm the app; with a concurrency of 1 the queries run one after
+/// another instead.
+async fn entity_component_types(
+    client: &RemoteClient,
+    concurrency: usize,
+) -> Result<HashMap<u64, Vec<String>>> {
+    // First, get all available component types
+    let component_types_result = client.list_entities().await?;
+    let mut component_types = Vec::new();
+
+    if let Some(types_array) = component_types_result.as_array() {
+        for component_type in types_array {
+            if let Some(type_name) = component_type.as_str() {
+                component_types.push(type_name.to_string());
+            }
+        }
+    }
+
+    // Now build a map of entity_id -> component_types
+    let mut entity_components_map: HashMap<u64, Vec<String>> = HashMap::new();
+
+    let queries = fan_out(component_types, concurrency, |component_type| {
+        let client = client.clone();
+        async move {
+            let result = client.query_entities(vec![&component_type]).await;
+            (component_type, result)
+        }
+    })
+    .await;
+
+    for (component_type, query_result) in queries.into_iter().flatten() {
+        let Ok(query_result) = query_result else {
+            continue;
+        };
+        if let Some(query_array) = query_result.as_array() {
+            for entity_data in query_array {
+                if let Some(entity_id) = entity_data.get("entity").and_then(|e| e.as_u64()) {
+                    entity_components_map
+                        .entry(entity_id)
+                        .or_default()
+                        .push(component_type.clone());
+                }
+            }
+        }
+    }
+    Ok(entity_components_map)
+}
+
+/// Every entity's component data and every resource's value, keyed by entity ID and resource
+/// type so that two snapshots line up in a `json_diff`
+///
+/// Entities despawned and resources that fail to serialize while the snapshot is taken are
+/// left out.
+async fn snapshot(client: &RemoteClient, concurrency: usize) -> Result<serde_json::Value> {
+    let mut entity_ids: Vec<u64> = entity_component_types(client, concurrency)
+        .await?
+        .into_keys()
+        .collect();
+    entity_ids.sort_unstable();
+    let fetched = fan_out(entity_ids.clone(), concurrency, |entity_id| {
+        let client = client.clone();
+        async move { client.list_entity(entity_id).await }
+    })
+    .await;
+    let entities: serde_json::Map<String, serde_json::Value> = entity_ids
+        .into_iter()
+        .zip(fetched)
+        .filter_map(|(entity_id, fetched)| match fetched {
+            Some(Ok(mut result)) => Some((entity_id.to_string(), result["components"].take())),
+            _ => None,
+        })
+        .collect();
+
+    let resource_types: Vec<String> = client
+        .call_brp_method(BEVY_LIST_RESOURCES, serde_json::Value::Null)
+        .await?
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|resource| resource.as_str().map(str::to_string))
+        .collect();
+    let fetched = fan_out(resource_types.clone(), concurrency, |resource| {
+        let client = client.clone();
+        async move {
+            let params = client.params().resource(&resource).build();
+            client.call_brp_method(BEVY_GET_RESOURCE, params).await
+        }
+    })
+    .await;
+    let resources: serde_json::Map<String, serde_json::Value> = resource_types
+        .into_iter()
+        .zip(fetched)
+        .filter_map(|(resource, fetched)| match fetched {
+            Some(Ok(mut result)) => Some((resource, result["value"].take())),
+            _ => None,
+        })
+        .collect();
+
+    Ok(json!({
+        "entities": entities,
+        "resources": resources
+    }))
+}
+
+/// Print a stream status message, on stderr in `--line` mode so stdout stays pure NDJSON
+fn stream_notice(line: bool, message: &str) {
+    if line {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
 
 /// Handle a streaming response with Ctrl+C interruption support
+///
+/// With `line`, each update is printed as one compact JSON line without spacing. Updates
+/// failing `filter` are dropped silently; `events` counts the updates seen so far toward
+/// `max_events`, and carries over between the streams of a reconnecting watch.
 async fn handle_stream_response(
     mut stream: impl StreamExt<Item = Result<serde_json::Value, anyhow::Error>> + Unpin,
-    entity_msg: &str,
-) -> Result<()> {
-    println!(
-        "Streaming component changes for {} (press Ctrl+C to stop):",
-        entity_msg
-    );
-
-    // Set up Ctrl+C handler
-    let ctrl_c = tokio::signal::ctrl_c();
-    tokio::pin!(ctrl_c);
+    ctrl_c: &mut (impl Future<Output = std::io::Result<()>> + Unpin),
+    options: &WatchOptions,
+    events: &mut u64,
+) -> Result<StreamEnd> {
+    let WatchOptions {
+        changes_only,
+        line,
+        filter,
+        max_events,
+        count_matching_only,
+        ..
+    } = options;
+    let line = *line;
 
-    println!("[Waiting for updates... Press Ctrl+C to stop]\n");
+    // The first value is the current state rather than a change
+    let mut skip_snapshot = *changes_only;
 
     // Process stream until Ctrl+C
     loop {
         tokio::select! {
-            _ = &mut ctrl_c => {
-                println!("\n[Stream interrupted by user]");
-                break;
+            _ = &mut *ctrl_c => {
+                stream_notice(line, "\n[Stream interrupted by user]");
+                return Ok(StreamEnd::Interrupted);
             }
             update = stream.next() => {
                 match update {
+                    Some(Ok(_)) if skip_snapshot => skip_snapshot = false,
                     Some(Ok(value)) => {
-                        print_json(&value)?;
-                        println!(); // Add spacing between updates
+                        let matches = filter
+                            .as_ref()
+                            .is_none_or(|filter| filter.check(&value).is_ok());
+                        if matches && line {
+                            print_json_line(&value)?;
+                        } else if matches {
+                            print_json(&value)?;
+                            println!(); // Add spacing between updates
+                        }
+
+                        if matches || !count_matching_only {
+                            *events += 1;
+                        }
+                        if max_events.is_some_and(|max| *events >= max) {
+                            stream_notice(line, &format!("[Stopped after {} updates]", events));
+                            return Ok(StreamEnd::Finished);
+                        }
                     }
                     Some(Err(e)) => {
                         eprintln!("Stream error: {}", e);
-                        break;
+                        return Ok(StreamEnd::Dropped);
                     }
                     None => {
-                        println!("[Stream ended]");
-                        break;
+                        stream_notice(line, "[Stream ended]");
+                        return Ok(StreamEnd::Dropped);
                     }
                 }
             }
         }
     }
+}
+
+/// Feed a recorded SSE byte stream through the watch parser and printer, as if it came from
+/// a live `+watch` request
+///
+/// The file is delivered in small chunks so events, lines and characters get split the way
+/// network reads split them.
+pub async fn replay_sse(path: &Path) -> Result<()> {
+    let recorded = fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read SSE recording {}", path.display()))?;
+    let chunks: Vec<Result<bytes::Bytes, std::convert::Infallible>> = recorded
+        .chunks(SSE_REPLAY_CHUNK_BYTES)
+        .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+        .collect();
+    let stream = parse_sse_stream(tokio_stream::iter(chunks));
 
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    handle_stream_response(stream, &mut ctrl_c, &WatchOptions::default(), &mut 0).await?;
     Ok(())
 }
 
+/// Stream updates from `method` until Ctrl+C, or until `max_events` updates have arrived
+///
+/// With `reconnect_max`, a stream that ends, fails or receives nothing for `keepalive` is
+/// requested again after a backoff, up to that many times in total. With `changes_only`,
+/// each stream's initial snapshot is skipped, including the one a reconnected stream starts
+/// with. With `line`, the banners are left out and updates are printed as NDJSON.
+async fn watch(
+    client: &RemoteClient,
+    method: &str,
+    params: serde_json::Value,
+    description: &str,
+    options: WatchOptions,
+) -> Result<()> {
+    let (reconnect_max, line) = (options.reconnect_max, options.line);
+
+    let keepalive = options.keepalive;
+    let mut stream = client
+        .stream_request(method, params.clone(), keepalive)
+        .await?;
+    if !line {
+        println!("Streaming {} (press Ctrl+C to stop):", description);
+    }
+
+    // A single Ctrl+C handler so it also interrupts the reconnect backoff
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    if !line {
+        println!("[Waiting for updates... Press Ctrl+C to stop]\n");
+    }
+
+    let mut attempts = 0;
+    let mut events = 0;
+    loop {
+        if handle_stream_response(stream, &mut ctrl_c, &options, &mut events).await?
+            != StreamEnd::Dropped
+        {
+            return Ok(());
+        }
+
+        stream = loop {
+            let Some(max) = reconnect_max else {
+                return Ok(());
+            };
+            if attempts >= max {
+                eprintln!("[Giving up after {} reconnect attempts]", attempts);
+                return Ok(());
+            }
+            attempts += 1;
+
+            let delay = reconnect_delay(attempts);
+            stream_notice(
+                line,
+                &format!(
+                    "[reconnecting... attempt {}/{} in {} ms]",
+                    attempts,
+                    max,
+                    delay.as_millis()
+                ),
+            );
+            tokio::select! {
+                _ = &mut ctrl_c => {
+                    stream_notice(line, "\n[Stream interrupted by user]");
+                    return Ok(());
+                }
+                _ = sleep(delay) => {}
+            }
+
+            // A dropped stream often means the app restarted, so its types may have changed
+            client.invalidate_cache();
+            match client
+                .stream_request(method, params.clone(), keepalive)
+                .await
+            {
+                Ok(stream) => break stream,
+                Err(e) => eprintln!("Reconnect failed: {}", e),
+            }
+        };
+    }
+}
+
+/// Exponential backoff before reconnect attempt `attempt` (starting at 1)
+fn reconnect_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    let delay_ms = WATCH_RECONNECT_BASE_DELAY_MS.saturating_mul(1 << exponent);
+    Duration::from_millis(delay_ms.min(WATCH_RECONNECT_MAX_DELAY_MS))
+}
+
+/// The question to ask before running `command` against `client`, if it needs one
+///
+/// Only a command typed at a terminal is confirmed, so scripts, pipes and command lists
+/// never block; `yes` answers every question in advance.
+pub fn pending_confirmation(
+    command: &Commands,
+    client: &RemoteClient,
+    yes: bool,
+) -> Option<String> {
+    if yes || !std::io::stdin().is_terminal() {
+        return None;
+    }
+    confirmation_prompt(command, client.port())
+}
+
+/// The question to ask before running `command`, if it is disruptive enough to need one
+fn confirmation_prompt(command: &Commands, port: u16) -> Option<String> {
+    match command {
+        Commands::Shutdown => Some(format!("Shut down the app on port {}?", port)),
+        Commands::Destroy {
+            entity: Some(entity),
+            recursive: true,
+            ..
+        } => Some(format!(
+            "Destroy entity {} and all of its descendants?",
+            entity
+        )),
+        _ => None,
+    }
+}
+
 /// Execute a command in standalone mode (app already running)
-pub async fn execute_standalone_command(client: &RemoteClient, command: Commands) -> Result<()> {
+pub async fn execute_standalone_command(
+    client: &RemoteClient,
+    command: Commands,
+    options: ExecutionOptions,
+) -> Result<()> {
+    // Replay journals its commands one by one, so the replay itself isn't recorded
+    let journal_entry =
+        (!matches!(command, Commands::Replay { .. })).then(|| format_command(command.clone()));
+
+    // Only the outermost command checks `--assert` and `--expect`, not the commands a
+    // replay runs
+    let assertion = take_assertion();
+    let expected_output = take_expected_output();
+    if assertion.is_some() || expected_output.is_some() {
+        capture_output();
+    }
+
+    let result = run_command(client, command, options).await;
+    if let Some(entry) = journal_entry {
+        record_command(&entry, result.is_ok());
+    }
+    result?;
+
+    if assertion.is_none() && expected_output.is_none() {
+        return Ok(());
+    }
+    let flag = if assertion.is_some() {
+        "--assert"
+    } else {
+        "--expect"
+    };
+    let output = take_captured_output()
+        .ok_or_else(|| anyhow::anyhow!("{} needs a command that prints a JSON result", flag))?;
+    if let Some(assertion) = assertion {
+        assertion.check(&output)?;
+    }
+    if let Some(path) = expected_output {
+        check_expected_output(&path, &output)?;
+    }
+    Ok(())
+}
+
+/// Run a single parsed command against the app
+async fn run_command(
+    client: &RemoteClient,
+    mut command: Commands,
+    options: ExecutionOptions,
+) -> Result<()> {
+    aliases::expand_type_aliases(&mut command);
+    if options.strict_types {
+        validate::check_strict_types(&command)?;
+    }
+
     // Wait for app to be ready before executing any command
     // Exceptions:
     // - Ready command (to avoid circular dependency)
     // - Workflows command (just displays help text, no app interaction)
+    // - Config and validate_json commands (only read local input)
+    // - Kill command (the app it targets may be wedged)
+    // - --no-ready-check (caller knows the app is up and wants to fail fast)
     match &command {
-        Commands::Ready => {
-            // This command doesn't need app readiness check
+        Commands::Ready | Commands::Health => {
+            // These commands report readiness themselves
         }
+        Commands::Config { .. } | Commands::Kill | Commands::ValidateJson { .. } => {}
+        _ if options.skip_ready_check => {}
         _ => {
-            wait_for_app_ready(client).await?;
+            wait_for_app_ready(client, options.poll_interval).await?;
         }
     }
 
     match command {
-        Commands::Destroy { entity } => {
-            let result = client.destroy_entity(entity).await?;
-            print_json(&result)?;
-        }
+        Commands::Clone {
+            entity,
+            count,
+            ids_only,
+        } => {
+            let count = count.unwrap_or(1);
+            if count == 0 {
+                anyhow::bail!("clone --count must be at least 1");
+            }
 
-        Commands::Get { entity, component } => {
-            let result = client.get_component(entity, &component).await?;
-            // Extract just the component data from the result
-            if let Some(components) = result.get("components") {
-                if let Some(component_data) = components.get(&component) {
-                    print_json(component_data)?;
-                } else {
-                    print_json(&result)?;
+            let source = client.list_entity(entity).await?;
+            let mut components = source
+                .get("components")
+                .and_then(|c| c.as_object())
+                .cloned()
+                .unwrap_or_default();
+
+            // Children is derived from each child's ChildOf - copying it would steal the
+            // source entity's children, so it is never cloned
+            let mut skipped: Vec<String> = components
+                .remove(BEVY_CHILDREN_COMPONENT)
+                .map(|_| BEVY_CHILDREN_COMPONENT.to_string())
+                .into_iter()
+                .collect();
+
+            let mut entities = Vec::new();
+            for _ in 0..count {
+                let (new_entity, failed) = client.spawn_clone(&components).await?;
+                // Drop failed components so later clones can spawn in a single request
+                for component_type in failed {
+                    components.remove(&component_type);
+                    skipped.push(component_type);
                 }
+                entities.push(new_entity);
+            }
+
+            if !skipped.is_empty() {
+                eprintln!(
+                    "Warning: skipped components that could not be cloned: {}",
+                    skipped.join(", ")
+                );
+            }
+
+            if ids_only {
+                print_json_line(&json!(entities))?;
             } else {
+                print_json(&json!({
+                    "source": entity,
+                    "entities": entities,
+                    "skipped_components": skipped,
+                }))?;
+            }
+        }
+
+        Commands::Config { action } => match action {
+            ConfigAction::Path => match config_path() {
+                Some(path) => println!("{}", path.display()),
+                None => anyhow::bail!("Could not determine the config directory"),
+            },
+        },
+
+        Commands::Destroy {
+            entity,
+            from_stdin,
+            recursive,
+        } => {
+            if from_stdin {
+                let entities = read_entity_ids_from_stdin().await?;
+                run_for_each_entity(&entities, "Destroy", options, |target| {
+                    destroy_entity(client, target, recursive, options.record_undo)
+                })
+                .await?;
+            } else {
+                let entity = require_entity(entity, "destroy")?;
+                let result = destroy_entity(client, entity, recursive, options.record_undo).await?;
                 print_json(&result)?;
             }
         }
 
-        Commands::GetResource { resource } => {
+        Commands::Ancestors { entity } => {
+            let chain = ancestors(client, entity).await?;
+            print_json(&chain)?;
+        }
+
+        Commands::Subtree { entity } => {
+            let concurrency = options
+                .max_concurrency
+                .unwrap_or(LIST_ENTITIES_DEFAULT_CONCURRENCY);
+            let tree = subtree(client, entity, concurrency).await?;
+            print_json(&tree)?;
+        }
+
+        Commands::Exists { entity } => {
+            let exists = client.entity_exists(entity).await?;
+            print_json(&json!({ "exists": exists }))?;
+        }
+
+        Commands::Get {
+            entity,
+            components,
+            from_stdin,
+            retry_on_empty,
+            timeout,
+        } => {
+            let components: Vec<&str> = components.iter().map(|s| s.as_str()).collect();
+
+            if from_stdin {
+                let entities = read_entity_ids_from_stdin().await?;
+                let components = &components;
+                run_for_each_entity(&entities, "Get", options, |target| async move {
+                    let result = client.get_components(target, components.clone()).await?;
+                    Ok(component_data(result, components))
+                })
+                .await?;
+                return Ok(());
+            }
+
+            let entity = require_entity(entity, "get")?;
+            let result = if retry_on_empty {
+                get_when_present(client, entity, &components, timeout, options.poll_interval)
+                    .await?
+            } else {
+                client.get_components(entity, components.clone()).await?
+            };
+            print_json(&component_data(result, &components))?;
+        }
+
+        Commands::GetResource { resource, field } => {
             let result = client
                 .call_brp_method(
                     BEVY_GET_RESOURCE,
-                    RpcParamsBuilder::new().resource(resource).build(),
+                    client.params().resource(&resource).build(),
                 )
                 .await?;
-            print_json(&result)?;
+            match field {
+                Some(field) => print_json(&resource_field(&result, &resource, &field)?)?,
+                None => print_json(&result)?,
+            }
         }
 
-        Commands::GetWatch { entity, components } => {
-            // Start streaming
+        Commands::GetResourceWatch {
+            resource,
+            interval,
+            changes_only,
+            line,
+        } => {
+            let interval = interval.unwrap_or(RESOURCE_WATCH_DEFAULT_INTERVAL_MS);
+            if interval == 0 {
+                anyhow::bail!("get_resource+watch --interval must be at least 1 ms");
+            }
+            // BRP has no resource watch method, so poll and emit only changed values
+            let stream = client.poll_resource(&resource, Duration::from_millis(interval));
+            if !line {
+                println!(
+                    "Polling resource {} every {} ms (press Ctrl+C to stop):",
+                    resource, interval
+                );
+            }
+
+            let ctrl_c = tokio::signal::ctrl_c();
+            tokio::pin!(ctrl_c);
+
+            if !line {
+                println!("[Waiting for updates... Press Ctrl+C to stop]\n");
+            }
+            let watch_options = WatchOptions {
+                changes_only,
+                line,
+                ..WatchOptions::default()
+            };
+            handle_stream_response(stream, &mut ctrl_c, &watch_options, &mut 0).await?;
+        }
+
+        Commands::GetWatch {
+            entity,
+            components,
+            reconnect,
+            reconnect_max,
+            changes_only,
+            line,
+            filter,
+            max_events,
+            count_matching_only,
+        } => {
             let components_refs: Vec<&str> = components.iter().map(|s| s.as_str()).collect();
-            let method = BEVY_GET_WATCH;
-            let stream = client
-                .stream_request(
-                    method,
-                    RpcParamsBuilder::new()
-                        .entity(entity)
-                        .component_list(components_refs)
-                        .build(),
-                )
-                .await?;
+            let params = client
+                .params()
+                .entity(entity)
+                .component_list(components_refs)
+                .build();
+            let reconnect_max =
+                reconnect.then(|| reconnect_max.unwrap_or(WATCH_RECONNECT_DEFAULT_MAX));
 
-            // Use the common stream handler
-            handle_stream_response(stream, &format!("entity {}", entity)).await?;
+            watch(
+                client,
+                BEVY_GET_WATCH,
+                params,
+                &format!("component changes for entity {}", entity),
+                WatchOptions {
+                    reconnect_max,
+                    changes_only,
+                    line,
+                    keepalive: options.keepalive_interval,
+                    filter,
+                    max_events,
+                    count_matching_only,
+                },
+            )
+            .await?;
         }
 
-        Commands::Insert { entity, components } => {
+        Commands::Insert {
+            entity,
+            components,
+            entities,
+            if_missing,
+            merge,
+        } => {
             let obj = parse_json_object(&components, "Insert")?;
-            for (component_type, component_data) in obj {
-                let result = client
-                    .insert_component(entity, &component_type, component_data)
+            let mode = if merge {
+                InsertMode::Merge
+            } else if if_missing {
+                InsertMode::IfMissing
+            } else {
+                InsertMode::Replace
+            };
+
+            let Some(entities) = entities else {
+                let entity = entity
+                    .ok_or_else(|| anyhow::anyhow!("insert requires an entity ID or --entities"))?;
+                for (component_type, component_data) in obj {
+                    let result =
+                        insert_component(client, entity, &component_type, component_data, mode)
+                            .await?;
+                    print_json(&result)?;
+                }
+                return Ok(());
+            };
+
+            // Apply the same components to every entity, reporting each outcome
+            let obj = &obj;
+            run_for_each_entity(&entities, "Insert", options, |target| async move {
+                let mut result = serde_json::Value::Null;
+                for (component_type, component_data) in obj {
+                    result = insert_component(
+                        client,
+                        target,
+                        component_type,
+                        component_data.clone(),
+                        mode,
+                    )
                     .await?;
-                print_json(&result)?;
-            }
+                }
+                Ok(result)
+            })
+            .await?;
+        }
+
+        Commands::ValidateJson { kind, json } => {
+            let result = validate::validate_payload(kind, &json)?;
+            print_json(&result)?;
+        }
+
+        Commands::Kill => {
+            let result = detached::kill_session(client.port())?;
+            print_json(&result)?;
         }
 
         Commands::InsertResource { data } => {
@@ -145,16 +1426,16 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             }
         }
 
-        Commands::List => {
+        Commands::List { no_sort, .. } => {
             let result = client.list_entities().await?;
-            print_json(&result)?;
+            print_json(&sort_type_names(result, !no_sort))?;
         }
 
-        Commands::ListResources => {
+        Commands::ListResources { no_sort, .. } => {
             let result = client
                 .call_brp_method(BEVY_LIST_RESOURCES, serde_json::Value::Null)
                 .await?;
-            print_json(&result)?;
+            print_json(&sort_type_names(result, !no_sort))?;
         }
 
         Commands::ListEntity { entity } => {
@@ -162,122 +1443,222 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             print_json(&result)?;
         }
 
-        Commands::ListEntities => {
-            // BRP doesn't have a direct "get all components for entity" method
-            // We'll use a different approach: get all component types, then query for each type
-            // This is more comprehensive than trying to get components per entity
-
-            // First, get all available component types
-            let component_types_result = client.list_entities().await?;
-            let mut component_types = Vec::new();
-
-            if let Some(types_array) = component_types_result.as_array() {
-                for component_type in types_array {
-                    if let Some(type_name) = component_type.as_str() {
-                        component_types.push(type_name.to_string());
-                    }
+        Commands::Snapshot { baseline } => {
+            // Read the baseline first, so a bad path fails before the world is captured
+            let baseline = match baseline {
+                Some(path) => {
+                    let text = fs::read_to_string(&path)
+                        .await
+                        .with_context(|| format!("Failed to read baseline snapshot {}", path))?;
+                    let baseline: serde_json::Value = serde_json::from_str(&text)
+                        .with_context(|| format!("Baseline {} is not valid JSON", path))?;
+                    Some(baseline)
                 }
-            }
-
-            // Now build a map of entity_id -> component_types
-            let mut entity_components_map: std::collections::HashMap<u64, Vec<String>> =
-                std::collections::HashMap::new();
-
-            // Query for component types in parallel using tokio::spawn
-            // We'll process them in batches to avoid overwhelming the system
-            const BATCH_SIZE: usize = 10;
+                None => None,
+            };
 
-            for chunk in component_types.chunks(BATCH_SIZE) {
-                let mut tasks = Vec::new();
+            let concurrency = options
+                .max_concurrency
+                .unwrap_or(LIST_ENTITIES_DEFAULT_CONCURRENCY);
+            let current = snapshot(client, concurrency).await?;
+            match baseline {
+                Some(baseline) => print_json(&json_diff(&baseline, &current))?,
+                None => print_json(&current)?,
+            }
+        }
 
-                // Spawn tasks for this batch
-                for component_type in chunk {
-                    let client = client.clone();
-                    let component_type = component_type.clone();
+        Commands::ListEntities {
+            with_data, jsonl, ..
+        } => {
+            let concurrency = options
+                .max_concurrency
+                .unwrap_or(LIST_ENTITIES_DEFAULT_CONCURRENCY);
+            // Sort by entity ID for consistent output
+            let mut rows: Vec<(u64, Vec<String>)> = entity_component_types(client, concurrency)
+                .await?
+                .into_iter()
+                .collect();
+            rows.sort_unstable_by_key(|(entity_id, _)| *entity_id);
 
-                    let task = tokio::spawn(async move {
-                        let result = client.query_entities(vec![&component_type]).await;
-                        (component_type, result)
-                    });
+            if jsonl {
+                // Print a batch at a time, so with --with-data the first rows appear before
+                // the data of the rest has been fetched and only one batch of data is held
+                let mut rows = rows.into_iter().peekable();
+                while rows.peek().is_some() {
+                    let batch: Vec<(u64, Vec<String>)> =
+                        rows.by_ref().take(concurrency.max(1)).collect();
+                    let mut data = if with_data {
+                        let entity_ids: Vec<u64> =
+                            batch.iter().map(|(entity_id, _)| *entity_id).collect();
+                        fan_out(entity_ids, concurrency, |entity_id| {
+                            let client = client.clone();
+                            async move { client.list_entity(entity_id).await }
+                        })
+                        .await
+                    } else {
+                        Vec::new()
+                    }
+                    .into_iter();
 
-                    tasks.push(task);
+                    for (entity_id, component_names) in batch {
+                        let mut entity = entity_row(entity_id, component_names);
+                        if with_data {
+                            entity["data"] = entity_data(data.next().flatten());
+                        }
+                        print_json_line(&entity)?;
+                    }
                 }
+            } else {
+                let mut entities: Vec<serde_json::Value> = rows
+                    .into_iter()
+                    .map(|(entity_id, component_names)| entity_row(entity_id, component_names))
+                    .collect();
 
-                // Wait for all tasks in this batch to complete
-                for task in tasks {
-                    if let Ok((component_type, Ok(query_result))) = task.await {
-                        if let Some(query_array) = query_result.as_array() {
-                            for entity_data in query_array {
-                                if let Some(entity_id) =
-                                    entity_data.get("entity").and_then(|e| e.as_u64())
-                                {
-                                    entity_components_map
-                                        .entry(entity_id)
-                                        .or_default()
-                                        .push(component_type.clone());
-                                }
-                            }
-                        }
+                if with_data {
+                    // Fetch each entity's data with the same concurrency as the queries above
+                    let entity_ids: Vec<u64> = entities
+                        .iter()
+                        .map(|entity| entity["entity"].as_u64().unwrap_or(0))
+                        .collect();
+                    let data = fan_out(entity_ids, concurrency, |entity_id| {
+                        let client = client.clone();
+                        async move { client.list_entity(entity_id).await }
+                    })
+                    .await;
+
+                    for (entity, data) in entities.iter_mut().zip(data) {
+                        entity["data"] = entity_data(data);
                     }
                 }
-            }
 
-            // Convert to the expected output format
-            let mut entities = Vec::new();
-            for (entity_id, component_names) in entity_components_map {
-                // Calculate generation from entity ID (upper 32 bits)
-                let generation = (entity_id >> 32) as u32;
-
-                entities.push(json!({
-                    "entity": entity_id,
-                    "generation": generation,
-                    "components": component_names
-                }));
-            }
+                let result = json!({
+                    "entities": entities,
+                    "total_count": entities.len()
+                });
 
-            // Sort by entity ID for consistent output
-            entities.sort_by(|a, b| {
-                let a_id = a.get("entity").and_then(|v| v.as_u64()).unwrap_or(0);
-                let b_id = b.get("entity").and_then(|v| v.as_u64()).unwrap_or(0);
-                a_id.cmp(&b_id)
-            });
+                print_json(&result)?;
+            }
+        }
 
-            let result = json!({
-                "entities": entities,
-                "total_count": entities.len()
-            });
+        Commands::ListWatch {
+            entity,
+            reconnect,
+            reconnect_max,
+            changes_only,
+            line,
+            filter,
+            max_events,
+            count_matching_only,
+        } => {
+            let reconnect_max =
+                reconnect.then(|| reconnect_max.unwrap_or(WATCH_RECONNECT_DEFAULT_MAX));
 
-            print_json(&result)?;
+            watch(
+                client,
+                BEVY_LIST_WATCH,
+                client.params().entity(entity).build(),
+                &format!("component changes for entity {}", entity),
+                WatchOptions {
+                    reconnect_max,
+                    changes_only,
+                    line,
+                    keepalive: options.keepalive_interval,
+                    filter,
+                    max_events,
+                    count_matching_only,
+                },
+            )
+            .await?;
         }
 
-        Commands::ListWatch { entity } => {
-            // Start streaming
-            let method = BEVY_LIST_WATCH;
-            let stream = client
-                .stream_request(method, RpcParamsBuilder::new().entity(entity).build())
-                .await?;
+        Commands::Health => {
+            // An empty query matches every entity
+            let (ready, methods, entities, resources) = tokio::join!(
+                client.is_ready(),
+                client.call_brp_method(RPC_DISCOVER, serde_json::Value::Null),
+                client.query_entities(vec![]),
+                client.call_brp_method(BEVY_LIST_RESOURCES, serde_json::Value::Null),
+            );
+
+            let mut errors = serde_json::Map::new();
+            let responsive = match ready {
+                Ok(ready) => ready,
+                Err(e) => {
+                    errors.insert("ready".to_string(), json!(e.to_string()));
+                    false
+                }
+            };
+            let method_count = health_count(
+                "methods",
+                methods,
+                |v| v.get("methods").and_then(|m| m.as_array()).map(Vec::len),
+                &mut errors,
+            );
+            let entity_count = health_count(
+                "entities",
+                entities,
+                |v| v.as_array().map(Vec::len),
+                &mut errors,
+            );
+            let resource_count = health_count(
+                "resources",
+                resources,
+                |v| v.as_array().map(Vec::len),
+                &mut errors,
+            );
 
-            // Use the common stream handler
-            handle_stream_response(stream, &format!("entity {}", entity)).await?;
+            print_json(&json!({
+                "responsive": responsive,
+                "method_count": method_count,
+                "entity_count": entity_count,
+                "resource_count": resource_count,
+                "errors": errors,
+            }))?;
         }
 
-        Commands::Methods => {
-            let result = client
-                .call_brp_method("rpc.discover", serde_json::Value::Null)
+        Commands::Methods { grep, group } => {
+            let mut result = client
+                .call_brp_method(RPC_DISCOVER, serde_json::Value::Null)
                 .await?;
-            print_json(&result)?;
+            if let Some(methods) = result.get_mut("methods") {
+                // The table is always grouped, so grouping is left to it
+                let group = group && !table_output();
+                *methods = filter_methods(methods.take(), grep.as_deref(), group);
+            }
+            if table_output() {
+                print_text(&methods_table(&result["methods"]))?;
+            } else {
+                print_json(&result)?;
+            }
         }
 
         Commands::MutateComponent {
             entity,
             component,
             patch,
+            from_stdin,
         } => {
             let patch_value = parse_json_value(&patch)?;
-            let result = client
-                .mutate_component(entity, &component, patch_value)
+            if from_stdin {
+                let entities = read_entity_ids_from_stdin().await?;
+                let (component, patch_value) = (component.as_str(), &patch_value);
+                run_for_each_entity(&entities, "Mutate", options, |target| {
+                    undo::mutate(
+                        client,
+                        target,
+                        component,
+                        patch_value.clone(),
+                        options.record_undo,
+                    )
+                })
                 .await?;
-            print_json(&result)?;
+            } else {
+                let entity = require_entity(entity, "mutate_component")?;
+                let result =
+                    undo::mutate(client, entity, &component, patch_value, options.record_undo)
+                        .await?;
+                print_json(&result)?;
+            }
         }
 
         Commands::MutateResource { resource, patch } => {
@@ -286,10 +1667,29 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             print_json(&result)?;
         }
 
-        Commands::Query { components } => {
+        Commands::Query {
+            components,
+            limit,
+            offset,
+            select,
+            fail_if_empty,
+        } => {
+            let selections = parse_selections(&select, &components)?;
             let components: Vec<&str> = components.iter().map(|s| s.as_str()).collect();
-            let result = client.query_entities(components).await?;
+            let mut result = client.query_entities(components).await?;
+            let empty = result
+                .as_array()
+                .is_some_and(|entities| entities.is_empty());
+            if limit.is_some() || offset.is_some() {
+                result = paginate(result, limit, offset);
+            }
+            if !selections.is_empty() {
+                project_query_result(&mut result, &selections);
+            }
             print_json(&result)?;
+            if fail_if_empty && empty {
+                return Err(EmptyQueryError.into());
+            }
         }
 
         Commands::Ready => {
@@ -305,32 +1705,56 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             print_json(&response)?;
         }
 
-        Commands::Remove { entity, component } => {
-            let result = client.remove_component(entity, &component).await?;
-            print_json(&result)?;
+        Commands::Remove {
+            entity,
+            component,
+            from_stdin,
+        } => {
+            if from_stdin {
+                let entities = read_entity_ids_from_stdin().await?;
+                let component = component.as_str();
+                run_for_each_entity(&entities, "Remove", options, |target| {
+                    undo::remove(client, target, component, options.record_undo)
+                })
+                .await?;
+            } else {
+                let entity = require_entity(entity, "remove")?;
+                let result = undo::remove(client, entity, &component, options.record_undo).await?;
+                print_json(&result)?;
+            }
         }
 
         Commands::RemoveResource { resource } => {
             let result = client
                 .call_brp_method(
                     BEVY_REMOVE_RESOURCE,
-                    RpcParamsBuilder::new().resource(resource).build(),
+                    client.params().resource(resource).build(),
                 )
                 .await?;
             print_json(&result)?;
         }
 
-        Commands::Reparent { child, parent } => {
+        Commands::Reparent {
+            child,
+            parent,
+            children,
+        } => {
+            let children = match (children, child) {
+                (Some(children), _) => children,
+                (None, Some(child)) => vec![resolve_entity_ref(client, &child).await?],
+                (None, None) => anyhow::bail!("reparent requires a child ID or --children"),
+            };
             let parent_value = if parent == "null" {
                 serde_json::Value::Null
             } else {
-                json!(parent.parse::<u64>()?)
+                json!(resolve_entity_ref(client, &parse_entity_ref(&parent)?).await?)
             };
             let result = client
                 .call_brp_method(
                     BEVY_REPARENT,
-                    RpcParamsBuilder::new()
-                        .entities(vec![child])
+                    client
+                        .params()
+                        .entities(children)
                         .parent(parent_value)
                         .build(),
                 )
@@ -338,13 +1762,78 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             print_json(&result)?;
         }
 
-        Commands::Screenshot { path } => {
-            let mut result = client.take_screenshot(&path).await?;
+        Commands::Screenshot {
+            path,
+            output_dir,
+            region,
+            image_format,
+            quality,
+            inline,
+        } => {
+            // clap splits the value on commas, so only the count is left to check
+            if region.as_ref().is_some_and(|region| region.len() != 4) {
+                anyhow::bail!("Invalid region: expected x,y,width,height");
+            }
+            let path = match output_dir {
+                Some(dir) => {
+                    Some(timestamped_screenshot_path(&dir, image_format.as_deref()).await?)
+                }
+                None => path,
+            };
+            let mut result = client
+                .take_screenshot(
+                    path.as_deref(),
+                    region.as_deref(),
+                    image_format.as_deref(),
+                    quality,
+                    inline,
+                )
+                .await?;
+
+            let poll_duration = options.poll_interval.unwrap_or(Duration::from_millis(100));
+            let timeout_duration = Duration::from_secs(5);
+
+            if inline {
+                // The image comes back over RPC, so there is no file to wait for
+                let capture_id = result
+                    .get("capture_id")
+                    .and_then(|id| id.as_u64())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("Server did not return a screenshot capture_id")
+                    })?;
+
+                let data = timeout(timeout_duration, async {
+                    loop {
+                        let response = client.screenshot_data(capture_id).await?;
+                        if response.get("ready").and_then(|r| r.as_bool()) == Some(true) {
+                            return Ok::<_, anyhow::Error>(response);
+                        }
+                        sleep(poll_duration).await;
+                    }
+                })
+                .await
+                .map_err(|_| {
+                    anyhow::anyhow!("Inline screenshot was not captured within 5 seconds")
+                })??;
+
+                if let Some(obj) = result.as_object_mut() {
+                    obj.insert("data".to_string(), data["data"].clone());
+                    obj.insert("size".to_string(), data["size"].clone());
+                    obj.insert(
+                        "note".to_string(),
+                        json!("Screenshot returned inline as base64."),
+                    );
+                }
+                print_json(&result)?;
+                return Ok(());
+            }
+
+            let path = path.ok_or_else(|| {
+                anyhow::anyhow!("screenshot requires a file path, --output-dir or --inline")
+            })?;
 
             // Poll for the file to be written with non-zero size
             let file_path = Path::new(&path);
-            let poll_duration = Duration::from_millis(100);
-            let timeout_duration = Duration::from_secs(5);
 
             let poll_result = timeout(timeout_duration, async {
                 loop {
@@ -389,9 +1878,37 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             print_json(&result)?;
         }
 
-        Commands::Spawn { components } => {
-            let json_value = parse_json_value(&components)?;
+        Commands::Spawn {
+            components,
+            with_name,
+            force,
+            id_only,
+        } => {
+            let mut json_value = parse_json_value(&components)?;
+            if let Some(name) = with_name {
+                let name_component = options.brp_version.name_component();
+                let Some(obj) = json_value.as_object_mut() else {
+                    anyhow::bail!("spawn --with-name requires a JSON object");
+                };
+                if obj.contains_key(name_component) && !force {
+                    anyhow::bail!(
+                        "The JSON already contains a {} component; use --force to replace it with '{}'",
+                        name_component,
+                        name
+                    );
+                }
+                obj.insert(name_component.to_string(), json!(name));
+            }
             let result = client.spawn_entity(json_value).await?;
+            if id_only {
+                print_json_line(&result["entity"])?;
+            } else {
+                print_json(&result)?;
+            }
+        }
+
+        Commands::Describe { type_name } => {
+            let result = describe::describe_type(client, &type_name).await?;
             print_json(&result)?;
         }
 
@@ -400,6 +1917,8 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
             without_crates,
             with_types,
             without_types,
+            out,
+            gzip,
         } => {
             let mut params = serde_json::Map::new();
 
@@ -416,20 +1935,147 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
                 params.insert("without_types".to_string(), json!(types));
             }
 
-            let result = client
-                .call_brp_method(BEVY_REGISTRY_SCHEMA, json!(params))
+            let result = client.registry_schema(json!(params)).await?;
+            match out {
+                Some(path) => {
+                    let size = write_json_file(Path::new(&path), &result, gzip)?;
+                    print_json(&json!({
+                        "path": path,
+                        "size": size,
+                        "gzip": gzip
+                    }))?;
+                }
+                None => print_json(&result)?,
+            }
+        }
+
+        Commands::Capabilities => {
+            let discovered = client
+                .call_brp_method(RPC_DISCOVER, serde_json::Value::Null)
                 .await?;
+            print_json(&capabilities::classify_methods(&discovered))?;
+        }
+
+        Commands::Bench { method, iterations } => {
+            let method = method.as_deref().unwrap_or(BEVY_LIST);
+            let iterations = iterations.unwrap_or(BENCH_DEFAULT_ITERATIONS);
+            let result = bench::bench_method(client, method, iterations).await?;
             print_json(&result)?;
         }
 
-        Commands::Raw { args } => {
+        Commands::Replay { path } => {
+            let entries = read_journal(Path::new(&path))?;
+            let commands: Vec<&str> = entries
+                .iter()
+                .filter(|entry| entry.succeeded)
+                .map(|entry| entry.command.as_str())
+                .collect();
+            println!(
+                "Replaying {} commands from {} ({} failed entries skipped)",
+                commands.len(),
+                path,
+                entries.len() - commands.len()
+            );
+
+            // Like a command list, a replayed session can undo its own changes
+            let options = ExecutionOptions {
+                record_undo: true,
+                ..options
+            };
+            let total = commands.len();
+            let mut failures = Vec::new();
+            for (index, command) in commands.iter().enumerate() {
+                println!("\n=== Replaying: {} ===", command);
+                // Boxed because executing a command can itself be a replay
+                if let Err(e) = Box::pin(execute_command(client, command, options)).await {
+                    let failure = format!(
+                        "Command {} of {} ('{}') failed: {}",
+                        index + 1,
+                        total,
+                        command,
+                        e
+                    );
+                    if !options.continue_on_error {
+                        anyhow::bail!(failure);
+                    }
+                    eprintln!("Error: {}", failure);
+                    failures.push(failure);
+                }
+            }
+            summarize_failures(&failures, total)?;
+        }
+
+        Commands::Undo => {
+            let result = undo::undo_last(client).await?;
+            print_json(&result)?;
+        }
+
+        Commands::WaitFor {
+            components,
+            timeout,
+        } => {
+            let timeout_secs = timeout.unwrap_or(WAIT_FOR_DEFAULT_TIMEOUT_SECS);
+            let components: Vec<&str> = components.iter().map(|s| s.as_str()).collect();
+
+            // Keep the response that ended the wait so we don't have to query again. A failed
+            // query, such as a refused connection or a rejected token, ends the wait at once
+            // instead of being retried until the timeout.
+            let outcome = std::sync::Mutex::new(None);
+
+            poll_until_ready(
+                || async {
+                    let result = client.query_entities(components.clone()).await;
+                    let empty = result.as_ref().is_ok_and(|result| {
+                        result.as_array().is_none_or(|entities| entities.is_empty())
+                    });
+                    if empty {
+                        anyhow::bail!("No matching entities yet");
+                    }
+                    *outcome.lock().unwrap() = Some(result);
+                    Ok(())
+                },
+                Duration::from_secs(timeout_secs),
+                options
+                    .poll_interval
+                    .unwrap_or(Duration::from_millis(WAIT_FOR_POLL_INTERVAL_MS)),
+                format!(
+                    "Timed out after {} seconds waiting for entities with: {}",
+                    timeout_secs,
+                    components.join(", ")
+                ),
+            )
+            .await?;
+
+            if let Some(result) = outcome.into_inner().unwrap() {
+                print_json(&result?)?;
+            }
+        }
+
+        Commands::Raw {
+            stream,
+            params_file,
+            params_stdin,
+            args,
+        } => {
             // Raw commands are method calls that go directly to the server
             if args.is_empty() {
                 anyhow::bail!("Raw command requires at least a method name");
             }
 
             let method = &args[0];
-            let params = if args.len() > 1 {
+            let params = if params_file.is_some() || params_stdin {
+                if args.len() > 1 {
+                    anyhow::bail!(
+                        "Raw params come from {} - pass only the method name as an argument",
+                        if params_stdin {
+                            "stdin"
+                        } else {
+                            "--params-file"
+                        }
+                    );
+                }
+                read_raw_params(params_file.as_deref()).await?
+            } else if args.len() > 1 {
                 // Try to parse remaining args as JSON
                 let remaining = args[1..].join(" ");
                 if remaining.trim().is_empty() {
@@ -447,10 +2093,95 @@ pub async fn execute_standalone_command(client: &RemoteClient, command: Commands
                 serde_json::Value::Null
             };
 
-            let result = client.call_brp_method(method, params).await?;
-            print_json(&result)?;
+            if stream {
+                watch(
+                    client,
+                    method,
+                    params,
+                    &format!("responses from {}", method),
+                    WatchOptions {
+                        keepalive: options.keepalive_interval,
+                        ..WatchOptions::default()
+                    },
+                )
+                .await?;
+            } else {
+                let result = client.call_brp_method(method, params).await?;
+                print_json(&result)?;
+            }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fetched `list_entity` result with a marker component and the given children
+    fn fetched_node(entity: u64, children: &[u64]) -> serde_json::Value {
+        json!({
+            "entity": entity,
+            "children": children,
+            "components": { "test::Marker": entity }
+        })
+    }
+
+    #[test]
+    fn test_subtree_node_nests_grandchildren() {
+        let mut nodes = HashMap::from([
+            (1, fetched_node(1, &[2, 3])),
+            (2, fetched_node(2, &[4])),
+            (3, fetched_node(3, &[])),
+            (4, fetched_node(4, &[])),
+        ]);
+
+        let tree = subtree_node(1, &mut nodes);
+
+        assert_eq!(
+            tree,
+            json!({
+                "entity": 1,
+                "components": { "test::Marker": 1 },
+                "children": [
+                    {
+                        "entity": 2,
+                        "components": { "test::Marker": 2 },
+                        "children": [
+                            { "entity": 4, "components": { "test::Marker": 4 }, "children": [] }
+                        ]
+                    },
+                    { "entity": 3, "components": { "test::Marker": 3 }, "children": [] }
+                ]
+            })
+        );
+        assert!(nodes.is_empty(), "every node should be placed exactly once");
+    }
+
+    #[test]
+    fn test_subtree_node_places_cyclic_child_once() {
+        let mut nodes = HashMap::from([(1, fetched_node(1, &[2])), (2, fetched_node(2, &[1]))]);
+
+        let tree = subtree_node(1, &mut nodes);
+
+        assert_eq!(tree["children"][0]["entity"], 2);
+        assert_eq!(tree["children"][0]["children"], json!([]));
+    }
+
+    #[test]
+    fn test_pending_confirmation_names_client_port() {
+        let client = RemoteClient::new(15790);
+
+        // `--yes` answers in advance
+        assert_eq!(
+            pending_confirmation(&Commands::Shutdown, &client, true),
+            None
+        );
+        assert_eq!(
+            confirmation_prompt(&Commands::Shutdown, client.port()),
+            Some("Shut down the app on port 15790?".to_string())
+        );
+        assert_eq!(confirmation_prompt(&Commands::Ready, client.port()), None);
+    }
+}
@@ -1,9 +1,17 @@
+mod aliases;
+mod bench;
+mod capabilities;
 mod cli;
+mod describe;
 mod execution;
 mod parsing;
 mod types;
+mod undo;
+mod validate;
 
 pub use cli::Cli;
 pub use execution::*;
-pub use parsing::{extract_command_from_error, format_command, parse_command_string};
+pub use parsing::{
+    extract_command_from_error, format_command, parse_command_string, split_command_list,
+};
 pub use types::{CommandTemplate, Commands, commands_by_category, find_command_by_name};
@@ -3,9 +3,9 @@ use std::str::FromStr;
 
 use anyhow::Result;
 
-use super::types::Commands;
+use super::types::{Commands, ConfigAction, PayloadKind};
 use crate::cli::constants::BIN_NAME;
-use crate::cli::support::parse_entity_arg;
+use crate::cli::support::{Assertion, parse_assertion, parse_entity_arg, parse_entity_ref};
 
 /// Parse a string command into a Commands enum
 pub fn parse_command_string(command: &str) -> Result<Commands> {
@@ -34,48 +34,458 @@ pub fn extract_command_from_error(error_msg: &str) -> Option<String> {
     None
 }
 
+/// Split a comma-separated command list, preserving commas inside JSON objects
+pub fn split_command_list(input: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut current_command = String::new();
+    let mut in_json = false;
+    let mut brace_count = 0;
+    let mut in_string = false;
+    let mut escape_next = false;
+
+    for ch in input.chars() {
+        if escape_next {
+            current_command.push(ch);
+            escape_next = false;
+            continue;
+        }
+
+        match ch {
+            '\\' if in_json => {
+                current_command.push(ch);
+                escape_next = true;
+            }
+            '"' if in_json => {
+                current_command.push(ch);
+                if !escape_next {
+                    in_string = !in_string;
+                }
+            }
+            '{' if !in_string => {
+                in_json = true;
+                brace_count += 1;
+                current_command.push(ch);
+            }
+            '}' if !in_string && in_json => {
+                brace_count -= 1;
+                current_command.push(ch);
+                if brace_count == 0 {
+                    in_json = false;
+                }
+            }
+            ',' if !in_json => {
+                if !current_command.trim().is_empty() {
+                    commands.push(current_command.trim().to_string());
+                }
+                current_command.clear();
+            }
+            _ => {
+                current_command.push(ch);
+            }
+        }
+    }
+
+    // Don't forget the last command
+    if !current_command.trim().is_empty() {
+        commands.push(current_command.trim().to_string());
+    }
+
+    commands
+}
+
+/// Shell-quote `value` when it has spaces or quotes, so it reads back as one argument
+fn quoted(value: &str) -> std::result::Result<std::borrow::Cow<'_, str>, fmt::Error> {
+    shlex::try_quote(value).map_err(|_| fmt::Error)
+}
+
+/// Read the argument at `args[start]`, rejoining a value that `quoted` wrapped in quotes,
+/// and return it with the number of words it spans
+///
+/// Commands are split on whitespace, so a quoted value arrives as several words.
+fn quoted_arg(args: &[&str], start: usize) -> Result<(String, usize)> {
+    let first = args[start];
+    if !first.starts_with(['\'', '"']) {
+        return Ok((first.to_string(), 1));
+    }
+    for end in start + 1..=args.len() {
+        if let Some([value]) = shlex::split(&args[start..end].join(" ")).as_deref() {
+            return Ok((value.clone(), end - start));
+        }
+    }
+    anyhow::bail!("Unterminated quote in '{}'", args[start..].join(" "))
+}
+
+/// Format an entity argument, which is replaced by `--from-stdin` when entities are piped in
+fn entity_arg(entity: &Option<u64>, from_stdin: bool) -> String {
+    match entity {
+        Some(entity) if !from_stdin => entity.to_string(),
+        _ => "--from-stdin".to_string(),
+    }
+}
+
+/// Write the `--sort`/`--no-sort` flags of the `list` commands, if either was given
+fn write_sort_flags(f: &mut fmt::Formatter<'_>, sort: bool, no_sort: bool) -> fmt::Result {
+    if sort {
+        write!(f, " --sort")?;
+    }
+    if no_sort {
+        write!(f, " --no-sort")?;
+    }
+    Ok(())
+}
+
+/// Write the `--reconnect`/`--reconnect-max`/`--changes-only`/`--line` flags of the streaming
+/// watch commands, if given
+fn write_watch_flags(
+    f: &mut fmt::Formatter<'_>,
+    reconnect: bool,
+    reconnect_max: Option<u32>,
+    changes_only: bool,
+    line: bool,
+) -> fmt::Result {
+    if reconnect {
+        write!(f, " --reconnect")?;
+    }
+    if let Some(max) = reconnect_max {
+        write!(f, " --reconnect-max {}", max)?;
+    }
+    if changes_only {
+        write!(f, " --changes-only")?;
+    }
+    if line {
+        write!(f, " --line")?;
+    }
+    Ok(())
+}
+
+/// Write the `--filter`/`--max-events`/`--count-matching-only` flags of the streaming watch
+/// commands, if given
+fn write_watch_event_flags(
+    f: &mut fmt::Formatter<'_>,
+    filter: Option<&Assertion>,
+    max_events: Option<u64>,
+    count_matching_only: bool,
+) -> fmt::Result {
+    if let Some(filter) = filter {
+        write!(f, " --filter {}", quoted(&filter.to_string())?)?;
+    }
+    if let Some(max) = max_events {
+        write!(f, " --max-events {}", max)?;
+    }
+    if count_matching_only {
+        write!(f, " --count-matching-only")?;
+    }
+    Ok(())
+}
+
 impl fmt::Display for Commands {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Commands::Destroy { entity } => write!(f, "destroy {}", entity),
-            Commands::Get { entity, component } => write!(f, "get {} {}", entity, component),
-            Commands::GetResource { resource } => write!(f, "get_resource {}", resource),
-            Commands::GetWatch { entity, components } => {
-                write!(f, "get+watch {} {}", entity, components.join(" "))
+            Commands::Bench { method, iterations } => {
+                write!(f, "bench")?;
+                if let Some(method) = method {
+                    write!(f, " {}", method)?;
+                }
+                if let Some(iterations) = iterations {
+                    write!(f, " --iterations {}", iterations)?;
+                }
+                Ok(())
             }
-            Commands::Insert { entity, components } => {
-                write!(f, "insert {} {}", entity, components)
+            Commands::Capabilities => write!(f, "capabilities"),
+            Commands::Clone {
+                entity,
+                count,
+                ids_only,
+            } => {
+                write!(f, "clone {}", entity)?;
+                if let Some(count) = count {
+                    write!(f, " --count {}", count)?;
+                }
+                if *ids_only {
+                    write!(f, " --ids-only")?;
+                }
+                Ok(())
+            }
+            Commands::Destroy {
+                entity,
+                from_stdin,
+                recursive,
+            } => {
+                write!(f, "destroy ")?;
+                if *recursive {
+                    write!(f, "--recursive ")?;
+                }
+                write!(f, "{}", entity_arg(entity, *from_stdin))
+            }
+            Commands::Ancestors { entity } => write!(f, "ancestors {}", entity),
+            Commands::Subtree { entity } => write!(f, "subtree {}", entity),
+            Commands::Exists { entity } => write!(f, "exists {}", entity),
+            Commands::Get {
+                entity,
+                components,
+                from_stdin,
+                retry_on_empty,
+                timeout,
+            } => {
+                write!(
+                    f,
+                    "get {} {}",
+                    entity_arg(entity, *from_stdin),
+                    components.join(" ")
+                )?;
+                if *retry_on_empty {
+                    write!(f, " --retry-on-empty")?;
+                }
+                if let Some(seconds) = timeout {
+                    write!(f, " --timeout {}", seconds)?;
+                }
+                Ok(())
+            }
+            Commands::GetResource { resource, field } => {
+                write!(f, "get_resource")?;
+                if let Some(field) = field {
+                    write!(f, " --field {}", field)?;
+                }
+                write!(f, " {}", resource)
+            }
+            Commands::GetResourceWatch {
+                resource,
+                interval,
+                changes_only,
+                line,
+            } => {
+                write!(f, "get_resource+watch")?;
+                if let Some(interval) = interval {
+                    write!(f, " --interval {}", interval)?;
+                }
+                if *changes_only {
+                    write!(f, " --changes-only")?;
+                }
+                if *line {
+                    write!(f, " --line")?;
+                }
+                write!(f, " {}", resource)
+            }
+            Commands::GetWatch {
+                entity,
+                components,
+                reconnect,
+                reconnect_max,
+                changes_only,
+                line,
+                filter,
+                max_events,
+                count_matching_only,
+            } => {
+                write!(f, "get+watch {} {}", entity, components.join(" "))?;
+                write_watch_flags(f, *reconnect, *reconnect_max, *changes_only, *line)?;
+                write_watch_event_flags(f, filter.as_ref(), *max_events, *count_matching_only)
+            }
+            Commands::Insert {
+                entity,
+                components,
+                entities,
+                if_missing,
+                merge,
+            } => {
+                write!(f, "insert")?;
+                if *if_missing {
+                    write!(f, " --if-missing")?;
+                }
+                if *merge {
+                    write!(f, " --merge")?;
+                }
+                if let Some(entity) = entity {
+                    write!(f, " {}", entity)?;
+                }
+                if let Some(entities) = entities {
+                    let ids: Vec<String> = entities.iter().map(|e| e.to_string()).collect();
+                    write!(f, " --entities {}", ids.join(","))?;
+                }
+                write!(f, " {}", components)
             }
             Commands::InsertResource { data } => write!(f, "insert_resource {}", data),
-            Commands::List => write!(f, "list"),
-            Commands::ListResources => write!(f, "list_resources"),
-            Commands::ListEntities => write!(f, "list_entities"),
+            Commands::Kill => write!(f, "kill"),
+            Commands::List { sort, no_sort } => {
+                write!(f, "list")?;
+                write_sort_flags(f, *sort, *no_sort)
+            }
+            Commands::ListResources { sort, no_sort } => {
+                write!(f, "list_resources")?;
+                write_sort_flags(f, *sort, *no_sort)
+            }
+            Commands::ListEntities {
+                components_only,
+                with_data,
+                jsonl,
+            } => {
+                write!(f, "list_entities")?;
+                if *components_only {
+                    write!(f, " --components-only")?;
+                }
+                if *with_data {
+                    write!(f, " --with-data")?;
+                }
+                if *jsonl {
+                    write!(f, " --jsonl")?;
+                }
+                Ok(())
+            }
             Commands::ListEntity { entity } => write!(f, "list_entity {}", entity),
-            Commands::ListWatch { entity } => write!(f, "list+watch {}", entity),
-            Commands::Methods => write!(f, "methods"),
+            Commands::Snapshot { baseline } => {
+                write!(f, "snapshot")?;
+                if let Some(path) = baseline {
+                    write!(f, " --baseline {}", path)?;
+                }
+                Ok(())
+            }
+            Commands::ListWatch {
+                entity,
+                reconnect,
+                reconnect_max,
+                changes_only,
+                line,
+                filter,
+                max_events,
+                count_matching_only,
+            } => {
+                write!(f, "list+watch {}", entity)?;
+                write_watch_flags(f, *reconnect, *reconnect_max, *changes_only, *line)?;
+                write_watch_event_flags(f, filter.as_ref(), *max_events, *count_matching_only)
+            }
+            Commands::Config { action } => write!(f, "config {}", action),
+            Commands::Health => write!(f, "health"),
+            Commands::Methods { grep, group } => {
+                write!(f, "methods")?;
+                if let Some(grep) = grep {
+                    write!(f, " --grep {}", grep)?;
+                }
+                if *group {
+                    write!(f, " --group")?;
+                }
+                Ok(())
+            }
             Commands::MutateComponent {
                 entity,
                 component,
                 patch,
-            } => {
-                write!(f, "mutate_component {} {} {}", entity, component, patch)
-            }
+                from_stdin,
+            } => write!(
+                f,
+                "mutate_component {} {} {}",
+                entity_arg(entity, *from_stdin),
+                component,
+                patch
+            ),
             Commands::MutateResource { resource, patch } => {
                 write!(f, "mutate_resource {} {}", resource, patch)
             }
-            Commands::Query { components } => write!(f, "query {}", components.join(" ")),
+            Commands::Query {
+                components,
+                limit,
+                offset,
+                select,
+                fail_if_empty,
+            } => {
+                write!(f, "query {}", components.join(" "))?;
+                if let Some(limit) = limit {
+                    write!(f, " --limit {}", limit)?;
+                }
+                if let Some(offset) = offset {
+                    write!(f, " --offset {}", offset)?;
+                }
+                for field in select {
+                    write!(f, " --select {}", field)?;
+                }
+                if *fail_if_empty {
+                    write!(f, " --fail-if-empty")?;
+                }
+                Ok(())
+            }
             Commands::Ready => write!(f, "ready"),
-            Commands::Remove { entity, component } => write!(f, "remove {} {}", entity, component),
+            Commands::Remove {
+                entity,
+                component,
+                from_stdin,
+            } => write!(
+                f,
+                "remove {} {}",
+                entity_arg(entity, *from_stdin),
+                component
+            ),
             Commands::RemoveResource { resource } => write!(f, "remove_resource {}", resource),
-            Commands::Reparent { child, parent } => write!(f, "reparent {} {}", child, parent),
-            Commands::Screenshot { path } => write!(f, "screenshot {}", path),
+            Commands::Reparent {
+                child,
+                parent,
+                children,
+            } => {
+                write!(f, "reparent")?;
+                if let Some(child) = child {
+                    write!(f, " {}", child)?;
+                }
+                if let Some(children) = children {
+                    let ids: Vec<String> = children.iter().map(|c| c.to_string()).collect();
+                    write!(f, " --children {}", ids.join(","))?;
+                }
+                write!(f, " {}", parent)
+            }
+            Commands::Screenshot {
+                path,
+                output_dir,
+                region,
+                image_format,
+                quality,
+                inline,
+            } => {
+                write!(f, "screenshot")?;
+                if let Some(path) = path {
+                    write!(f, " {}", path)?;
+                }
+                if let Some(output_dir) = output_dir {
+                    write!(f, " --output-dir {}", output_dir)?;
+                }
+                if *inline {
+                    write!(f, " --inline")?;
+                }
+                if let Some(region) = region {
+                    let values: Vec<String> = region.iter().map(|v| v.to_string()).collect();
+                    write!(f, " --region {}", values.join(","))?;
+                }
+                if let Some(image_format) = image_format {
+                    write!(f, " --image-format {}", image_format)?;
+                }
+                if let Some(quality) = quality {
+                    write!(f, " --quality {}", quality)?;
+                }
+                Ok(())
+            }
             Commands::Shutdown => write!(f, "shutdown"),
-            Commands::Spawn { components } => write!(f, "spawn {}", components),
+            Commands::Spawn {
+                components,
+                with_name,
+                force,
+                id_only,
+            } => {
+                write!(f, "spawn")?;
+                if let Some(name) = with_name {
+                    write!(f, " --with-name {}", quoted(name)?)?;
+                }
+                if *force {
+                    write!(f, " --force")?;
+                }
+                if *id_only {
+                    write!(f, " --id-only")?;
+                }
+                write!(f, " {}", components)
+            }
+            Commands::Describe { type_name } => write!(f, "describe {}", type_name),
             Commands::Schema {
                 with_crates,
                 without_crates,
                 with_types,
                 without_types,
+                out,
+                gzip,
             } => {
                 let mut parts = vec!["schema".to_string()];
                 if let Some(crates) = with_crates {
@@ -90,9 +500,48 @@ impl fmt::Display for Commands {
                 if let Some(types) = without_types {
                     parts.push(format!("--without-types {}", types.join(" ")));
                 }
+                if let Some(path) = out {
+                    parts.push(format!("--out {}", path));
+                }
+                if *gzip {
+                    parts.push("--gzip".to_string());
+                }
                 write!(f, "{}", parts.join(" "))
             }
-            Commands::Raw { args } => write!(f, "{}", args.join(" ")),
+            Commands::Replay { path } => write!(f, "replay {}", path),
+            Commands::Undo => write!(f, "undo"),
+            Commands::ValidateJson { kind, json } => write!(f, "validate_json {} {}", kind, json),
+            Commands::WaitFor {
+                components,
+                timeout,
+            } => {
+                write!(f, "wait_for {}", components.join(" "))?;
+                if let Some(seconds) = timeout {
+                    write!(f, " --timeout {}", seconds)?;
+                }
+                Ok(())
+            }
+            Commands::Raw {
+                stream,
+                params_file,
+                params_stdin,
+                args,
+            } => {
+                if !*stream && params_file.is_none() && !*params_stdin {
+                    return write!(f, "{}", args.join(" "));
+                }
+                write!(f, "raw")?;
+                if *stream {
+                    write!(f, " --stream")?;
+                }
+                if let Some(path) = params_file {
+                    write!(f, " --params-file {}", path)?;
+                }
+                if *params_stdin {
+                    write!(f, " --params-stdin")?;
+                }
+                write!(f, " {}", args.join(" "))
+            }
         }
     }
 }
@@ -126,6 +575,131 @@ impl FromStr for Commands {
             args.iter().map(|s| s.to_string()).collect()
         }
 
+        // Helper to parse `--sort`/`--no-sort`, where the last flag given wins
+        fn parse_sort_flags(args: &[&str], command_name: &str) -> Result<(bool, bool)> {
+            let mut flags = (false, false);
+            for arg in args {
+                flags = match *arg {
+                    "--sort" => (true, false),
+                    "--no-sort" => (false, true),
+                    other => anyhow::bail!("Unknown {} option '{}'", command_name, other),
+                };
+            }
+            Ok(flags)
+        }
+
+        // Flags of the streaming watch commands, as pulled out by `parse_watch_flags`
+        #[derive(Default)]
+        struct WatchFlags {
+            reconnect: bool,
+            reconnect_max: Option<u32>,
+            changes_only: bool,
+            line: bool,
+            filter: Option<Assertion>,
+            max_events: Option<u64>,
+            count_matching_only: bool,
+        }
+
+        // Helper to pull the `--reconnect`, `--changes-only`, `--filter` etc. flags out of a
+        // streaming watch command's args
+        fn parse_watch_flags<'a>(
+            args: &[&'a str],
+            command_name: &str,
+        ) -> Result<(Vec<&'a str>, WatchFlags)> {
+            let mut rest = Vec::new();
+            let mut flags = WatchFlags::default();
+
+            let mut i = 0;
+            while i < args.len() {
+                match args[i] {
+                    "--reconnect" => {
+                        flags.reconnect = true;
+                        i += 1;
+                    }
+                    "--changes-only" => {
+                        flags.changes_only = true;
+                        i += 1;
+                    }
+                    "--line" => {
+                        flags.line = true;
+                        i += 1;
+                    }
+                    "--count-matching-only" => {
+                        flags.count_matching_only = true;
+                        i += 1;
+                    }
+                    "--reconnect-max" => {
+                        let value = args.get(i + 1).ok_or_else(|| {
+                            anyhow::anyhow!("{} --reconnect-max requires a number", command_name)
+                        })?;
+                        flags.reconnect_max = Some(value.parse::<u32>().map_err(|_| {
+                            anyhow::anyhow!(
+                                "Invalid reconnect-max '{}': expected a positive integer",
+                                value
+                            )
+                        })?);
+                        i += 2;
+                    }
+                    "--max-events" => {
+                        let value = args.get(i + 1).ok_or_else(|| {
+                            anyhow::anyhow!("{} --max-events requires a number", command_name)
+                        })?;
+                        flags.max_events = Some(value.parse::<u64>().map_err(|_| {
+                            anyhow::anyhow!(
+                                "Invalid max-events '{}': expected a positive integer",
+                                value
+                            )
+                        })?);
+                        i += 2;
+                    }
+                    // Commands are split on whitespace, so the predicate is either quoted or
+                    // the next three words: POINTER OP VALUE
+                    "--filter" | "--watch-filter" => {
+                        let quoted_predicate = args
+                            .get(i + 1)
+                            .is_some_and(|first| first.starts_with(['\'', '"']));
+                        let (predicate, words) = if quoted_predicate {
+                            quoted_arg(args, i + 1)?
+                        } else {
+                            let Some(predicate) = args.get(i + 1..i + 4) else {
+                                anyhow::bail!(
+                                    "{} --filter requires a predicate: POINTER OP VALUE",
+                                    command_name
+                                );
+                            };
+                            (predicate.join(" "), 3)
+                        };
+                        flags.filter = Some(parse_assertion(&predicate)?);
+                        i += 1 + words;
+                    }
+                    arg => {
+                        rest.push(arg);
+                        i += 1;
+                    }
+                }
+            }
+
+            if flags.reconnect_max.is_some() && !flags.reconnect {
+                anyhow::bail!("{} --reconnect-max requires --reconnect", command_name);
+            }
+            if flags.count_matching_only && flags.max_events.is_none() {
+                anyhow::bail!(
+                    "{} --count-matching-only requires --max-events",
+                    command_name
+                );
+            }
+            Ok((rest, flags))
+        }
+
+        // A leading `--from-stdin` takes the place of the entity ID
+        fn parse_entity_or_stdin(args: &[&str]) -> Result<(Option<u64>, bool)> {
+            if args[0] == "--from-stdin" {
+                Ok((None, true))
+            } else {
+                Ok((Some(parse_entity_arg(args)?), false))
+            }
+        }
+
         let parts: Vec<&str> = command.split_whitespace().collect();
         if parts.is_empty() {
             anyhow::bail!("Empty command");
@@ -135,43 +709,293 @@ impl FromStr for Commands {
         let args = &parts[1..];
 
         match cmd_name {
+            "bench" => {
+                let mut method = None;
+                let mut iterations = None;
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--iterations" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("bench --iterations requires a number")
+                            })?;
+                            iterations = Some(value.parse::<u32>().map_err(|_| {
+                                anyhow::anyhow!(
+                                    "Invalid iterations '{}': expected a positive integer",
+                                    value
+                                )
+                            })?);
+                            i += 2;
+                        }
+                        name if method.is_none() => {
+                            method = Some(name.to_string());
+                            i += 1;
+                        }
+                        other => anyhow::bail!("Unexpected bench argument '{}'", other),
+                    }
+                }
+                Ok(Commands::Bench { method, iterations })
+            }
+            "capabilities" => Ok(Commands::Capabilities),
+            "clone" => {
+                let ids_only = args.contains(&"--ids-only");
+                let args: Vec<&str> = args
+                    .iter()
+                    .copied()
+                    .filter(|arg| *arg != "--ids-only")
+                    .collect();
+                validate_arg_count(&args, 1, "clone", "entity ID")?;
+                let count = match &args[1..] {
+                    ["--count", value] => Some(value.parse::<u64>().map_err(|_| {
+                        anyhow::anyhow!("Invalid count '{}': expected a positive integer", value)
+                    })?),
+                    [] => None,
+                    _ => anyhow::bail!(
+                        "clone accepts only an entity ID, '--count <N>' and '--ids-only'"
+                    ),
+                };
+                Ok(Commands::Clone {
+                    entity: parse_entity_arg(&args)?,
+                    count,
+                    ids_only,
+                })
+            }
+            "config" => {
+                validate_arg_count(args, 1, "config", "an action ('path')")?;
+                let action = match args[0] {
+                    "path" => ConfigAction::Path,
+                    other => anyhow::bail!("Unknown config action '{}': expected 'path'", other),
+                };
+                Ok(Commands::Config { action })
+            }
+            "describe" => {
+                validate_arg_count(args, 1, "describe", "type name")?;
+                Ok(Commands::Describe {
+                    type_name: join_args_from(args, 0),
+                })
+            }
             "destroy" => {
-                validate_arg_count(args, 1, "destroy", "entity ID")?;
+                let recursive = args.contains(&"--recursive");
+                let args: Vec<&str> = args
+                    .iter()
+                    .copied()
+                    .filter(|arg| *arg != "--recursive")
+                    .collect();
+                validate_arg_count(&args, 1, "destroy", "entity ID or --from-stdin")?;
+                let (entity, from_stdin) = parse_entity_or_stdin(&args)?;
                 Ok(Commands::Destroy {
+                    entity,
+                    from_stdin,
+                    recursive,
+                })
+            }
+            "ancestors" | "parents" => {
+                validate_arg_count(args, 1, "ancestors", "entity ID")?;
+                Ok(Commands::Ancestors {
+                    entity: parse_entity_arg(args)?,
+                })
+            }
+            "subtree" => {
+                validate_arg_count(args, 1, "subtree", "entity ID")?;
+                Ok(Commands::Subtree {
+                    entity: parse_entity_arg(args)?,
+                })
+            }
+            "exists" => {
+                validate_arg_count(args, 1, "exists", "entity ID")?;
+                Ok(Commands::Exists {
                     entity: parse_entity_arg(args)?,
                 })
             }
             "get" => {
-                validate_arg_count(args, 2, "get", "entity ID and component name")?;
+                let mut retry_on_empty = false;
+                let mut timeout = None;
+                let mut positional = Vec::new();
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--retry-on-empty" => {
+                            retry_on_empty = true;
+                            i += 1;
+                        }
+                        "--timeout" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("get --timeout requires a number of seconds")
+                            })?;
+                            timeout = Some(value.parse::<u64>().map_err(|_| {
+                                anyhow::anyhow!("Invalid timeout '{}': expected seconds", value)
+                            })?);
+                            i += 2;
+                        }
+                        arg => {
+                            positional.push(arg);
+                            i += 1;
+                        }
+                    }
+                }
+
+                validate_arg_count(&positional, 2, "get", "entity ID and component name")?;
+                let (entity, from_stdin) = parse_entity_or_stdin(&positional)?;
+                if timeout.is_some() && !retry_on_empty {
+                    anyhow::bail!("get --timeout requires --retry-on-empty");
+                }
+                if retry_on_empty && from_stdin {
+                    anyhow::bail!("get --retry-on-empty cannot be combined with --from-stdin");
+                }
                 Ok(Commands::Get {
-                    entity: parse_entity_arg(args)?,
-                    component: get_arg_string(args, 1),
+                    entity,
+                    components: args_to_strings(&positional[1..]),
+                    from_stdin,
+                    retry_on_empty,
+                    timeout,
                 })
             }
             "get_resource" => {
-                validate_arg_count(args, 1, "get_resource", "resource name")?;
+                let mut field = None;
+                let mut positional = Vec::new();
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--field" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("get_resource --field requires a JSON pointer")
+                            })?;
+                            field = Some(value.to_string());
+                            i += 2;
+                        }
+                        arg => {
+                            positional.push(arg);
+                            i += 1;
+                        }
+                    }
+                }
+
+                validate_arg_count(&positional, 1, "get_resource", "resource name")?;
                 Ok(Commands::GetResource {
-                    resource: join_args_from(args, 0),
+                    resource: join_args_from(&positional, 0),
+                    field,
+                })
+            }
+            "get_resource+watch" => {
+                let mut interval = None;
+                let mut resource = None;
+                let mut changes_only = false;
+                let mut line = false;
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--changes-only" => {
+                            changes_only = true;
+                            i += 1;
+                        }
+                        "--line" => {
+                            line = true;
+                            i += 1;
+                        }
+                        "--interval" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "get_resource+watch --interval requires milliseconds"
+                                )
+                            })?;
+                            interval = Some(value.parse::<u64>().map_err(|_| {
+                                anyhow::anyhow!(
+                                    "Invalid interval '{}': expected milliseconds",
+                                    value
+                                )
+                            })?);
+                            i += 2;
+                        }
+                        name if resource.is_none() => {
+                            resource = Some(name.to_string());
+                            i += 1;
+                        }
+                        other => {
+                            anyhow::bail!("Unexpected get_resource+watch argument '{}'", other)
+                        }
+                    }
+                }
+                let resource = resource.ok_or_else(|| {
+                    anyhow::anyhow!("get_resource+watch requires a resource name")
+                })?;
+                Ok(Commands::GetResourceWatch {
+                    resource,
+                    interval,
+                    changes_only,
+                    line,
                 })
             }
             "get+watch" => {
+                let (args, flags) = parse_watch_flags(args, "get+watch")?;
                 validate_arg_count(
-                    args,
+                    &args,
                     2,
                     "get+watch",
                     "entity ID and at least one component name",
                 )?;
                 Ok(Commands::GetWatch {
-                    entity: parse_entity_arg(args)?,
+                    entity: parse_entity_arg(&args)?,
                     components: args_to_strings(&args[1..]),
+                    reconnect: flags.reconnect,
+                    reconnect_max: flags.reconnect_max,
+                    changes_only: flags.changes_only,
+                    line: flags.line,
+                    filter: flags.filter,
+                    max_events: flags.max_events,
+                    count_matching_only: flags.count_matching_only,
                 })
             }
             "insert" => {
+                let mut if_missing = false;
+                let mut merge = false;
+                let mut args = args;
+                loop {
+                    match args {
+                        ["--if-missing" | "--insert-if-missing", rest @ ..] => {
+                            if_missing = true;
+                            args = rest;
+                        }
+                        ["--merge", rest @ ..] => {
+                            merge = true;
+                            args = rest;
+                        }
+                        _ => break,
+                    }
+                }
+                if if_missing && merge {
+                    anyhow::bail!("insert --if-missing and --merge cannot be used together");
+                }
                 validate_arg_count(args, 2, "insert", "entity ID and JSON object")?;
-                Ok(Commands::Insert {
-                    entity: parse_entity_arg(args)?,
-                    components: join_args_from(args, 1),
-                })
+                if args[0] == "--entities" {
+                    validate_arg_count(args, 3, "insert --entities", "entity IDs and JSON object")?;
+                    let entities = args[1]
+                        .split(',')
+                        .map(|id| {
+                            id.trim().parse::<u64>().map_err(|_| {
+                                anyhow::anyhow!("Invalid entity ID '{}' in --entities", id)
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(Commands::Insert {
+                        entity: None,
+                        components: join_args_from(args, 2),
+                        entities: Some(entities),
+                        if_missing,
+                        merge,
+                    })
+                } else {
+                    Ok(Commands::Insert {
+                        entity: Some(parse_entity_arg(args)?),
+                        components: join_args_from(args, 1),
+                        entities: None,
+                        if_missing,
+                        merge,
+                    })
+                }
             }
             "insert_resource" => {
                 validate_arg_count(args, 1, "insert_resource", "JSON object with resource data")?;
@@ -179,22 +1003,90 @@ impl FromStr for Commands {
                     data: join_args_from(args, 0),
                 })
             }
-            "list" => Ok(Commands::List),
-            "list_resources" => Ok(Commands::ListResources),
-            "list_entities" => Ok(Commands::ListEntities),
+            "kill" => Ok(Commands::Kill),
+            "list" => {
+                let (sort, no_sort) = parse_sort_flags(args, "list")?;
+                Ok(Commands::List { sort, no_sort })
+            }
+            "list_resources" => {
+                let (sort, no_sort) = parse_sort_flags(args, "list_resources")?;
+                Ok(Commands::ListResources { sort, no_sort })
+            }
+            "list_entities" => {
+                let mut components_only = false;
+                let mut with_data = false;
+                let mut jsonl = false;
+                for arg in args {
+                    match *arg {
+                        "--components-only" => components_only = true,
+                        "--with-data" => with_data = true,
+                        "--jsonl" => jsonl = true,
+                        other => anyhow::bail!("Unknown list_entities option '{}'", other),
+                    }
+                }
+                if components_only && with_data {
+                    anyhow::bail!(
+                        "list_entities --components-only and --with-data cannot be used together"
+                    );
+                }
+                Ok(Commands::ListEntities {
+                    components_only,
+                    with_data,
+                    jsonl,
+                })
+            }
             "list_entity" => {
                 validate_arg_count(args, 1, "list_entity", "entity ID")?;
                 Ok(Commands::ListEntity {
                     entity: parse_entity_arg(args)?,
                 })
             }
+            "snapshot" => match args {
+                [] => Ok(Commands::Snapshot { baseline: None }),
+                ["--baseline", path] => Ok(Commands::Snapshot {
+                    baseline: Some(path.to_string()),
+                }),
+                ["--baseline"] => anyhow::bail!("snapshot --baseline requires a path"),
+                _ => anyhow::bail!("Unexpected snapshot arguments '{}'", args.join(" ")),
+            },
             "list+watch" => {
-                validate_arg_count(args, 1, "list+watch", "entity ID")?;
+                let (args, flags) = parse_watch_flags(args, "list+watch")?;
+                validate_arg_count(&args, 1, "list+watch", "entity ID")?;
                 Ok(Commands::ListWatch {
-                    entity: parse_entity_arg(args)?,
+                    entity: parse_entity_arg(&args)?,
+                    reconnect: flags.reconnect,
+                    reconnect_max: flags.reconnect_max,
+                    changes_only: flags.changes_only,
+                    line: flags.line,
+                    filter: flags.filter,
+                    max_events: flags.max_events,
+                    count_matching_only: flags.count_matching_only,
                 })
             }
-            "methods" => Ok(Commands::Methods),
+            "health" => Ok(Commands::Health),
+            "methods" => {
+                let mut grep = None;
+                let mut group = false;
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--grep" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("methods --grep requires a substring")
+                            })?;
+                            grep = Some(value.to_string());
+                            i += 2;
+                        }
+                        "--group" => {
+                            group = true;
+                            i += 1;
+                        }
+                        other => anyhow::bail!("Unknown methods option '{}'", other),
+                    }
+                }
+                Ok(Commands::Methods { grep, group })
+            }
             "mutate_component" => {
                 validate_arg_count(
                     args,
@@ -202,10 +1094,12 @@ impl FromStr for Commands {
                     "mutate_component",
                     "entity ID, component name, and JSON patch",
                 )?;
+                let (entity, from_stdin) = parse_entity_or_stdin(args)?;
                 Ok(Commands::MutateComponent {
-                    entity: parse_entity_arg(args)?,
+                    entity,
                     component: get_arg_string(args, 1),
                     patch: join_args_from(args, 2),
+                    from_stdin,
                 })
             }
             "mutate_resource" => {
@@ -216,17 +1110,70 @@ impl FromStr for Commands {
                 })
             }
             "query" => {
-                validate_arg_count(args, 1, "query", "at least one component name")?;
+                let mut components = Vec::new();
+                let mut limit = None;
+                let mut offset = None;
+                let mut select = Vec::new();
+                let mut fail_if_empty = false;
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--fail-if-empty" => {
+                            fail_if_empty = true;
+                            i += 1;
+                        }
+                        "--select" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("query --select requires COMPONENT.PATH")
+                            })?;
+                            select.push(value.to_string());
+                            i += 2;
+                        }
+                        flag @ ("--limit" | "--offset") => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("query {} requires a number", flag)
+                            })?;
+                            let value = value.parse::<usize>().map_err(|_| {
+                                anyhow::anyhow!(
+                                    "Invalid {} '{}': expected a non-negative integer",
+                                    flag,
+                                    value
+                                )
+                            })?;
+                            if flag == "--limit" {
+                                limit = Some(value);
+                            } else {
+                                offset = Some(value);
+                            }
+                            i += 2;
+                        }
+                        component => {
+                            components.push(component.to_string());
+                            i += 1;
+                        }
+                    }
+                }
+
+                if components.is_empty() {
+                    anyhow::bail!("query requires at least one component name");
+                }
                 Ok(Commands::Query {
-                    components: args_to_strings(args),
+                    components,
+                    limit,
+                    offset,
+                    select,
+                    fail_if_empty,
                 })
             }
             "ready" => Ok(Commands::Ready),
             "remove" => {
                 validate_arg_count(args, 2, "remove", "entity ID and component name")?;
+                let (entity, from_stdin) = parse_entity_or_stdin(args)?;
                 Ok(Commands::Remove {
-                    entity: parse_entity_arg(args)?,
+                    entity,
                     component: get_arg_string(args, 1),
+                    from_stdin,
                 })
             }
             "remove_resource" => {
@@ -236,23 +1183,149 @@ impl FromStr for Commands {
                 })
             }
             "reparent" => {
-                validate_arg_count(args, 2, "reparent", "child ID and parent ID (or 'null')")?;
-                Ok(Commands::Reparent {
-                    child: parse_entity_arg(args)?,
-                    parent: get_arg_string(args, 1),
-                })
+                validate_arg_count(
+                    args,
+                    2,
+                    "reparent",
+                    "child (ID or name:<NAME>, or --children) and parent (ID, name:<NAME> or 'null')",
+                )?;
+                if args[0] == "--children" {
+                    validate_arg_count(
+                        args,
+                        3,
+                        "reparent --children",
+                        "child entity IDs and parent entity ID",
+                    )?;
+                    let children = args[1]
+                        .split(',')
+                        .map(|id| {
+                            id.trim().parse::<u64>().map_err(|_| {
+                                anyhow::anyhow!("Invalid entity ID '{}' in --children", id)
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(Commands::Reparent {
+                        child: None,
+                        parent: get_arg_string(args, 2),
+                        children: Some(children),
+                    })
+                } else {
+                    Ok(Commands::Reparent {
+                        child: Some(parse_entity_ref(args[0])?),
+                        parent: get_arg_string(args, 1),
+                        children: None,
+                    })
+                }
             }
             "screenshot" => {
-                validate_arg_count(args, 1, "screenshot", "file path")?;
+                validate_arg_count(args, 1, "screenshot", "file path, --output-dir or --inline")?;
+                let mut path_parts = Vec::new();
+                let mut output_dir = None;
+                let mut region = None;
+                let mut image_format = None;
+                let mut quality = None;
+                let mut inline = false;
+
+                let mut i = 0;
+                while i < args.len() {
+                    let flag = args[i];
+                    if flag == "--inline" {
+                        inline = true;
+                        i += 1;
+                        continue;
+                    }
+                    if !matches!(
+                        flag,
+                        "--output-dir" | "--region" | "--image-format" | "--quality"
+                    ) {
+                        path_parts.push(flag);
+                        i += 1;
+                        continue;
+                    }
+
+                    let value = args
+                        .get(i + 1)
+                        .ok_or_else(|| anyhow::anyhow!("screenshot {} requires a value", flag))?;
+                    match flag {
+                        "--output-dir" => output_dir = Some(value.to_string()),
+                        "--region" => {
+                            let values = value
+                                .split(',')
+                                .map(|v| v.trim().parse::<u32>())
+                                .collect::<std::result::Result<Vec<_>, _>>()
+                                .ok()
+                                .filter(|values| values.len() == 4)
+                                .ok_or_else(|| {
+                                    anyhow::anyhow!(
+                                        "Invalid region '{}': expected x,y,width,height",
+                                        value
+                                    )
+                                })?;
+                            region = Some(values);
+                        }
+                        "--image-format" => image_format = Some(value.to_string()),
+                        _ => {
+                            quality = Some(value.parse::<u8>().map_err(|_| {
+                                anyhow::anyhow!("Invalid quality '{}': expected 1-100", value)
+                            })?);
+                        }
+                    }
+                    i += 2;
+                }
+
+                if path_parts.is_empty() && output_dir.is_none() && !inline {
+                    anyhow::bail!("screenshot requires file path, --output-dir or --inline");
+                }
+                if !path_parts.is_empty() && output_dir.is_some() {
+                    anyhow::bail!("screenshot takes a file path or --output-dir, not both");
+                }
                 Ok(Commands::Screenshot {
-                    path: join_args_from(args, 0),
+                    path: (!path_parts.is_empty()).then(|| path_parts.join(" ")),
+                    output_dir,
+                    region,
+                    image_format,
+                    quality,
+                    inline,
                 })
             }
             "shutdown" => Ok(Commands::Shutdown),
             "spawn" => {
-                validate_arg_count(args, 1, "spawn", "JSON object with component data")?;
+                let mut with_name = None;
+                let mut force = false;
+                let mut id_only = false;
+
+                // Options come before the JSON, which takes the rest of the arguments
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--with-name" => {
+                            if i + 1 >= args.len() {
+                                anyhow::bail!("spawn --with-name requires a name");
+                            }
+                            let (name, words) = quoted_arg(args, i + 1)?;
+                            with_name = Some(name);
+                            i += 1 + words;
+                        }
+                        "--force" => {
+                            force = true;
+                            i += 1;
+                        }
+                        "--id-only" => {
+                            id_only = true;
+                            i += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                if force && with_name.is_none() {
+                    anyhow::bail!("spawn --force requires --with-name");
+                }
+                validate_arg_count(&args[i..], 1, "spawn", "JSON object with component data")?;
                 Ok(Commands::Spawn {
-                    components: join_args_from(args, 0),
+                    components: join_args_from(args, i),
+                    with_name,
+                    force,
+                    id_only,
                 })
             }
             "schema" => {
@@ -261,6 +1334,8 @@ impl FromStr for Commands {
                 let mut without_crates = None;
                 let mut with_types = None;
                 let mut without_types = None;
+                let mut out = None;
+                let mut gzip = false;
 
                 let mut i = 0;
                 while i < args.len() {
@@ -321,22 +1396,124 @@ impl FromStr for Commands {
                             }
                             i += 1;
                         }
+                        "--out" => {
+                            let path = args
+                                .get(i + 1)
+                                .ok_or_else(|| anyhow::anyhow!("schema --out requires a path"))?;
+                            out = Some(path.to_string());
+                            i += 2;
+                        }
+                        "--gzip" => {
+                            gzip = true;
+                            i += 1;
+                        }
                         _ => {
                             i += 1;
                         }
                     }
                 }
 
+                if gzip && out.is_none() {
+                    anyhow::bail!("schema --gzip requires --out");
+                }
                 Ok(Commands::Schema {
                     with_crates,
                     without_crates,
                     with_types,
                     without_types,
+                    out,
+                    gzip,
                 })
             }
+            "validate_json" | "validate-json" => {
+                validate_arg_count(args, 2, "validate_json", "a payload kind and JSON")?;
+                let kind = match args[0] {
+                    "spawn" => PayloadKind::Spawn,
+                    "insert" => PayloadKind::Insert,
+                    "insert_resource" => PayloadKind::InsertResource,
+                    "mutate" => PayloadKind::Mutate,
+                    other => anyhow::bail!(
+                        "Unknown payload kind '{}': expected spawn, insert, insert_resource or mutate",
+                        other
+                    ),
+                };
+                Ok(Commands::ValidateJson {
+                    kind,
+                    json: join_args_from(args, 1),
+                })
+            }
+            "wait_for" | "wait-for" => {
+                let mut components = Vec::new();
+                let mut timeout = None;
+
+                let mut i = 0;
+                while i < args.len() {
+                    match args[i] {
+                        "--timeout" => {
+                            let value = args.get(i + 1).ok_or_else(|| {
+                                anyhow::anyhow!("wait_for --timeout requires a number of seconds")
+                            })?;
+                            timeout = Some(value.parse::<u64>().map_err(|_| {
+                                anyhow::anyhow!("Invalid timeout '{}': expected seconds", value)
+                            })?);
+                            i += 2;
+                        }
+                        component => {
+                            components.push(component.to_string());
+                            i += 1;
+                        }
+                    }
+                }
+
+                if components.is_empty() {
+                    anyhow::bail!("wait_for requires at least one component name");
+                }
+                Ok(Commands::WaitFor {
+                    components,
+                    timeout,
+                })
+            }
+            "replay" => {
+                validate_arg_count(args, 1, "replay", "journal file path")?;
+                Ok(Commands::Replay {
+                    path: join_args_from(args, 0),
+                })
+            }
+            "undo" => Ok(Commands::Undo),
             "raw" => {
                 validate_arg_count(args, 1, "raw", "at least one command argument")?;
+                let mut stream = false;
+                let mut params_file = None;
+                let mut params_stdin = false;
+                let mut args = args;
+                loop {
+                    match args {
+                        ["--stream", rest @ ..] => {
+                            stream = true;
+                            args = rest;
+                        }
+                        ["--params-file", path, rest @ ..] => {
+                            params_file = Some(path.to_string());
+                            args = rest;
+                        }
+                        ["--params-stdin", rest @ ..] => {
+                            params_stdin = true;
+                            args = rest;
+                        }
+                        ["--params-file"] => {
+                            anyhow::bail!("raw --params-file requires a path");
+                        }
+                        _ => break,
+                    }
+                }
+                if params_file.is_some() && params_stdin {
+                    anyhow::bail!("raw --params-file and --params-stdin cannot be used together");
+                }
+                validate_arg_count(args, 1, "raw", "a method name")?;
                 Ok(Commands::Raw {
+                    stream,
+                    params_file,
+                    params_stdin,
                     args: args_to_strings(args),
                 })
             }
@@ -1,8 +1,9 @@
 use std::fmt;
 
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
 use strum::{EnumIter, IntoEnumIterator};
 
+use crate::cli::support::{Assertion, parse_assertion, parse_entity_ref};
 use crate::include_help;
 
 /// Metadata for a command including all its descriptive information
@@ -13,23 +14,135 @@ pub struct CommandMetadata {
     pub brief: &'static str,
 }
 
+/// What the `config` command should show
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// Print where the config file is expected
+    Path,
+}
+
+impl fmt::Display for ConfigAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigAction::Path => write!(f, "path"),
+        }
+    }
+}
+
+/// Which command's payload `validate_json` checks
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// Components for `spawn`, as {type: data}
+    Spawn,
+    /// Components for `insert`, as {type: data}
+    Insert,
+    /// Resources for `insert_resource`, as {type: data}
+    #[value(name = "insert_resource")]
+    InsertResource,
+    /// A patch for `mutate_component` or `mutate_resource`
+    Mutate,
+}
+
+impl fmt::Display for PayloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadKind::Spawn => write!(f, "spawn"),
+            PayloadKind::Insert => write!(f, "insert"),
+            PayloadKind::InsertResource => write!(f, "insert_resource"),
+            PayloadKind::Mutate => write!(f, "mutate"),
+        }
+    }
+}
+
 #[derive(Subcommand, Debug, Clone, PartialEq)]
 pub enum Commands {
+    /// Show the chain of parents from an entity up to its root
+    Ancestors {
+        /// Entity ID to start from (u64 integer, e.g., 12345)
+        #[arg(value_name = "ENTITY_ID")]
+        entity: u64,
+    },
+
+    /// Measure round-trip latency of a BRP method
+    Bench {
+        /// BRP method to call [default: bevy/list]
+        #[arg(value_name = "METHOD")]
+        method: Option<String>,
+        /// Number of timed calls after the warm-up call [default: 20]
+        #[arg(long, value_name = "N")]
+        iterations: Option<u32>,
+    },
+
+    /// Report which BRP methods the app supports as has_* flags
+    Capabilities,
+
+    /// Clone an entity and all of its components
+    Clone {
+        /// Entity ID to clone (u64 integer, e.g., 12345)
+        #[arg(value_name = "ENTITY_ID")]
+        entity: u64,
+        /// Number of clones to create [default: 1]
+        #[arg(long, value_name = "N")]
+        count: Option<u64>,
+        /// Print only a JSON array of the new entity IDs
+        #[arg(long = "ids-only")]
+        ids_only: bool,
+    },
+
+    /// Show information about the config file that provides flag defaults
+    Config {
+        /// What to show ('path' prints where the config file is expected)
+        #[arg(value_enum, value_name = "ACTION")]
+        action: ConfigAction,
+    },
+
     /// Destroy an entity
     Destroy {
         /// Entity ID to destroy (u64 integer, e.g., 12345)
+        #[arg(
+            value_name = "ENTITY_ID",
+            required_unless_present = "from_stdin",
+            conflicts_with = "from_stdin"
+        )]
+        entity: Option<u64>,
+        /// Read entity IDs as JSON from stdin (e.g., piped from 'query' or 'list_entities')
+        #[arg(long)]
+        from_stdin: bool,
+        /// Also destroy every descendant found through Children, deepest first
+        #[arg(long)]
+        recursive: bool,
+    },
+
+    /// Check whether an entity exists
+    Exists {
+        /// Entity ID to check (u64 integer, e.g., 12345)
         #[arg(value_name = "ENTITY_ID")]
         entity: u64,
     },
 
     /// Get component data for an entity
+    #[command(allow_missing_positional = true)]
     Get {
         /// Entity ID (u64 integer, e.g., 12345)
-        #[arg(value_name = "ENTITY_ID")]
-        entity: u64,
-        /// Component type name (e.g., bevy_transform::components::transform::Transform)
-        #[arg(value_name = "COMPONENT_TYPE")]
-        component: String,
+        #[arg(
+            value_name = "ENTITY_ID",
+            required_unless_present = "from_stdin",
+            conflicts_with = "from_stdin"
+        )]
+        entity: Option<u64>,
+        /// Component type names (e.g., bevy_transform::components::transform::Transform
+        /// bevy_core::name::Name)
+        #[arg(value_name = "COMPONENT_TYPES", required = true)]
+        components: Vec<String>,
+        /// Read entity IDs as JSON from stdin (e.g., piped from 'query' or 'list_entities')
+        #[arg(long)]
+        from_stdin: bool,
+        /// Poll until every component is present instead of returning its error
+        #[arg(long = "retry-on-empty", conflicts_with = "from_stdin")]
+        retry_on_empty: bool,
+        /// Seconds to keep polling with --retry-on-empty before failing [default: 30]
+        #[arg(long, value_name = "SECONDS", requires = "retry_on_empty")]
+        timeout: Option<u64>,
     },
 
     /// Get resource data
@@ -38,6 +151,26 @@ pub enum Commands {
         /// Resource type name (e.g., bevy_time::time::Time)
         #[arg(value_name = "RESOURCE_TYPE")]
         resource: String,
+        /// Only print the value at this JSON pointer into the resource (e.g., audio/volume)
+        #[arg(long, value_name = "POINTER")]
+        field: Option<String>,
+    },
+
+    /// Watch a resource for value changes by polling (streaming - press Ctrl+C to stop)
+    #[command(name = "get_resource+watch")]
+    GetResourceWatch {
+        /// Resource type name (e.g., bevy_time::time::Time)
+        #[arg(value_name = "RESOURCE_TYPE")]
+        resource: String,
+        /// Milliseconds between polls [default: 500]
+        #[arg(long, value_name = "MS")]
+        interval: Option<u64>,
+        /// Skip the initial value and print only later changes
+        #[arg(long = "changes-only")]
+        changes_only: bool,
+        /// Print each change as one compact JSON line (NDJSON), without banners
+        #[arg(long)]
+        line: bool,
     },
 
     /// Watch component data changes on an entity (streaming - press Ctrl+C to stop)
@@ -50,17 +183,55 @@ pub enum Commands {
         /// bevy_core::name::Name)
         #[arg(value_name = "COMPONENT_TYPES", required = true)]
         components: Vec<String>,
+        /// Re-issue the watch request with backoff when the stream drops
+        #[arg(long)]
+        reconnect: bool,
+        /// Consecutive reconnect attempts before giving up [default: 5]
+        #[arg(long = "reconnect-max", value_name = "N", requires = "reconnect")]
+        reconnect_max: Option<u32>,
+        /// Skip the initial snapshot of each stream and print only later changes
+        #[arg(long = "changes-only")]
+        changes_only: bool,
+        /// Print each update as one compact JSON line (NDJSON), without banners
+        #[arg(long)]
+        line: bool,
+        /// Only print updates matching this predicate (POINTER OP VALUE, as for --assert)
+        #[arg(long, visible_alias = "watch-filter", value_name = "PREDICATE", value_parser = parse_assertion)]
+        filter: Option<Assertion>,
+        /// Stop after this many updates
+        #[arg(long = "max-events", value_name = "N")]
+        max_events: Option<u64>,
+        /// Count only updates matching --filter toward --max-events
+        #[arg(long = "count-matching-only", requires = "max_events")]
+        count_matching_only: bool,
     },
 
+    /// Summarize app health: readiness, method count, entity and resource counts
+    Health,
+
     /// Insert a component on an entity
+    #[command(allow_missing_positional = true)]
     Insert {
         /// Entity ID (u64 integer, e.g., 12345)
-        #[arg(value_name = "ENTITY_ID")]
-        entity: u64,
+        #[arg(
+            value_name = "ENTITY_ID",
+            required_unless_present = "entities",
+            conflicts_with = "entities"
+        )]
+        entity: Option<u64>,
         /// JSON object with component type and data (e.g., '{"bevy_core::name::Name":
         /// "MyEntity"}')
         #[arg(value_name = "JSON")]
         components: String,
+        /// Comma-separated entity IDs to insert the same components on (e.g., 12345,67890)
+        #[arg(long, value_name = "ENTITY_IDS", value_delimiter = ',')]
+        entities: Option<Vec<u64>>,
+        /// Skip components the entity already has instead of replacing them
+        #[arg(long = "if-missing", alias = "insert-if-missing")]
+        if_missing: bool,
+        /// Deep-merge the JSON into each existing component, then insert the merged value
+        #[arg(long, conflicts_with = "if_missing")]
+        merge: bool,
     },
 
     /// Insert or update a resource
@@ -72,16 +243,43 @@ pub enum Commands {
         data: String,
     },
 
+    /// Forcibly terminate the detached session on --port and remove its session files
+    Kill,
+
     /// List all component types
-    List,
+    List {
+        /// Sort type names alphabetically (the default)
+        #[arg(long, overrides_with = "no_sort")]
+        sort: bool,
+        /// Keep the server's registration order instead of sorting
+        #[arg(long, overrides_with = "sort")]
+        no_sort: bool,
+    },
 
     /// List all resources
     #[command(name = "list_resources")]
-    ListResources,
+    ListResources {
+        /// Sort type names alphabetically (the default)
+        #[arg(long, overrides_with = "no_sort")]
+        sort: bool,
+        /// Keep the server's registration order instead of sorting
+        #[arg(long, overrides_with = "sort")]
+        no_sort: bool,
+    },
 
     /// List all entities with their components
     #[command(name = "list_entities")]
-    ListEntities,
+    ListEntities {
+        /// List component type names only (the default), never component data
+        #[arg(long, conflicts_with = "with_data")]
+        components_only: bool,
+        /// Also fetch each entity's component data, as 'list_entity' would
+        #[arg(long)]
+        with_data: bool,
+        /// Print each entity on its own line as soon as it is ready, unsorted
+        #[arg(long)]
+        jsonl: bool,
+    },
 
     /// Get all component data for a single entity
     #[command(name = "list_entity")]
@@ -91,29 +289,71 @@ pub enum Commands {
         entity: u64,
     },
 
+    /// Capture every entity's component data and every resource
+    Snapshot {
+        /// Earlier snapshot to compare against, printing only what changed since
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<String>,
+    },
+
     /// Watch component changes on an entity (streaming - press Ctrl+C to stop)
     #[command(name = "list+watch")]
     ListWatch {
         /// Entity ID to watch for component changes (u64 integer, e.g., 12345)
         #[arg(value_name = "ENTITY_ID")]
         entity: u64,
+        /// Re-issue the watch request with backoff when the stream drops
+        #[arg(long)]
+        reconnect: bool,
+        /// Consecutive reconnect attempts before giving up [default: 5]
+        #[arg(long = "reconnect-max", value_name = "N", requires = "reconnect")]
+        reconnect_max: Option<u32>,
+        /// Skip the initial snapshot of each stream and print only later changes
+        #[arg(long = "changes-only")]
+        changes_only: bool,
+        /// Print each update as one compact JSON line (NDJSON), without banners
+        #[arg(long)]
+        line: bool,
+        /// Only print updates matching this predicate (POINTER OP VALUE, as for --assert)
+        #[arg(long, visible_alias = "watch-filter", value_name = "PREDICATE", value_parser = parse_assertion)]
+        filter: Option<Assertion>,
+        /// Stop after this many updates
+        #[arg(long = "max-events", value_name = "N")]
+        max_events: Option<u64>,
+        /// Count only updates matching --filter toward --max-events
+        #[arg(long = "count-matching-only", requires = "max_events")]
+        count_matching_only: bool,
     },
 
     /// List available remote methods
-    Methods,
+    Methods {
+        /// Only show methods whose name contains this substring
+        #[arg(long, value_name = "SUBSTRING")]
+        grep: Option<String>,
+        /// Group methods by namespace prefix (bevy, brp_tool, rpc, ...)
+        #[arg(long)]
+        group: bool,
+    },
 
     /// Modify specific fields of a component
-    #[command(name = "mutate_component")]
+    #[command(name = "mutate_component", allow_missing_positional = true)]
     MutateComponent {
         /// Entity ID (u64 integer, e.g., 12345)
-        #[arg(value_name = "ENTITY_ID")]
-        entity: u64,
+        #[arg(
+            value_name = "ENTITY_ID",
+            required_unless_present = "from_stdin",
+            conflicts_with = "from_stdin"
+        )]
+        entity: Option<u64>,
         /// Component type name (e.g., bevy_transform::components::transform::Transform)
         #[arg(value_name = "COMPONENT_TYPE")]
         component: String,
         /// JSON patch with fields to update (e.g., '{"translation": [10.0, 0.0, 0.0]}')
         #[arg(value_name = "JSON_PATCH")]
         patch: String,
+        /// Read entity IDs as JSON from stdin (e.g., piped from 'query' or 'list_entities')
+        #[arg(long)]
+        from_stdin: bool,
     },
 
     /// Modify specific fields of a resource
@@ -133,19 +373,39 @@ pub enum Commands {
         /// bevy_transform::components::transform::Transform bevy_core::name::Name)
         #[arg(value_name = "COMPONENT_TYPES", required = true)]
         components: Vec<String>,
+        /// Return at most N entities, wrapped in a {total, offset, limit, entities} envelope
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+        /// Skip the first N entities, wrapped in a {total, offset, limit, entities} envelope
+        #[arg(long, value_name = "N")]
+        offset: Option<usize>,
+        /// Only return this component field (e.g., Transform.translation.x); repeatable
+        #[arg(long, value_name = "COMPONENT.PATH")]
+        select: Vec<String>,
+        /// Exit with code 3 when no entities match, after printing the empty result
+        #[arg(long = "fail-if-empty")]
+        fail_if_empty: bool,
     },
 
     /// Check if app is ready
     Ready,
 
     /// Remove a component from an entity
+    #[command(allow_missing_positional = true)]
     Remove {
         /// Entity ID (u64 integer, e.g., 12345)
-        #[arg(value_name = "ENTITY_ID")]
-        entity: u64,
+        #[arg(
+            value_name = "ENTITY_ID",
+            required_unless_present = "from_stdin",
+            conflicts_with = "from_stdin"
+        )]
+        entity: Option<u64>,
         /// Component type to remove (e.g., bevy_core::name::Name)
         #[arg(value_name = "COMPONENT_TYPE")]
         component: String,
+        /// Read entity IDs as JSON from stdin (e.g., piped from 'query' or 'list_entities')
+        #[arg(long)]
+        from_stdin: bool,
     },
 
     /// Remove a resource
@@ -157,20 +417,44 @@ pub enum Commands {
     },
 
     /// Change entity parent-child relationship
+    #[command(allow_missing_positional = true)]
     Reparent {
-        /// Child entity ID (u64 integer, e.g., 12345)
-        #[arg(value_name = "CHILD_ID")]
-        child: u64,
-        /// Parent entity ID (u64 integer, e.g., 67890) or 'null' for no parent
-        #[arg(value_name = "PARENT_ID")]
+        /// Child entity ID (u64 integer, e.g., 12345) or name:<NAME>
+        #[arg(
+            value_name = "CHILD",
+            value_parser = parse_entity_ref,
+            required_unless_present = "children",
+            conflicts_with = "children"
+        )]
+        child: Option<String>,
+        /// Parent entity ID (u64 integer, e.g., 67890), name:<NAME>, or 'null' for no parent
+        #[arg(value_name = "PARENT")]
         parent: String,
+        /// Comma-separated child entity IDs to attach to the same parent (e.g., 12345,67890)
+        #[arg(long, value_name = "CHILD_IDS", value_delimiter = ',')]
+        children: Option<Vec<u64>>,
     },
 
     /// Take a screenshot
     Screenshot {
         /// Path to save the screenshot (e.g., ./screenshot.png or /tmp/capture.png)
-        #[arg(value_name = "FILE_PATH")]
-        path: String,
+        #[arg(value_name = "FILE_PATH", required_unless_present_any = ["inline", "output_dir"])]
+        path: Option<String>,
+        /// Save to screenshot_<unix_millis>.<ext> in this directory, creating it if needed
+        #[arg(long = "output-dir", value_name = "DIR", conflicts_with = "path")]
+        output_dir: Option<String>,
+        /// Capture only this region of the window in physical pixels (e.g., 0,0,640,480)
+        #[arg(long, value_name = "X,Y,W,H", value_delimiter = ',')]
+        region: Option<Vec<u32>>,
+        /// Image format to encode [default: from the file extension]
+        #[arg(long = "image-format", value_name = "FORMAT", value_parser = ["png", "jpg", "jpeg"])]
+        image_format: Option<String>,
+        /// JPEG quality from 1 to 100 [default: 90]
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=100))]
+        quality: Option<u8>,
+        /// Return the image as base64 in the JSON response instead of waiting for a file
+        #[arg(long)]
+        inline: bool,
     },
 
     /// Shutdown the app
@@ -182,6 +466,63 @@ pub enum Commands {
         /// '{"bevy_transform::components::transform::Transform": {"translation": [0, 0, 0]}}')
         #[arg(value_name = "JSON")]
         components: String,
+        /// Also give the entity a Name component with this value
+        #[arg(long = "with-name", value_name = "NAME")]
+        with_name: Option<String>,
+        /// Let --with-name replace a Name already present in the JSON
+        #[arg(long, requires = "with_name")]
+        force: bool,
+        /// Print only the new entity ID, for capturing in shell scripts
+        #[arg(long = "id-only")]
+        id_only: bool,
+    },
+
+    /// Dump an entity and all of its descendants as a nested tree
+    Subtree {
+        /// Entity ID at the top of the tree (u64 integer, e.g., 12345)
+        #[arg(value_name = "ENTITY_ID")]
+        entity: u64,
+    },
+
+    /// Re-run the successful commands recorded in a `--journal` file
+    Replay {
+        /// Journal file written by `--journal`
+        #[arg(value_name = "PATH")]
+        path: String,
+    },
+
+    /// Undo the last destroy, remove or mutate_component in this command list
+    Undo,
+
+    /// Check a JSON payload offline before sending it with spawn, insert, insert_resource or
+    /// a mutate command
+    #[command(name = "validate_json", alias = "validate-json")]
+    ValidateJson {
+        /// Which command the payload is for
+        #[arg(value_enum, value_name = "KIND")]
+        kind: PayloadKind,
+        /// JSON payload to check
+        #[arg(value_name = "JSON")]
+        json: String,
+    },
+
+    /// Wait until a query for the given components returns at least one entity
+    #[command(name = "wait_for", alias = "wait-for")]
+    WaitFor {
+        /// Component type names to wait for (e.g.,
+        /// bevy_transform::components::transform::Transform bevy_core::name::Name)
+        #[arg(value_name = "COMPONENT_TYPES", required = true)]
+        components: Vec<String>,
+        /// Maximum number of seconds to wait before failing [default: 30]
+        #[arg(long, value_name = "SECONDS")]
+        timeout: Option<u64>,
+    },
+
+    /// Show the schema and fields of a single registered type
+    Describe {
+        /// Fully qualified type name (e.g., bevy_transform::components::transform::Transform)
+        #[arg(value_name = "TYPE")]
+        type_name: String,
     },
 
     /// Get JSON schemas for all registered types in the Bevy app
@@ -198,10 +539,25 @@ pub enum Commands {
         /// Exclude types with these reflect traits
         #[arg(long = "without-types")]
         without_types: Option<Vec<String>>,
+        /// Write the schema to this file instead of printing it
+        #[arg(long, value_name = "PATH")]
+        out: Option<String>,
+        /// Gzip-compress the file written by --out
+        #[arg(long, requires = "out")]
+        gzip: bool,
     },
 
     /// Execute a raw command string (e.g., bevy/list, bevy/registry/schema)
     Raw {
+        /// Treat the method as a streaming (SSE) endpoint and print each update
+        #[arg(long)]
+        stream: bool,
+        /// Read the params JSON from this file instead of the trailing arguments
+        #[arg(long, value_name = "PATH", conflicts_with = "params_stdin")]
+        params_file: Option<String>,
+        /// Read the params JSON from stdin instead of the trailing arguments
+        #[arg(long)]
+        params_stdin: bool,
         /// Command and arguments to pass directly to the server
         #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
         args: Vec<String>,
@@ -212,7 +568,7 @@ impl Commands {
     /// Get metadata for this command
     fn metadata(&self) -> CommandMetadata {
         match self {
-            Commands::List => CommandMetadata {
+            Commands::List { .. } => CommandMetadata {
                 names: &["bevy/list", "list"],
                 brief: "List all component types in the world",
             },
@@ -228,10 +584,34 @@ impl Commands {
                 names: &["bevy/spawn", "spawn"],
                 brief: "Spawn new entities with components",
             },
+            Commands::Kill => CommandMetadata {
+                names: &["kill"],
+                brief: "Force-kill the detached session on the selected port",
+            },
+            Commands::Capabilities => CommandMetadata {
+                names: &["capabilities"],
+                brief: "Report which BRP methods the app supports",
+            },
+            Commands::Clone { .. } => CommandMetadata {
+                names: &["clone"],
+                brief: "Duplicate an entity with all of its components",
+            },
             Commands::Destroy { .. } => CommandMetadata {
                 names: &["bevy/destroy", "destroy"],
                 brief: "Destroy entities",
             },
+            Commands::Ancestors { .. } => CommandMetadata {
+                names: &["ancestors", "parents"],
+                brief: "Show the chain of parents from an entity to its root",
+            },
+            Commands::Subtree { .. } => CommandMetadata {
+                names: &["subtree"],
+                brief: "Dump an entity and all of its descendants as a tree",
+            },
+            Commands::Exists { .. } => CommandMetadata {
+                names: &["exists"],
+                brief: "Check whether an entity exists",
+            },
             Commands::Insert { .. } => CommandMetadata {
                 names: &["bevy/insert", "insert"],
                 brief: "Insert components on existing entities",
@@ -248,7 +628,7 @@ impl Commands {
                 names: &["bevy/mutate_component", "mutate_component"],
                 brief: "Modify specific fields of a component",
             },
-            Commands::ListResources => CommandMetadata {
+            Commands::ListResources { .. } => CommandMetadata {
                 names: &["bevy/list_resources", "list_resources"],
                 brief: "List all resources in the world",
             },
@@ -272,17 +652,33 @@ impl Commands {
                 names: &["bevy/list+watch", "list+watch"],
                 brief: "Watch component changes on an entity",
             },
+            Commands::GetResourceWatch { .. } => CommandMetadata {
+                names: &["get_resource+watch"],
+                brief: "Poll a resource and print its value when it changes",
+            },
             Commands::GetWatch { .. } => CommandMetadata {
                 names: &["bevy/get+watch", "get+watch"],
                 brief: "Watch component data changes on an entity",
             },
+            Commands::Describe { .. } => CommandMetadata {
+                names: &["describe"],
+                brief: "Show the schema and fields of a single type",
+            },
             Commands::Schema { .. } => CommandMetadata {
                 names: &["bevy/registry/schema", "schema"],
                 brief: "Get JSON schemas for registered types",
             },
             Commands::Screenshot { .. } => CommandMetadata {
                 names: &["brp_tool/screenshot", "screenshot"],
-                brief: "Take a screenshot and save to file",
+                brief: "Take a screenshot to a file, a directory, or inline base64",
+            },
+            Commands::Config { .. } => CommandMetadata {
+                names: &["config"],
+                brief: "Show where the config file is expected",
+            },
+            Commands::Health => CommandMetadata {
+                names: &["health"],
+                brief: "Summarize readiness, methods, entity and resource counts",
             },
             Commands::Ready => CommandMetadata {
                 names: &["ready"],
@@ -292,11 +688,11 @@ impl Commands {
                 names: &["brp_tool/shutdown", "shutdown"],
                 brief: "Gracefully shutdown the application",
             },
-            Commands::Methods => CommandMetadata {
+            Commands::Methods { .. } => CommandMetadata {
                 names: &["methods"],
                 brief: "List commands available from running app",
             },
-            Commands::ListEntities => CommandMetadata {
+            Commands::ListEntities { .. } => CommandMetadata {
                 names: &["list_entities"],
                 brief: "List all entities with their components",
             },
@@ -304,10 +700,34 @@ impl Commands {
                 names: &["list_entity"],
                 brief: "Get all component data for a single entity",
             },
+            Commands::Snapshot { .. } => CommandMetadata {
+                names: &["snapshot"],
+                brief: "Capture all entities and resources, or what changed since a snapshot",
+            },
+            Commands::Bench { .. } => CommandMetadata {
+                names: &["bench"],
+                brief: "Measure round-trip latency of a BRP method",
+            },
             Commands::Raw { .. } => CommandMetadata {
                 names: &["raw"],
                 brief: "Execute any command directly (bypass CLI parsing)",
             },
+            Commands::Replay { .. } => CommandMetadata {
+                names: &["replay"],
+                brief: "Re-run the commands recorded in a journal file",
+            },
+            Commands::Undo => CommandMetadata {
+                names: &["undo"],
+                brief: "Undo the last destroy, remove or mutate_component in a command list",
+            },
+            Commands::ValidateJson { .. } => CommandMetadata {
+                names: &["validate_json", "validate-json"],
+                brief: "Check a spawn, insert or mutate JSON payload without an app",
+            },
+            Commands::WaitFor { .. } => CommandMetadata {
+                names: &["wait_for", "wait-for"],
+                brief: "Wait until a query returns at least one entity",
+            },
         }
     }
 
@@ -330,57 +750,91 @@ impl Commands {
     /// Get the detailed help text for this command
     pub fn detailed_help(&self) -> String {
         match self {
-            Commands::List => include_help!("list").to_string(),
+            Commands::List { .. } => include_help!("list").to_string(),
             Commands::Query { .. } => include_help!("query").to_string(),
             Commands::Get { .. } => include_help!("get").to_string(),
             Commands::Spawn { .. } => include_help!("spawn").to_string(),
+            Commands::Clone { .. } => include_help!("clone").to_string(),
+            Commands::Capabilities => include_help!("capabilities").to_string(),
             Commands::Destroy { .. } => include_help!("destroy").to_string(),
+            Commands::Ancestors { .. } => include_help!("ancestors").to_string(),
+            Commands::Subtree { .. } => include_help!("subtree").to_string(),
+            Commands::Exists { .. } => include_help!("exists").to_string(),
             Commands::Insert { .. } => include_help!("insert").to_string(),
             Commands::Remove { .. } => include_help!("remove").to_string(),
             Commands::Reparent { .. } => include_help!("reparent").to_string(),
             Commands::MutateComponent { .. } => include_help!("mutate_component").to_string(),
-            Commands::ListResources => include_help!("list_resources").to_string(),
+            Commands::ListResources { .. } => include_help!("list_resources").to_string(),
             Commands::GetResource { .. } => include_help!("get_resource").to_string(),
             Commands::InsertResource { .. } => include_help!("insert_resource").to_string(),
             Commands::RemoveResource { .. } => include_help!("remove_resource").to_string(),
             Commands::MutateResource { .. } => include_help!("mutate_resource").to_string(),
             Commands::ListWatch { .. } => include_help!("list_watch").to_string(),
             Commands::GetWatch { .. } => include_help!("get_watch").to_string(),
+            Commands::GetResourceWatch { .. } => include_help!("get_resource_watch").to_string(),
+            Commands::Describe { .. } => include_help!("describe").to_string(),
             Commands::Schema { .. } => include_help!("schema").to_string(),
             Commands::Screenshot { .. } => include_help!("screenshot").to_string(),
+            Commands::Config { .. } => include_help!("config").to_string(),
+            Commands::Kill => include_help!("kill").to_string(),
+            Commands::Health => include_help!("health").to_string(),
             Commands::Ready => include_help!("ready").to_string(),
             Commands::Shutdown => include_help!("shutdown").to_string(),
-            Commands::Methods => include_help!("methods").to_string(),
-            Commands::ListEntities => include_help!("list_entities").to_string(),
+            Commands::Methods { .. } => include_help!("methods").to_string(),
+            Commands::ListEntities { .. } => include_help!("list_entities").to_string(),
             Commands::ListEntity { .. } => include_help!("list_entity").to_string(),
+            Commands::Snapshot { .. } => include_help!("snapshot").to_string(),
             Commands::Raw { .. } => include_help!("raw").to_string(),
+            Commands::Bench { .. } => include_help!("bench").to_string(),
+            Commands::Replay { .. } => include_help!("replay").to_string(),
+            Commands::Undo => include_help!("undo").to_string(),
+            Commands::ValidateJson { .. } => include_help!("validate_json").to_string(),
+            Commands::WaitFor { .. } => include_help!("wait_for").to_string(),
         }
     }
 
     /// Get the category for this command
     pub fn category(&self) -> CommandCategory {
         match self {
-            Commands::List
+            Commands::List { .. }
             | Commands::Query { .. }
             | Commands::Get { .. }
             | Commands::Spawn { .. }
+            | Commands::Clone { .. }
             | Commands::Destroy { .. }
             | Commands::Insert { .. }
             | Commands::Remove { .. }
             | Commands::Reparent { .. }
             | Commands::MutateComponent { .. }
+            | Commands::Describe { .. }
             | Commands::Schema { .. }
-            | Commands::ListEntities
-            | Commands::ListEntity { .. } => CommandCategory::BevyEntity,
-            Commands::ListResources
+            | Commands::ListEntities { .. }
+            | Commands::ListEntity { .. }
+            | Commands::Ancestors { .. }
+            | Commands::Subtree { .. } => CommandCategory::BevyEntity,
+            Commands::ListResources { .. }
             | Commands::GetResource { .. }
             | Commands::InsertResource { .. }
             | Commands::RemoveResource { .. }
             | Commands::MutateResource { .. } => CommandCategory::BevyResource,
-            Commands::ListWatch { .. } | Commands::GetWatch { .. } => CommandCategory::BevyWatch,
+            Commands::ListWatch { .. }
+            | Commands::GetWatch { .. }
+            | Commands::GetResourceWatch { .. } => CommandCategory::BevyWatch,
             Commands::Screenshot { .. } | Commands::Shutdown => CommandCategory::BrpTool,
-            Commands::Methods | Commands::Ready => CommandCategory::Special,
-            Commands::Raw { .. } => CommandCategory::Special,
+            Commands::Bench { .. }
+            | Commands::Capabilities
+            | Commands::Config { .. }
+            | Commands::Exists { .. }
+            | Commands::Health
+            | Commands::Kill
+            | Commands::Methods { .. }
+            | Commands::Ready
+            | Commands::Snapshot { .. } => CommandCategory::Special,
+            Commands::Raw { .. }
+            | Commands::Replay { .. }
+            | Commands::Undo
+            | Commands::ValidateJson { .. }
+            | Commands::WaitFor { .. } => CommandCategory::Special,
         }
     }
 }
@@ -394,6 +848,19 @@ pub enum CommandCategory {
     Special,
 }
 
+impl CommandCategory {
+    /// Stable identifier for machine-readable output, unlike the display title
+    pub fn key(&self) -> &'static str {
+        match self {
+            CommandCategory::BevyEntity => "entity",
+            CommandCategory::BevyResource => "resource",
+            CommandCategory::BevyWatch => "watch",
+            CommandCategory::BrpTool => "brp_tool",
+            CommandCategory::Special => "special",
+        }
+    }
+}
+
 impl fmt::Display for CommandCategory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -411,12 +878,22 @@ impl fmt::Display for CommandCategory {
 /// Command template enum without fields for strum iteration
 #[derive(Debug, Clone, Copy, EnumIter)]
 pub enum CommandTemplate {
+    Ancestors,
+    Bench,
+    Capabilities,
+    Clone,
+    Config,
+    Describe,
     Destroy,
+    Exists,
     Get,
     GetResource,
+    GetResourceWatch,
     GetWatch,
+    Health,
     Insert,
     InsertResource,
+    Kill,
     List,
     ListResources,
     ListEntities,
@@ -432,8 +909,14 @@ pub enum CommandTemplate {
     Reparent,
     Screenshot,
     Shutdown,
+    Snapshot,
     Spawn,
+    Subtree,
     Schema,
+    Replay,
+    Undo,
+    ValidateJson,
+    WaitFor,
     Raw,
 }
 
@@ -441,68 +924,172 @@ impl CommandTemplate {
     /// Convert template to actual command with default values
     pub fn to_command(self) -> Option<Commands> {
         match self {
-            CommandTemplate::Destroy => Some(Commands::Destroy { entity: 0 }),
-            CommandTemplate::Get => Some(Commands::Get {
+            CommandTemplate::Bench => Some(Commands::Bench {
+                method: None,
+                iterations: None,
+            }),
+            CommandTemplate::Capabilities => Some(Commands::Capabilities),
+            CommandTemplate::Clone => Some(Commands::Clone {
                 entity: 0,
-                component: String::new(),
+                count: None,
+                ids_only: false,
+            }),
+            CommandTemplate::Config => Some(Commands::Config {
+                action: ConfigAction::Path,
+            }),
+            CommandTemplate::Describe => Some(Commands::Describe {
+                type_name: String::new(),
+            }),
+            CommandTemplate::Destroy => Some(Commands::Destroy {
+                entity: Some(0),
+                from_stdin: false,
+                recursive: false,
+            }),
+            CommandTemplate::Ancestors => Some(Commands::Ancestors { entity: 0 }),
+            CommandTemplate::Exists => Some(Commands::Exists { entity: 0 }),
+            CommandTemplate::Get => Some(Commands::Get {
+                entity: Some(0),
+                components: vec![],
+                from_stdin: false,
+                retry_on_empty: false,
+                timeout: None,
             }),
             CommandTemplate::GetResource => Some(Commands::GetResource {
                 resource: String::new(),
+                field: None,
+            }),
+            CommandTemplate::GetResourceWatch => Some(Commands::GetResourceWatch {
+                resource: String::new(),
+                interval: None,
+                changes_only: false,
+                line: false,
             }),
             CommandTemplate::GetWatch => Some(Commands::GetWatch {
                 entity: 0,
                 components: vec![],
+                reconnect: false,
+                reconnect_max: None,
+                changes_only: false,
+                line: false,
+                filter: None,
+                max_events: None,
+                count_matching_only: false,
             }),
             CommandTemplate::Insert => Some(Commands::Insert {
-                entity: 0,
+                entity: Some(0),
                 components: String::new(),
+                entities: None,
+                if_missing: false,
+                merge: false,
             }),
             CommandTemplate::InsertResource => Some(Commands::InsertResource {
                 data: String::new(),
             }),
-            CommandTemplate::List => Some(Commands::List),
-            CommandTemplate::ListResources => Some(Commands::ListResources),
-            CommandTemplate::ListEntities => Some(Commands::ListEntities),
+            CommandTemplate::Kill => Some(Commands::Kill),
+            CommandTemplate::List => Some(Commands::List {
+                sort: false,
+                no_sort: false,
+            }),
+            CommandTemplate::ListResources => Some(Commands::ListResources {
+                sort: false,
+                no_sort: false,
+            }),
+            CommandTemplate::ListEntities => Some(Commands::ListEntities {
+                components_only: false,
+                with_data: false,
+                jsonl: false,
+            }),
             CommandTemplate::ListEntity => Some(Commands::ListEntity { entity: 0 }),
-            CommandTemplate::ListWatch => Some(Commands::ListWatch { entity: 0 }),
-            CommandTemplate::Methods => Some(Commands::Methods),
-            CommandTemplate::MutateComponent => Some(Commands::MutateComponent {
+            CommandTemplate::ListWatch => Some(Commands::ListWatch {
                 entity: 0,
+                reconnect: false,
+                reconnect_max: None,
+                changes_only: false,
+                line: false,
+                filter: None,
+                max_events: None,
+                count_matching_only: false,
+            }),
+            CommandTemplate::Health => Some(Commands::Health),
+            CommandTemplate::Methods => Some(Commands::Methods {
+                grep: None,
+                group: false,
+            }),
+            CommandTemplate::MutateComponent => Some(Commands::MutateComponent {
+                entity: None,
                 component: String::new(),
                 patch: String::new(),
+                from_stdin: false,
             }),
             CommandTemplate::MutateResource => Some(Commands::MutateResource {
                 resource: String::new(),
                 patch: String::new(),
             }),
-            CommandTemplate::Query => Some(Commands::Query { components: vec![] }),
+            CommandTemplate::Query => Some(Commands::Query {
+                components: vec![],
+                limit: None,
+                offset: None,
+                select: vec![],
+                fail_if_empty: false,
+            }),
             CommandTemplate::Ready => Some(Commands::Ready),
             CommandTemplate::Remove => Some(Commands::Remove {
-                entity: 0,
+                entity: Some(0),
                 component: String::new(),
+                from_stdin: false,
             }),
             CommandTemplate::RemoveResource => Some(Commands::RemoveResource {
                 resource: String::new(),
             }),
             CommandTemplate::Reparent => Some(Commands::Reparent {
-                child: 0,
+                child: Some(String::new()),
                 parent: String::new(),
+                children: None,
             }),
             CommandTemplate::Screenshot => Some(Commands::Screenshot {
-                path: String::new(),
+                path: Some(String::new()),
+                output_dir: None,
+                region: None,
+                image_format: None,
+                quality: None,
+                inline: false,
             }),
             CommandTemplate::Shutdown => Some(Commands::Shutdown),
+            CommandTemplate::Snapshot => Some(Commands::Snapshot { baseline: None }),
             CommandTemplate::Spawn => Some(Commands::Spawn {
                 components: String::new(),
+                with_name: None,
+                force: false,
+                id_only: false,
             }),
+            CommandTemplate::Subtree => Some(Commands::Subtree { entity: 0 }),
             CommandTemplate::Schema => Some(Commands::Schema {
                 with_crates: None,
                 without_crates: None,
                 with_types: None,
                 without_types: None,
+                out: None,
+                gzip: false,
+            }),
+            CommandTemplate::Replay => Some(Commands::Replay {
+                path: String::new(),
+            }),
+            CommandTemplate::Undo => Some(Commands::Undo),
+            CommandTemplate::ValidateJson => Some(Commands::ValidateJson {
+                kind: PayloadKind::Spawn,
+                json: String::new(),
+            }),
+            CommandTemplate::WaitFor => Some(Commands::WaitFor {
+                components: vec![],
+                timeout: None,
             }),
-            CommandTemplate::Raw => Some(Commands::Raw { args: vec![] }), /* Empty vec for */
-                                                                          /* display purposes */
+            CommandTemplate::Raw => Some(Commands::Raw {
+                stream: false,
+                params_file: None,
+                params_stdin: false,
+                args: vec![],
+            }), /* Empty vec for */
+                /* display purposes */
         }
     }
 }
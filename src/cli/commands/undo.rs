@@ -0,0 +1,187 @@
+//! Undo history for destructive commands within a session
+//!
+//! A session is one `--commands` or `--managed-commands` run. Before `destroy`,
+//! `remove` or `mutate_component` changes anything, the affected data is captured
+//! so `undo` can put it back. Undoing a `destroy` spawns a new entity, so the
+//! original entity ID is not preserved.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde_json::{Map, Value, json};
+
+use crate::cli::client::RemoteClient;
+use crate::cli::constants::{BEVY_CHILDREN_COMPONENT, UNDO_HISTORY_LIMIT};
+
+/// Reversible operations, most recent last
+static HISTORY: Mutex<VecDeque<UndoEntry>> = Mutex::new(VecDeque::new());
+
+/// State captured before a destructive operation
+#[derive(Debug)]
+enum UndoEntry {
+    /// An entity was destroyed; holds the components it had
+    Destroy {
+        entity: u64,
+        components: Map<String, Value>,
+    },
+    /// A component was removed; holds its last value
+    Remove {
+        entity: u64,
+        component: String,
+        data: Value,
+    },
+    /// A component was mutated; holds its value before the mutation
+    Mutate {
+        entity: u64,
+        component: String,
+        data: Value,
+    },
+}
+
+fn record(entry: UndoEntry) {
+    let mut history = HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+    if history.len() == UNDO_HISTORY_LIMIT {
+        history.pop_front();
+    }
+    history.push_back(entry);
+}
+
+fn pop() -> Option<UndoEntry> {
+    HISTORY.lock().unwrap_or_else(|e| e.into_inner()).pop_back()
+}
+
+/// Current value of a component, if the entity has it
+async fn capture_component(client: &RemoteClient, entity: u64, component: &str) -> Option<Value> {
+    let result = client.get_component(entity, component).await.ok()?;
+    result
+        .get("components")
+        .and_then(|c| c.get(component))
+        .filter(|data| !data.is_null())
+        .cloned()
+}
+
+/// Destroy an entity, remembering its components when `record_undo` is set
+pub(super) async fn destroy(
+    client: &RemoteClient,
+    entity: u64,
+    record_undo: bool,
+) -> Result<Value> {
+    let snapshot = if record_undo {
+        client.list_entity(entity).await.ok().and_then(|source| {
+            let mut components = source.get("components")?.as_object()?.clone();
+            // Children is rebuilt from each child's ChildOf and cannot be spawned directly
+            components.remove(BEVY_CHILDREN_COMPONENT);
+            Some(components)
+        })
+    } else {
+        None
+    };
+
+    let result = client.destroy_entity(entity).await?;
+    if let Some(components) = snapshot {
+        record(UndoEntry::Destroy { entity, components });
+    }
+    Ok(result)
+}
+
+/// Remove a component, remembering its value when `record_undo` is set
+pub(super) async fn remove(
+    client: &RemoteClient,
+    entity: u64,
+    component: &str,
+    record_undo: bool,
+) -> Result<Value> {
+    let snapshot = if record_undo {
+        capture_component(client, entity, component).await
+    } else {
+        None
+    };
+
+    let result = client.remove_component(entity, component).await?;
+    if let Some(data) = snapshot {
+        record(UndoEntry::Remove {
+            entity,
+            component: component.to_string(),
+            data,
+        });
+    }
+    Ok(result)
+}
+
+/// Mutate a component, remembering its prior value when `record_undo` is set
+pub(super) async fn mutate(
+    client: &RemoteClient,
+    entity: u64,
+    component: &str,
+    patch: Value,
+    record_undo: bool,
+) -> Result<Value> {
+    let snapshot = if record_undo {
+        capture_component(client, entity, component).await
+    } else {
+        None
+    };
+
+    let result = client.mutate_component(entity, component, patch).await?;
+    if let Some(data) = snapshot {
+        record(UndoEntry::Mutate {
+            entity,
+            component: component.to_string(),
+            data,
+        });
+    }
+    Ok(result)
+}
+
+/// Reverse the most recent recorded operation
+pub(super) async fn undo_last(client: &RemoteClient) -> Result<Value> {
+    let Some(entry) = pop() else {
+        anyhow::bail!(
+            "Nothing to undo: only destroy, remove and mutate_component earlier in the same \
+             --commands or --managed-commands run can be undone"
+        );
+    };
+
+    match entry {
+        UndoEntry::Destroy { entity, components } => {
+            let (new_entity, skipped) = client.spawn_clone(&components).await?;
+            if !skipped.is_empty() {
+                eprintln!(
+                    "Warning: skipped components that could not be restored: {}",
+                    skipped.join(", ")
+                );
+            }
+            Ok(json!({
+                "undone": "destroy",
+                "entity": entity,
+                "new_entity": new_entity,
+                "skipped_components": skipped,
+            }))
+        }
+        UndoEntry::Remove {
+            entity,
+            component,
+            data,
+        } => {
+            client.insert_component(entity, &component, data).await?;
+            Ok(json!({
+                "undone": "remove",
+                "entity": entity,
+                "component": component,
+            }))
+        }
+        UndoEntry::Mutate {
+            entity,
+            component,
+            data,
+        } => {
+            client.insert_component(entity, &component, data).await?;
+            Ok(json!({
+                "undone": "mutate_component",
+                "entity": entity,
+                "component": component,
+            }))
+        }
+    }
+}
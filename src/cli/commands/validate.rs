@@ -0,0 +1,163 @@
+//! Offline payload checks for the `validate_json` command
+//!
+//! Payloads go through the same parsing helpers the real commands use, then the shape is
+//! checked so mistakes show up before anything is sent to an app.
+
+use anyhow::{Result, bail};
+use serde_json::{Value, json};
+
+use super::types::{Commands, PayloadKind};
+use crate::cli::support::{parse_json_object, parse_json_value};
+
+/// Check `json` as a payload for the command named by `kind` and summarize what it contains
+pub fn validate_payload(kind: PayloadKind, json: &str) -> Result<Value> {
+    let command_name = kind.to_string();
+    if kind == PayloadKind::Mutate {
+        let value = parse_json_value(json)?;
+        return Ok(json!({ "valid": true, "kind": command_name, "value": value }));
+    }
+
+    let obj = parse_json_object(json, &command_name)?;
+    let issues = type_map_issues(&obj);
+    if !issues.is_empty() {
+        bail!(
+            "Invalid {} payload:\n  - {}",
+            command_name,
+            issues.join("\n  - ")
+        );
+    }
+
+    let types: Vec<&String> = obj.keys().collect();
+    Ok(json!({ "valid": true, "kind": command_name, "types": types }))
+}
+
+/// Reject type-name arguments of `command` that aren't fully qualified, for `--strict-types`
+///
+/// Type names inside spawn and insert payloads are checked too. Payloads that don't parse are
+/// left for the command itself to report.
+pub fn check_strict_types(command: &Commands) -> Result<()> {
+    let type_names: Vec<String> = match command {
+        Commands::Get { components, .. }
+        | Commands::GetWatch { components, .. }
+        | Commands::Query { components, .. }
+        | Commands::WaitFor { components, .. } => components.clone(),
+        Commands::GetResource { resource, .. }
+        | Commands::GetResourceWatch { resource, .. }
+        | Commands::MutateResource { resource, .. }
+        | Commands::RemoveResource { resource } => vec![resource.clone()],
+        Commands::MutateComponent { component, .. } | Commands::Remove { component, .. } => {
+            vec![component.clone()]
+        }
+        Commands::Describe { type_name } => vec![type_name.clone()],
+        Commands::Spawn { components, .. } | Commands::Insert { components, .. } => {
+            payload_type_names(components)
+        }
+        Commands::InsertResource { data } => payload_type_names(data),
+        _ => Vec::new(),
+    };
+
+    let unqualified: Vec<String> = type_names
+        .into_iter()
+        .filter(|type_name| !type_name.contains("::"))
+        .collect();
+    if !unqualified.is_empty() {
+        bail!(
+            "--strict-types requires fully qualified type paths (e.g., \
+             bevy_transform::components::transform::Transform), but got: {}",
+            unqualified.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Top-level keys of a JSON object payload, or none if it isn't one
+fn payload_type_names(json: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Map<String, Value>>(json)
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Structural problems in an object that should map fully qualified type names to data
+fn type_map_issues(obj: &serde_json::Map<String, Value>) -> Vec<String> {
+    if obj.is_empty() {
+        return vec![
+            "top-level must be an object mapping type names to data, but it is empty".to_string(),
+        ];
+    }
+
+    obj.keys()
+        .filter_map(|type_name| {
+            if type_name.trim().is_empty() {
+                Some("type names must not be empty".to_string())
+            } else if type_name.chars().any(char::is_whitespace) {
+                Some(format!("'{}' contains whitespace", type_name))
+            } else if !type_name.contains("::") {
+                Some(format!(
+                    "'{}' is not a fully qualified type path (e.g., bevy_transform::components::transform::Transform)",
+                    type_name
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_type_map_payloads() {
+        let result =
+            validate_payload(PayloadKind::Spawn, r#"{"bevy_ecs::name::Name": "Player"}"#).unwrap();
+        assert_eq!(result["types"], json!(["bevy_ecs::name::Name"]));
+
+        let error = validate_payload(PayloadKind::Insert, r#"{"Transform": {}}"#).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("'Transform' is not a fully qualified")
+        );
+
+        let error = validate_payload(PayloadKind::InsertResource, "{}").unwrap_err();
+        assert!(error.to_string().contains("top-level must be an object"));
+
+        assert!(validate_payload(PayloadKind::Spawn, "[1, 2]").is_err());
+        assert!(validate_payload(PayloadKind::Spawn, "{not json").is_err());
+    }
+
+    #[test]
+    fn test_strict_types_rejects_unqualified_names() {
+        let query = Commands::Query {
+            components: vec!["bevy_ecs::name::Name".to_string(), "Transform".to_string()],
+            limit: None,
+            offset: None,
+            select: Vec::new(),
+            fail_if_empty: false,
+        };
+        let error = check_strict_types(&query).unwrap_err().to_string();
+        assert!(error.ends_with("but got: Transform"), "{}", error);
+
+        let spawn = Commands::Spawn {
+            components: r#"{"bevy_ecs::name::Name": "Player"}"#.to_string(),
+            with_name: None,
+            force: false,
+            id_only: false,
+        };
+        assert!(check_strict_types(&spawn).is_ok());
+
+        let insert_resource = Commands::InsertResource {
+            data: r#"{"Score": 10}"#.to_string(),
+        };
+        assert!(check_strict_types(&insert_resource).is_err());
+        assert!(check_strict_types(&Commands::Ready).is_ok());
+    }
+
+    #[test]
+    fn test_validate_mutate_payload() {
+        let result = validate_payload(PayloadKind::Mutate, "[1.0, 2.0, 3.0]").unwrap();
+        assert_eq!(result["value"], json!([1.0, 2.0, 3.0]));
+        assert!(validate_payload(PayloadKind::Mutate, "1.0,").is_err());
+    }
+}
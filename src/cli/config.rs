@@ -0,0 +1,308 @@
+//! User config file providing defaults for CLI flags
+//!
+//! The file lives at `$XDG_CONFIG_HOME/brp/config.toml`, falling back to
+//! `~/.config/brp/config.toml`. Flags passed on the command line always win
+//! over values from the file, and a missing file is not an error.
+//!
+//! `BRP_*` environment variables sit between the two: they override the file
+//! but not flags, giving flag > environment > config file > built-in default.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde::Deserialize;
+
+use super::commands::Cli;
+use super::constants::{
+    CONFIG_DIR_NAME, CONFIG_FILE_NAME, ENV_APP, ENV_OUTPUT, ENV_PORT, ENV_PROFILE, ENV_TIMEOUT,
+    ENV_TOKEN,
+};
+
+/// Defaults loaded from the config file
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Default port to connect to
+    pub port: Option<u16>,
+    /// Default build profile
+    pub profile: Option<String>,
+    /// Default app binary for managed and detached modes
+    pub app: Option<String>,
+    /// Default alias file mapping short type names to full paths
+    pub aliases: Option<PathBuf>,
+    /// Default seconds managed commands may take before the app is stopped
+    pub timeout: Option<u64>,
+    /// Default file to write command output JSON to
+    pub output: Option<PathBuf>,
+}
+
+impl Config {
+    /// Parse config file contents
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Fill in any flag the user did not pass on the command line
+    pub fn apply(self, cli: &mut Cli, matches: &ArgMatches) {
+        if let Some(port) = self.port
+            && !from_command_line(matches, "port")
+        {
+            cli.port = port;
+        }
+        if cli.profile.is_none() {
+            cli.profile = self.profile;
+        }
+        // --app is only valid when we start the app, so a configured app must not
+        // turn every other invocation into a usage error
+        if cli.app.is_none() && (cli.detached || cli.managed_commands.is_some()) {
+            cli.app = self.app;
+        }
+        if cli.aliases.is_none() {
+            cli.aliases = self.aliases;
+        }
+        // Like BRP_TIMEOUT, the timeout only bounds a command list we run against our own app
+        if cli.managed_timeout.is_none() && cli.managed_commands.is_some() {
+            cli.managed_timeout = self.timeout;
+        }
+        if cli.output_file.is_none() {
+            cli.output_file = self.output;
+        }
+    }
+}
+
+/// Whether `id` was given on the command line rather than defaulted or filled in
+fn from_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+/// Override config file values and defaults with `BRP_*` environment variables, leaving
+/// flags passed on the command line alone
+///
+/// `lookup` reads a variable; empty values count as unset.
+pub fn apply_env_overrides(
+    cli: &mut Cli,
+    matches: &ArgMatches,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<()> {
+    // The variable's value, unless it is empty or the flag `id` was passed explicitly
+    let env_value = |name: &str, id: &str| {
+        lookup(name).filter(|value| !value.is_empty() && !from_command_line(matches, id))
+    };
+
+    if let Some(port) = env_value(ENV_PORT, "port") {
+        cli.port = port
+            .parse()
+            .with_context(|| format!("Invalid {} '{}': expected a port number", ENV_PORT, port))?;
+    }
+    if let Some(profile) = env_value(ENV_PROFILE, "profile") {
+        cli.profile = Some(profile);
+    }
+    // Like the config file, app and timeout only apply when we start the app
+    let starts_app = cli.detached || cli.managed_commands.is_some();
+    if let Some(app) = env_value(ENV_APP, "app").filter(|_| starts_app) {
+        cli.app = Some(app);
+    }
+    let managed = cli.managed_commands.is_some();
+    if let Some(seconds) = env_value(ENV_TIMEOUT, "managed_timeout").filter(|_| managed) {
+        cli.managed_timeout =
+            Some(seconds.parse().with_context(|| {
+                format!("Invalid {} '{}': expected seconds", ENV_TIMEOUT, seconds)
+            })?);
+    }
+    if let Some(path) = env_value(ENV_OUTPUT, "output_file") {
+        cli.output_file = Some(PathBuf::from(path));
+    }
+    if let Some(token) = env_value(ENV_TOKEN, "token") {
+        cli.token = Some(token);
+    }
+    Ok(())
+}
+
+/// Where the config file is expected, whether or not it exists
+pub fn config_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(config_home.join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME))
+}
+
+/// Load the config file, returning empty defaults if it does not exist
+pub fn load_config() -> Result<Config> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Config::from_toml(&contents)
+            .with_context(|| format!("Invalid config file {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read config file {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+
+    fn parse(args: &[&str]) -> (Cli, ArgMatches) {
+        let matches = Cli::command().get_matches_from(args);
+        let cli = Cli::from_arg_matches(&matches).unwrap();
+        (cli, matches)
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::from_toml(
+            r#"
+            port = 15800
+            profile = "release"
+            app = "my_game"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                port: Some(15800),
+                profile: Some("release".to_string()),
+                app: Some("my_game".to_string()),
+                aliases: None,
+                timeout: None,
+                output: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_key_is_rejected() {
+        assert!(Config::from_toml("prot = 15800").is_err());
+    }
+
+    #[test]
+    fn test_config_fills_missing_flags() {
+        let config =
+            Config::from_toml("port = 15800\nprofile = \"release\"\napp = \"my_game\"").unwrap();
+        let (mut cli, matches) = parse(&["brp", "-m", "ready"]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.port, 15800);
+        assert_eq!(cli.profile.as_deref(), Some("release"));
+        assert_eq!(cli.app.as_deref(), Some("my_game"));
+    }
+
+    #[test]
+    fn test_cli_flags_override_config() {
+        let config =
+            Config::from_toml("port = 15800\nprofile = \"release\"\napp = \"my_game\"").unwrap();
+        let (mut cli, matches) = parse(&[
+            "brp", "-p", "15702", "-P", "debug", "-a", "other", "-m", "ready",
+        ]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.port, 15702);
+        assert_eq!(cli.profile.as_deref(), Some("debug"));
+        assert_eq!(cli.app.as_deref(), Some("other"));
+    }
+
+    #[test]
+    fn test_env_overrides_config_but_not_flags() {
+        let env = |name: &str| match name {
+            "BRP_PORT" => Some("15900".to_string()),
+            "BRP_PROFILE" => Some("env-profile".to_string()),
+            "BRP_APP" => Some("env_game".to_string()),
+            "BRP_TIMEOUT" => Some("30".to_string()),
+            "BRP_OUTPUT" => Some("out.json".to_string()),
+            "BRP_TOKEN" => Some("s3cret".to_string()),
+            _ => None,
+        };
+        let config = Config::from_toml(
+            "port = 15800
+profile = \"release\"\napp = \"my_game\"",
+        )
+        .unwrap();
+        let (mut cli, matches) = parse(&["brp", "-P", "debug", "-m", "ready"]);
+        config.apply(&mut cli, &matches);
+        apply_env_overrides(&mut cli, &matches, env).unwrap();
+
+        assert_eq!(cli.port, 15900);
+        assert_eq!(cli.profile.as_deref(), Some("debug"));
+        assert_eq!(cli.app.as_deref(), Some("env_game"));
+        assert_eq!(cli.managed_timeout, Some(30));
+        assert_eq!(cli.output_file, Some(PathBuf::from("out.json")));
+        assert_eq!(cli.token.as_deref(), Some("s3cret"));
+    }
+
+    #[test]
+    fn test_invalid_env_port_is_rejected() {
+        let (mut cli, matches) = parse(&["brp", "ready"]);
+        let result = apply_env_overrides(&mut cli, &matches, |name| {
+            (name == "BRP_PORT").then(|| "not-a-port".to_string())
+        });
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_interactive_takes_app_and_timeout_from_config_and_env() {
+        let env = |name: &str| (name == "BRP_TIMEOUT").then(|| "45".to_string());
+        let config = Config::from_toml("app = \"my_game\"").unwrap();
+        let (mut cli, matches) = parse(&["brp", "--interactive"]);
+        cli.apply_interactive_default();
+        config.apply(&mut cli, &matches);
+        apply_env_overrides(&mut cli, &matches, env).unwrap();
+
+        assert_eq!(cli.app.as_deref(), Some("my_game"));
+        assert_eq!(cli.managed_timeout, Some(45));
+    }
+
+    #[test]
+    fn test_config_fills_timeout_and_output() {
+        let config = Config::from_toml("timeout = 20\noutput = \"result.json\"").unwrap();
+        let (mut cli, matches) = parse(&["brp", "-m", "ready"]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.managed_timeout, Some(20));
+        assert_eq!(cli.output_file, Some(PathBuf::from("result.json")));
+    }
+
+    #[test]
+    fn test_timeout_and_output_flags_override_config() {
+        let config = Config::from_toml("timeout = 20\noutput = \"result.json\"").unwrap();
+        let (mut cli, matches) = parse(&[
+            "brp",
+            "--managed-timeout",
+            "5",
+            "--output-file",
+            "other.json",
+            "-m",
+            "ready",
+        ]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.managed_timeout, Some(5));
+        assert_eq!(cli.output_file, Some(PathBuf::from("other.json")));
+    }
+
+    #[test]
+    fn test_config_timeout_ignored_without_managed_commands() {
+        let config = Config::from_toml("timeout = 20").unwrap();
+        let (mut cli, matches) = parse(&["brp", "ready"]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.managed_timeout, None);
+    }
+
+    #[test]
+    fn test_config_app_ignored_without_managed_or_detached() {
+        let config = Config::from_toml("app = \"my_game\"").unwrap();
+        let (mut cli, matches) = parse(&["brp", "ready"]);
+        config.apply(&mut cli, &matches);
+
+        assert_eq!(cli.app, None);
+    }
+}
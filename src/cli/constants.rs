@@ -30,25 +30,100 @@ pub const BEVY_MUTATE_RESOURCE: &str = "bevy/mutate_resource";
 pub const BEVY_REPARENT: &str = "bevy/reparent";
 pub const BEVY_REGISTRY_SCHEMA: &str = "bevy/registry/schema";
 
+// JSON-RPC built-in methods
+pub const RPC_DISCOVER: &str = "rpc.discover";
+
 // Streaming variants
 pub const BEVY_GET_WATCH: &str = "bevy/get+watch";
 pub const BEVY_LIST_WATCH: &str = "bevy/list+watch";
 
 // BRP Tool specific commands
 pub const BRP_TOOL_SCREENSHOT: &str = "brp_tool/screenshot";
+pub const BRP_TOOL_SCREENSHOT_DATA: &str = "brp_tool/screenshot_data";
 pub const BRP_TOOL_SHUTDOWN: &str = "brp_tool/shutdown";
 
+// Request constants
+/// Maximum number of JSON-RPC requests sent in one batch
+pub const BATCH_REQUEST_SIZE: usize = 100;
+
+/// Default number of `bevy/query` requests `list_entities` runs at once
+pub const LIST_ENTITIES_DEFAULT_CONCURRENCY: usize = 10;
+
+// Exit code constants
+/// Exit code for `query --fail-if-empty` when no entities matched
+pub const EXIT_CODE_EMPTY_QUERY: i32 = 3;
+/// Exit code when an `--assert` post-condition does not hold or `--expect` output differs
+pub const EXIT_CODE_ASSERTION_FAILED: i32 = 4;
+
 // Entity ID constants
 /// Type used for entity IDs in BRP commands
 pub const ENTITY_ID_TYPE: &str = "u64";
 /// Example entity ID for documentation and error messages
 pub const ENTITY_ID_EXAMPLE: &str = "12345";
 
+// Component type constants
+/// Relationship component pointing from a child to its parent
+pub const BEVY_CHILD_OF_COMPONENT: &str = "bevy_ecs::hierarchy::ChildOf";
+/// Relationship target component maintained by Bevy from each child's `ChildOf`
+pub const BEVY_CHILDREN_COMPONENT: &str = "bevy_ecs::hierarchy::Children";
+
+// Config file constants
+/// Directory under the user config directory holding the config file
+pub const CONFIG_DIR_NAME: &str = "brp";
+/// Name of the config file
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+// Environment variable constants
+/// Default for --port
+pub const ENV_PORT: &str = "BRP_PORT";
+/// Default for --profile
+pub const ENV_PROFILE: &str = "BRP_PROFILE";
+/// Default for --app
+pub const ENV_APP: &str = "BRP_APP";
+/// Default for --managed-timeout
+pub const ENV_TIMEOUT: &str = "BRP_TIMEOUT";
+/// Default for --output-file
+pub const ENV_OUTPUT: &str = "BRP_OUTPUT";
+/// Default for --token
+pub const ENV_TOKEN: &str = "BRP_TOKEN";
+
+// Undo constants
+/// Number of reversible operations `undo` remembers per session
+pub const UNDO_HISTORY_LIMIT: usize = 32;
+
 // Polling constants
 /// Polling interval in milliseconds used for waiting operations
 /// Used in support/port_utils.rs for wait_for_port_connectable
 pub const POLL_INTERVAL_MS: u64 = 50;
 
+/// Seconds an app launched with `--detached` or `--managed-commands` has to satisfy
+/// `--ready-when`
+pub const LAUNCH_READY_TIMEOUT_SECS: u64 = 30;
+
+/// Default number of seconds `wait_for` and `get --retry-on-empty` poll before giving up
+pub const WAIT_FOR_DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Default number of consecutive reconnect attempts for `--reconnect` watches
+pub const WATCH_RECONNECT_DEFAULT_MAX: u32 = 5;
+
+/// Delay before the first reconnect attempt; doubles with each consecutive attempt
+pub const WATCH_RECONNECT_BASE_DELAY_MS: u64 = 500;
+
+/// Upper bound on the delay between reconnect attempts
+pub const WATCH_RECONNECT_MAX_DELAY_MS: u64 = 10_000;
+/// Chunk size `--replay-sse` feeds a recording to the SSE parser in, small enough to split
+/// lines and multi-byte characters
+pub const SSE_REPLAY_CHUNK_BYTES: usize = 7;
+
+/// Default milliseconds between `get_resource+watch` polls
+pub const RESOURCE_WATCH_DEFAULT_INTERVAL_MS: u64 = 500;
+
+/// Default number of timed calls made by `bench`
+pub const BENCH_DEFAULT_ITERATIONS: u32 = 20;
+
+/// Polling interval in milliseconds between `wait_for` queries and `get --retry-on-empty` requests
+pub const WAIT_FOR_POLL_INTERVAL_MS: u64 = 250;
+
 /// Macro to include help text files and replace placeholders
 #[macro_export]
 macro_rules! include_help {
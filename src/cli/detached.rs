@@ -3,17 +3,20 @@
 use std::env;
 use std::fs::{self, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use sysinfo::{Pid, System};
 
 use super::cli_client;
-use super::constants::BIN_NAME;
-use super::support::{detect_bevy_app, find_workspace_binary_with_target_dir, poll_until_ready};
+use super::client::RemoteClient;
+use super::constants::{BIN_NAME, LAUNCH_READY_TIMEOUT_SECS};
+use super::support::{ReadyWhen, resolve_app_binary, wait_for_log_match};
+use crate::DEFAULT_REMOTE_PORT;
 
 /// Session information for a detached app
 #[derive(Debug)]
@@ -48,37 +51,33 @@ fn get_session_log_path(timestamp: u128) -> PathBuf {
     env::temp_dir().join(format!("{}_{}.log", get_session_prefix(), timestamp))
 }
 
+/// When an app started in detached mode counts as started
+#[derive(Debug, Clone, Copy)]
+pub struct DetachedReadiness<'a> {
+    /// Check the app must pass
+    pub ready_when: &'a ReadyWhen,
+    /// Pattern a line of the app's log output must match after `ready_when` passes
+    pub log_pattern: Option<&'a Regex>,
+    /// Poll cadence for both checks, instead of 100 ms
+    pub poll_interval: Option<Duration>,
+    /// Time each check gets before the app is killed, instead of 30 seconds
+    pub timeout: Option<Duration>,
+    /// Bearer token for an app whose plugin requires one
+    pub token: Option<&'a str>,
+}
+
 /// Start app in detached mode with auto-generated temp log file
 pub async fn start_detached(
     app_binary: Option<String>,
     port: u16,
     profile: Option<String>,
+    app_args: &[String],
+    env: &[(String, String)],
+    readiness: DetachedReadiness<'_>,
 ) -> Result<DetachedSession> {
-    // Determine which app to run and get its manifest directory and target directory
-    let (app_to_run, manifest_dir, target_dir) = detect_bevy_app(app_binary)?;
-    // Generate unique log file name in temp directory using process ID and timestamp
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|e| anyhow::anyhow!("Failed to get timestamp: {}", e))?
-        .as_millis();
-    let log_file = get_session_log_path(timestamp);
-
-    // Create log file
-    let mut file = File::create(&log_file)
-        .with_context(|| format!("Failed to create log file: {:?}", log_file))?;
-    writeln!(file, "=== BRP Tool Detached Session ===")?;
-    writeln!(file, "Started at: {:?}", std::time::SystemTime::now())?;
-    writeln!(file, "Port: {}", port)?;
-    writeln!(file, "App binary: {}", app_to_run)?;
-    writeln!(file, "============================================\n")?;
-    file.sync_all()?;
-
-    // Find full path to app binary using consolidated function
-    let app_path =
-        find_workspace_binary_with_target_dir(&app_to_run, &target_dir, profile.as_deref())?;
-
-    // Make the path absolute since we'll be changing directories
-    let app_path = std::fs::canonicalize(&app_path)?;
+    // Determine which app to run, its working directory and the absolute binary path
+    let (app_to_run, manifest_dir, app_path) = resolve_app_binary(app_binary, profile.as_deref())?;
+    let log_file = create_session_log(port, &app_to_run, app_args, env)?;
 
     // Start the app in background with output redirected to log file
     let log_file_for_redirect = File::options().append(true).open(&log_file)?;
@@ -101,11 +100,16 @@ pub async fn start_detached(
     )?;
     log_file_for_debug.sync_all()?;
 
+    // Only output written by the app, not the header above, may match --app-ready-regex
+    let app_output_start = fs::metadata(&log_file)?.len();
+
     let child = Command::new(&app_path)
         .current_dir(&manifest_dir)
         .env("CARGO_MANIFEST_DIR", &manifest_dir)
+        .envs(env.iter().map(|(key, value)| (key, value)))
         .arg("--port")
         .arg(port.to_string())
+        .args(app_args)
         .stdout(Stdio::from(log_file_for_redirect.try_clone()?))
         .stderr(Stdio::from(log_file_for_redirect))
         .spawn()
@@ -117,18 +121,32 @@ pub async fn start_detached(
     println!("Starting app in detached mode...");
     println!("Log file: {:?}", log_file);
 
-    let app_ready = poll_until_ready(
-        || async move {
-            match cli_client::detect_running_instances(port).await {
-                Ok(instances) if instances.contains(&port) => Ok(()),
-                _ => Err(anyhow::anyhow!("App not responding")),
-            }
-        },
-        Duration::from_secs(30),
-        Duration::from_millis(100),
-        "Timeout waiting for app to start. Check log file for errors.",
-    )
-    .await;
+    let timeout = readiness
+        .timeout
+        .unwrap_or(Duration::from_secs(LAUNCH_READY_TIMEOUT_SECS));
+    let poll_interval = readiness
+        .poll_interval
+        .unwrap_or(Duration::from_millis(100));
+    let client = match readiness.token {
+        Some(token) => RemoteClient::new(port).with_token(token),
+        None => RemoteClient::new(port),
+    };
+    let app_ready = async {
+        cli_client::wait_for_launched_app(
+            &client,
+            readiness.ready_when,
+            timeout,
+            Some(poll_interval),
+        )
+        .await?;
+        if let Some(pattern) = readiness.log_pattern {
+            wait_for_log_match(&log_file, app_output_start, pattern, timeout, poll_interval)
+                .await?;
+        }
+        anyhow::Ok(())
+    }
+    .await
+    .map_err(|e| anyhow::anyhow!("{}\nCheck log file for errors.", e));
 
     if let Err(error) = app_ready {
         // Try to clean up the process
@@ -139,25 +157,77 @@ pub async fn start_detached(
 
     println!("App started successfully on port {}", port);
 
-    // Save session info to temp directory
-    let session_info = SessionInfo {
+    let session = DetachedSession {
         pid,
         port,
-        log_file: log_file.clone(),
+        log_file,
+    };
+    save_session_info(&session, &app_to_run)?;
+    Ok(session)
+}
+
+/// Describe where a started session runs and how to inspect or stop it
+pub fn session_summary(session: &DetachedSession) -> String {
+    let port_arg = if session.port == DEFAULT_REMOTE_PORT {
+        String::new()
+    } else {
+        format!(" -p {}", session.port)
+    };
+    format!(
+        "\nDetached session started:\n  PID: {}\n  Port: {}\n  Log file: {:?}\n\n\
+         Use '{}{} --info' to get session details\n\
+         Use '{}{} shutdown' to stop the app",
+        session.pid, session.port, session.log_file, BIN_NAME, port_arg, BIN_NAME, port_arg
+    )
+}
+
+/// Create a session log file in the temp directory with a header describing the launch
+pub fn create_session_log(
+    port: u16,
+    app_binary: &str,
+    app_args: &[String],
+    env: &[(String, String)],
+) -> Result<PathBuf> {
+    // Generate unique log file name in temp directory using process ID and timestamp
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| anyhow::anyhow!("Failed to get timestamp: {}", e))?
+        .as_millis();
+    let log_file = get_session_log_path(timestamp);
+
+    // Create log file
+    let mut file = File::create(&log_file)
+        .with_context(|| format!("Failed to create log file: {:?}", log_file))?;
+    writeln!(file, "=== BRP Tool Detached Session ===")?;
+    writeln!(file, "Started at: {:?}", std::time::SystemTime::now())?;
+    writeln!(file, "Port: {}", port)?;
+    writeln!(file, "App binary: {}", app_binary)?;
+    if !app_args.is_empty() {
+        writeln!(file, "App args: {:?}", app_args)?;
+    }
+    for (key, value) in env {
+        writeln!(file, "App env: {}={}", key, value)?;
+    }
+    writeln!(file, "============================================\n")?;
+    file.sync_all()?;
+
+    Ok(log_file)
+}
+
+/// Record `session` in the temp directory so `--info` and `kill` can find it
+pub fn save_session_info(session: &DetachedSession, app_binary: &str) -> Result<()> {
+    let session_info = SessionInfo {
+        pid: session.pid,
+        port: session.port,
+        log_file: session.log_file.clone(),
         start_time: SystemTime::now(),
-        app_binary: app_to_run.clone(),
+        app_binary: app_binary.to_string(),
     };
 
-    let session_info_path = get_session_info_path(port);
+    let session_info_path = get_session_info_path(session.port);
     let session_json = serde_json::to_string_pretty(&session_info)?;
     fs::write(&session_info_path, session_json)
-        .with_context(|| format!("Failed to save session info to {:?}", session_info_path))?;
-
-    Ok(DetachedSession {
-        pid,
-        port,
-        log_file,
-    })
+        .with_context(|| format!("Failed to save session info to {:?}", session_info_path))
 }
 
 /// Get information about a running detached session
@@ -167,7 +237,7 @@ pub async fn get_session_info(port: u16) -> Result<Option<serde_json::Value>> {
 
     if !session_info_path.exists() {
         // No session info file - check if app is running anyway
-        let instances = cli_client::detect_running_instances(port).await?;
+        let instances = cli_client::detect_running_instances(port, None).await?;
         if instances.contains(&port) {
             return Ok(Some(serde_json::json!({
                 "app_running": true,
@@ -185,7 +255,7 @@ pub async fn get_session_info(port: u16) -> Result<Option<serde_json::Value>> {
     let session_info: SessionInfo = serde_json::from_str(&contents)?;
 
     // Check if app is still running
-    let instances = cli_client::detect_running_instances(port).await?;
+    let instances = cli_client::detect_running_instances(port, None).await?;
     let app_running = instances.contains(&port);
 
     // Calculate uptime
@@ -227,6 +297,22 @@ fn is_process_alive(pid: u32) -> bool {
     system.process(Pid::from_u32(pid)).is_some()
 }
 
+/// Pick the most recently started live detached session among `ports`
+///
+/// Ports without a session file (apps started by hand) and sessions whose process has exited
+/// are skipped.
+pub fn newest_session_port(ports: &[u16]) -> Option<u16> {
+    ports
+        .iter()
+        .filter_map(|&port| {
+            let contents = fs::read_to_string(get_session_info_path(port)).ok()?;
+            serde_json::from_str::<SessionInfo>(&contents).ok()
+        })
+        .filter(|session_info| is_process_alive(session_info.pid))
+        .max_by_key(|session_info| session_info.start_time)
+        .map(|session_info| session_info.port)
+}
+
 /// Format duration in human-readable format
 fn format_duration(seconds: u64) -> String {
     let hours = seconds / 3600;
@@ -243,7 +329,9 @@ fn format_duration(seconds: u64) -> String {
 }
 
 /// Kill a process by PID (cross-platform)
-fn kill_process(pid: u32) -> Result<()> {
+///
+/// Returns whether a kill signal was sent. A process that is already gone is not an error.
+fn kill_process(pid: u32) -> Result<bool> {
     let mut system = System::new();
     system.refresh_processes(
         sysinfo::ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
@@ -251,22 +339,66 @@ fn kill_process(pid: u32) -> Result<()> {
     );
 
     if let Some(process) = system.process(Pid::from_u32(pid)) {
-        process.kill();
-        Ok(())
+        Ok(process.kill())
     } else {
         // Process not found - this is not an error, it might have already exited
-        Ok(())
+        Ok(false)
+    }
+}
+
+/// Forcibly kill the detached session on a port and remove its session info and log files
+///
+/// This is the last resort for an app that no longer answers `brp_tool/shutdown`.
+pub fn kill_session(port: u16) -> Result<serde_json::Value> {
+    let session_info_path = get_session_info_path(port);
+    if !session_info_path.exists() {
+        anyhow::bail!("No detached session found on port {}", port);
     }
+
+    let contents = fs::read_to_string(&session_info_path)
+        .with_context(|| format!("Failed to read session info {:?}", session_info_path))?;
+    let session_info: SessionInfo = serde_json::from_str(&contents)?;
+
+    let process_found = is_process_alive(session_info.pid);
+    let killed = process_found && kill_process(session_info.pid)?;
+
+    // The log may already have been removed by --cleanup-logs
+    let _ = fs::remove_file(&session_info.log_file);
+    fs::remove_file(&session_info_path)
+        .with_context(|| format!("Failed to remove session info {:?}", session_info_path))?;
+
+    Ok(serde_json::json!({
+        "port": port,
+        "pid": session_info.pid,
+        "process_found": process_found,
+        "killed": killed,
+        "log_file": session_info.log_file.to_string_lossy(),
+    }))
+}
+
+/// Whether `path` was modified less than `age` ago; unreadable files count as old
+async fn modified_within(path: &Path, age: Duration) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|elapsed| elapsed < age)
 }
 
 /// Clean up all session log files and info files
-pub async fn cleanup_all_logs() -> Result<()> {
+///
+/// Files of active sessions are always kept. With `older_than`, an inactive session is only
+/// removed once its log has gone unmodified for that long, so recent crash logs survive.
+pub async fn cleanup_all_logs(older_than: Option<Duration>) -> Result<()> {
     let temp_dir = env::temp_dir();
     let mut cleaned_count = 0;
     let mut preserved_count = 0;
+    let mut recent_count = 0;
     let mut error_count = 0;
     let session_prefix = get_session_prefix();
     let mut active_session_files = std::collections::HashSet::new();
+    let mut recent_session_files = std::collections::HashSet::new();
 
     // First pass: identify active sessions by reading all JSON files
     let mut entries = tokio::fs::read_dir(&temp_dir).await?;
@@ -292,6 +424,15 @@ pub async fn cleanup_all_logs() -> Result<()> {
                                         "Found active session on port {} (PID: {})",
                                         session_info.port, session_info.pid
                                     );
+                                } else if let Some(age) = older_than {
+                                    // An inactive session is as old as its log's last write
+                                    if let Some(log_file_name) = session_info.log_file.file_name() {
+                                        let log_path = temp_dir.join(log_file_name);
+                                        if modified_within(&log_path, age).await {
+                                            recent_session_files.insert(path.clone());
+                                            recent_session_files.insert(log_path);
+                                        }
+                                    }
                                 }
                             }
                             Err(e) => {
@@ -320,6 +461,15 @@ pub async fn cleanup_all_logs() -> Result<()> {
             if file_name_str.starts_with(&session_prefix)
                 && (file_name_str.ends_with(".log") || file_name_str.ends_with(".json"))
             {
+                // Logs without a session file are judged by their own modification time
+                let recent = match older_than {
+                    Some(age) => {
+                        recent_session_files.contains(&path)
+                            || (file_name_str.ends_with(".log")
+                                && modified_within(&path, age).await)
+                    }
+                    None => false,
+                };
                 if active_session_files.contains(&path) {
                     // This file belongs to an active session - preserve it
                     let file_type = if file_name_str.ends_with(".log") {
@@ -329,6 +479,9 @@ pub async fn cleanup_all_logs() -> Result<()> {
                     };
                     println!("Preserving active {}: {}", file_type, file_name_str);
                     preserved_count += 1;
+                } else if recent {
+                    // Inactive, but too recent for --older-than - keep it for debugging
+                    recent_count += 1;
                 } else {
                     // This file doesn't belong to an active session - remove it
                     match tokio::fs::remove_file(&path).await {
@@ -351,7 +504,7 @@ pub async fn cleanup_all_logs() -> Result<()> {
         }
     }
 
-    if cleaned_count == 0 && error_count == 0 && preserved_count == 0 {
+    if cleaned_count == 0 && error_count == 0 && preserved_count == 0 && recent_count == 0 {
         println!("No {} session files found", BIN_NAME);
     } else {
         println!("\nCleanup complete:");
@@ -361,6 +514,12 @@ pub async fn cleanup_all_logs() -> Result<()> {
         if preserved_count > 0 {
             println!("  - {} active session files preserved", preserved_count);
         }
+        if recent_count > 0 {
+            println!(
+                "  - {} inactive files kept as newer than --older-than",
+                recent_count
+            );
+        }
         if error_count > 0 {
             println!("  - {} files could not be removed (errors)", error_count);
         }
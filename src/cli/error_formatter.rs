@@ -1,7 +1,45 @@
+use std::io::IsTerminal;
+
 use clap::CommandFactory;
 
-use crate::cli::commands::Cli;
-use crate::cli::constants::{ENTITY_ID_EXAMPLE, ENTITY_ID_TYPE};
+use crate::cli::commands::{Cli, EmptyQueryError};
+use crate::cli::constants::{
+    BIN_NAME, ENTITY_ID_EXAMPLE, ENTITY_ID_TYPE, EXIT_CODE_ASSERTION_FAILED, EXIT_CODE_EMPTY_QUERY,
+};
+use crate::cli::support::{AssertionFailure, ExpectationFailure, is_connection_error};
+
+// JSON-RPC and BRP error codes (mirrors `bevy::remote::error_codes`)
+const METHOD_NOT_FOUND: i64 = -32601;
+const ENTITY_NOT_FOUND: i64 = -23401;
+const COMPONENT_ERROR: i64 = -23402;
+const COMPONENT_NOT_PRESENT: i64 = -23403;
+const RESOURCE_ERROR: i64 = -23501;
+const RESOURCE_NOT_PRESENT: i64 = -23502;
+
+// ANSI escape sequences for colored error output
+const RED_BOLD: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// A friendlier description of a known error, with a concrete next step
+struct ErrorHint {
+    summary: &'static str,
+    suggestion: String,
+}
+
+/// Process exit code for an error returned by a command
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    if error.downcast_ref::<EmptyQueryError>().is_some() {
+        EXIT_CODE_EMPTY_QUERY
+    } else if error.downcast_ref::<AssertionFailure>().is_some()
+        || error.downcast_ref::<ExpectationFailure>().is_some()
+    {
+        EXIT_CODE_ASSERTION_FAILED
+    } else {
+        1
+    }
+}
 
 /// Display enhanced error messages for missing arguments
 pub fn display_missing_args_error(command_name: &str, missing_args: &[(String, String, String)]) {
@@ -207,3 +245,183 @@ pub fn get_command_args(command: &str) -> Vec<(String, String, String)> {
 
     args
 }
+
+/// Get the `--flag` options of a command with their help text, using Clap introspection
+pub fn get_command_options(command: &str) -> Vec<(String, String)> {
+    let cli = Cli::command();
+    let Some(subcmd) = cli.find_subcommand(command) else {
+        return vec![];
+    };
+    subcmd
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && arg.get_id().as_str() != "help")
+        .filter_map(|arg| {
+            let long = arg.get_long()?;
+            let help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+            Some((format!("--{}", long), help))
+        })
+        .collect()
+}
+
+/// Decide whether error output should be colorized
+///
+/// Color is disabled by `--no-color`, by a non-empty `NO_COLOR` environment variable,
+/// or when stderr is not a terminal.
+pub fn should_use_color(no_color_flag: bool) -> bool {
+    if no_color_flag {
+        return false;
+    }
+    if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    std::io::stderr().is_terminal()
+}
+
+/// Display a top-level error with a friendly summary and suggestion when the error is recognized
+///
+/// The original error message is always shown. With `verbose`, the full error chain is
+/// printed as well.
+pub fn display_error(error: &anyhow::Error, use_color: bool, verbose: bool) {
+    let paint = |style: &str, text: &str| {
+        if use_color {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let message = error.to_string();
+    match hint_for_error(&message) {
+        Some(hint) => {
+            eprintln!("{} {}", paint(RED_BOLD, "error:"), hint.summary);
+            eprintln!("  {}", paint(DIM, &message));
+            eprintln!("{} {}", paint(YELLOW, "hint:"), hint.suggestion);
+        }
+        None => {
+            eprintln!("{} {}", paint(RED_BOLD, "error:"), message);
+        }
+    }
+
+    if verbose {
+        eprintln!();
+        eprintln!("{}", paint(DIM, "Full error:"));
+        eprintln!("{:?}", error);
+    }
+}
+
+/// Split a `Remote error [code]: message` string into its code and message
+fn parse_remote_error(message: &str) -> Option<(i64, &str)> {
+    let rest = message.strip_prefix("Remote error [")?;
+    let (code, remote_message) = rest.split_once("]: ")?;
+    Some((code.parse().ok()?, remote_message))
+}
+
+/// Map known error signatures to a friendlier summary and suggestion
+fn hint_for_error(message: &str) -> Option<ErrorHint> {
+    if is_connection_error(message) {
+        return Some(ErrorHint {
+            summary: "Could not connect to the app",
+            suggestion: format!(
+                "start the app first, or let {} start it with --managed-commands or --detached",
+                BIN_NAME
+            ),
+        });
+    }
+
+    let (code, remote_message) = match parse_remote_error(message) {
+        Some(parsed) => parsed,
+        None => {
+            // Local errors that don't come from the server
+            if message.contains("does not exist") {
+                return Some(entity_not_found_hint());
+            }
+            if message.contains("at line") && message.contains("column") {
+                return Some(ErrorHint {
+                    summary: "Invalid JSON",
+                    suggestion: "check the JSON payload - wrap it in single quotes so the shell \
+                                 keeps the double quotes intact"
+                        .to_string(),
+                });
+            }
+            return None;
+        }
+    };
+    let remote_lower = remote_message.to_lowercase();
+
+    match code {
+        METHOD_NOT_FOUND => Some(ErrorHint {
+            summary: "The app does not support this method",
+            suggestion: format!(
+                "run `{} methods` to see what the app supports - brp_tool/* methods require \
+                 BrpToolPlugin",
+                BIN_NAME
+            ),
+        }),
+        ENTITY_NOT_FOUND => Some(entity_not_found_hint()),
+        COMPONENT_ERROR | COMPONENT_NOT_PRESENT => Some(ErrorHint {
+            summary: if code == COMPONENT_NOT_PRESENT {
+                "The entity does not have this component"
+            } else {
+                "Unknown or unusable component type"
+            },
+            suggestion: format!(
+                "run `{} list` to see valid component names - names must be fully qualified and \
+                 the type must be registered with Reflect, Serialize and Deserialize",
+                BIN_NAME
+            ),
+        }),
+        RESOURCE_ERROR | RESOURCE_NOT_PRESENT => Some(resource_hint()),
+        _ if remote_lower.contains("resource") => Some(resource_hint()),
+        _ if remote_lower.contains("unknown component") => Some(ErrorHint {
+            summary: "Unknown component type",
+            suggestion: format!("run `{} list` to see valid component names", BIN_NAME),
+        }),
+        _ => None,
+    }
+}
+
+fn entity_not_found_hint() -> ErrorHint {
+    ErrorHint {
+        summary: "Entity not found",
+        suggestion: format!(
+            "run `{} list_entities` to see the IDs of existing entities",
+            BIN_NAME
+        ),
+    }
+}
+
+fn resource_hint() -> ErrorHint {
+    ErrorHint {
+        summary: "Unknown or unregistered resource",
+        suggestion: format!(
+            "run `{} list_resources` to see valid resource names - resources must be registered \
+             with Reflect, Serialize and Deserialize",
+            BIN_NAME
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_error() {
+        let parsed = parse_remote_error("Remote error [-23401]: Entity 42 not found");
+        assert_eq!(parsed, Some((ENTITY_NOT_FOUND, "Entity 42 not found")));
+        assert_eq!(parse_remote_error("Connection refused"), None);
+    }
+
+    #[test]
+    fn test_hint_for_known_errors() {
+        let hint = hint_for_error("Remote error [-23402]: Unknown component type: `Transform`")
+            .expect("component errors should have a hint");
+        assert!(hint.suggestion.contains("list"));
+
+        let hint = hint_for_error("Remote error [-32601]: Method `foo/bar` not found")
+            .expect("method errors should have a hint");
+        assert!(hint.suggestion.contains("methods"));
+
+        assert!(hint_for_error("Something unexpected happened").is_none());
+    }
+}
@@ -1,26 +1,49 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
+use serde::Serialize;
+use serde_json::json;
 use strum::IntoEnumIterator;
 
 use super::commands::{CommandTemplate, commands_by_category, find_command_by_name};
 use super::constants::{BEVY_COMMAND_PREFIX, BIN_NAME, BRP_TOOL_COMMAND_PREFIX};
-use super::{help_builder, support};
+use super::{error_formatter, help_builder, support};
 use crate::include_help;
 
+/// A Bevy app detected in the current workspace, as reported by `--detect`
+#[derive(Serialize)]
+struct DetectedApp {
+    app_name: String,
+    /// `None` until the app has been built for the selected profile
+    binary_path: Option<PathBuf>,
+    manifest_dir: Option<PathBuf>,
+    target_dir: Option<PathBuf>,
+    built: bool,
+}
+
 /// Get the detected app information with binary path if available
-fn get_detected_app_info(profile: Option<&str>) -> Option<(String, Option<std::path::PathBuf>)> {
+fn get_detected_app_info(profile: Option<&str>) -> Option<DetectedApp> {
     help_builder::get_detected_app().map(|app_name| {
         match support::detect_bevy_app(Some(app_name.clone())) {
-            Ok((_, _, target_dir)) => {
-                match support::find_workspace_binary_with_target_dir(
-                    &app_name,
-                    &target_dir,
-                    profile,
-                ) {
-                    Ok(binary_path) => (app_name, Some(binary_path)),
-                    Err(_) => (app_name, None),
+            Ok((_, manifest_dir, target_dir)) => {
+                let binary_path =
+                    support::find_workspace_binary_with_target_dir(&app_name, &target_dir, profile)
+                        .ok();
+                DetectedApp {
+                    app_name,
+                    built: binary_path.is_some(),
+                    binary_path,
+                    manifest_dir: Some(manifest_dir),
+                    target_dir: Some(target_dir),
                 }
             }
-            Err(_) => (app_name, None),
+            Err(_) => DetectedApp {
+                app_name,
+                binary_path: None,
+                manifest_dir: None,
+                target_dir: None,
+                built: false,
+            },
         }
     })
 }
@@ -29,7 +52,11 @@ fn get_detected_app_info(profile: Option<&str>) -> Option<(String, Option<std::p
 fn replace_detected_app(text: &str, profile: Option<&str>) -> String {
     if text.contains("{{DETECTED_APP}}") {
         let detected_app_info = match get_detected_app_info(profile) {
-            Some((app_name, binary_path)) => match binary_path {
+            Some(DetectedApp {
+                app_name,
+                binary_path,
+                ..
+            }) => match binary_path {
                 Some(path) => format!("  Detected app: {} (binary: {})", app_name, path.display()),
                 None => format!("  Detected app: {}", app_name),
             },
@@ -61,16 +88,7 @@ pub fn display_command_help(command: &str, profile: Option<&str>) {
     match find_command_by_name(command) {
         Some(cmd) => {
             let primary_name = cmd.primary_name();
-            // Extract short name (without prefix)
-            let short_name = if primary_name == "bevy/registry/schema" {
-                "schema"
-            } else if let Some(name) = primary_name.strip_prefix(BEVY_COMMAND_PREFIX) {
-                name
-            } else if let Some(name) = primary_name.strip_prefix(BRP_TOOL_COMMAND_PREFIX) {
-                name
-            } else {
-                primary_name
-            };
+            let short_name = short_name(primary_name);
 
             println!("Help for command: {}", short_name);
             if primary_name != short_name {
@@ -164,6 +182,55 @@ pub fn display_command_help(command: &str, profile: Option<&str>) {
     }
 }
 
+/// Extract the short command name (without prefix) used on the command line
+fn short_name(primary_name: &str) -> &str {
+    if primary_name == "bevy/registry/schema" {
+        "schema"
+    } else if let Some(name) = primary_name.strip_prefix(BEVY_COMMAND_PREFIX) {
+        name
+    } else if let Some(name) = primary_name.strip_prefix(BRP_TOOL_COMMAND_PREFIX) {
+        name
+    } else {
+        primary_name
+    }
+}
+
+/// Print the command catalog as JSON, for editor integrations and documentation generators
+///
+/// Commands appear in the same order as `--list-commands`, each with its names, brief,
+/// category and the positional arguments and options it accepts.
+pub fn display_all_commands_json() -> Result<()> {
+    let mut catalog = Vec::new();
+    for (category, commands) in commands_by_category() {
+        for cmd in commands {
+            let primary_name = cmd.primary_name();
+            let name = short_name(primary_name);
+            let arguments: Vec<_> = error_formatter::get_command_args(name)
+                .into_iter()
+                .map(|(arg, arg_type, example)| {
+                    json!({ "name": arg, "type": arg_type, "example": example })
+                })
+                .collect();
+            let options: Vec<_> = error_formatter::get_command_options(name)
+                .into_iter()
+                .map(|(option, help)| json!({ "name": option, "help": help }))
+                .collect();
+            catalog.push(json!({
+                "name": name,
+                "brp_method": primary_name.contains('/').then_some(primary_name),
+                "names": cmd.names(),
+                "brief": cmd.brief_description(),
+                "category": category.key(),
+                "category_title": category.to_string(),
+                "arguments": arguments,
+                "options": options,
+            }));
+        }
+    }
+    println!("{}", support::format_json(&json!({ "commands": catalog }))?);
+    Ok(())
+}
+
 /// Display all available commands organized by category
 pub fn display_all_commands() {
     println!("======================================");
@@ -181,25 +248,16 @@ pub fn display_all_commands() {
             println!("{}:", category);
             for cmd in commands {
                 let primary_name = cmd.primary_name();
-                let display_name = if primary_name == "bevy/registry/schema" {
-                    "schema"
-                } else if let Some(name) = primary_name.strip_prefix(BEVY_COMMAND_PREFIX) {
-                    name
-                } else if let Some(name) = primary_name.strip_prefix(BRP_TOOL_COMMAND_PREFIX) {
-                    name
-                } else {
-                    primary_name
-                };
+                let display_name = short_name(primary_name);
 
                 // Create padded command display
                 let padded_display = format!("{:<17}", display_name);
 
                 // Handle commands without a bevy namespace
-                let padded_primary = match primary_name {
-                    "ready" | "methods" | "list_entities" | "list_entity" | "raw" => {
-                        format!("{:<22}", "[composite command]")
-                    }
-                    _ => format!("{:<22}", primary_name),
+                let padded_primary = if primary_name.contains('/') {
+                    format!("{:<22}", primary_name)
+                } else {
+                    format!("{:<22}", "[composite command]")
                 };
 
                 println!(
@@ -312,13 +370,35 @@ pub fn display_brp_configuration() {
     println!("{}", include_help!("brp"));
 }
 
+/// Print detected app information as JSON, for editor integrations and scripts
+///
+/// Every field is present even when no app is found, so consumers can rely on the shape.
+pub fn display_detected_app_json(profile: Option<&str>) -> Result<()> {
+    let info = match get_detected_app_info(profile) {
+        Some(info) => serde_json::to_value(info)?,
+        None => json!({
+            "app_name": null,
+            "binary_path": null,
+            "manifest_dir": null,
+            "target_dir": null,
+            "built": false,
+        }),
+    };
+    println!("{}", support::format_json(&info)?);
+    Ok(())
+}
+
 /// Display detected app information
 pub fn display_detected_app(profile: Option<&str>) -> Result<()> {
     println!("🔍 Bevy App Detection");
     println!("===================");
 
     match get_detected_app_info(profile) {
-        Some((app_name, binary_path)) => {
+        Some(DetectedApp {
+            app_name,
+            binary_path,
+            ..
+        }) => {
             println!("✅ Detected app: {}", app_name);
 
             match binary_path {
@@ -331,12 +411,10 @@ pub fn display_detected_app(profile: Option<&str>) -> Result<()> {
                 None => {
                     println!("⚠️  Binary not found");
                     println!("   The app was detected in Cargo.toml but binary doesn't exist");
-                    match profile {
-                        Some(p) if p != "debug" => {
-                            println!("   Try running: cargo build --profile {}", p)
-                        }
-                        _ => println!("   Try running: cargo build"),
-                    }
+                    println!(
+                        "   Try running: {}",
+                        support::build_command(&app_name, profile)
+                    );
                 }
             }
         }
@@ -1,107 +1,309 @@
+use std::fs::File;
+use std::io::Write;
+use std::process::Stdio;
 use std::time::Duration;
 
 use anyhow::Result;
+use serde_json::{Value, json};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::time::sleep;
+use tokio::time::{sleep, timeout};
 
-use super::cli_client::{execute_command, wait_for_app_ready};
+use super::cli_client::{execute_command, summarize_failures, wait_for_launched_app};
 use super::client::RemoteClient;
+use super::commands::{
+    ExecutionOptions, parse_command_string, pending_confirmation, split_command_list,
+};
+use super::constants::{BIN_NAME, LAUNCH_READY_TIMEOUT_SECS};
+use super::detached::{self, DetachedSession};
 use super::support::{
-    detect_bevy_app, find_workspace_binary_with_target_dir, is_port_available,
-    wait_for_port_connectable,
+    ReadyWhen, ask_confirmation, check_confirmation, collect_output, is_port_available, print_json,
+    resolve_app_binary, take_collected_output, wait_for_port_connectable,
 };
 use crate::DEFAULT_REMOTE_PORT;
 
+/// How `run_managed` runs its command list and what happens to the app afterwards
+#[derive(Debug, Clone)]
+pub struct ManagedRun<'a> {
+    /// Comma-separated commands to execute
+    pub commands: String,
+    /// Setup commands run before `commands`; if they fail, `commands` are skipped
+    pub before: Option<String>,
+    /// Teardown commands run after `commands`, even when the setup or main commands failed
+    pub after: Option<String>,
+    /// When the started app counts as ready for the first command
+    pub ready_when: &'a ReadyWhen,
+    /// Time the app gets to become ready, instead of 30 seconds
+    pub ready_timeout: Option<Duration>,
+    /// Bearer token for an app whose plugin requires one
+    pub token: Option<String>,
+    /// Stop the app and fail if the commands take longer than this
+    pub command_timeout: Option<Duration>,
+    /// Leave the app running as a detached session once every command has succeeded
+    pub keep_alive: bool,
+    /// Print one JSON array of `{command, ok, result|error}` at the end instead of each
+    /// result as it arrives, with progress messages moved to stderr
+    pub json_results: bool,
+    /// Prompt for commands on stdin once `commands` have succeeded, until `exit` or end of input
+    pub interactive: bool,
+    /// Run disruptive commands typed at the prompt without asking first
+    pub yes: bool,
+}
+
 /// Run in managed mode (start app and manage lifecycle)
+///
+/// With `keep_alive`, app output goes to a session log instead of the terminal and the app is
+/// left running as a detached session once every command has succeeded.
 pub async fn run_managed(
     app: Option<String>,
-    commands: Option<String>,
     requested_port: u16,
     profile: Option<String>,
+    app_args: &[String],
+    env: &[(String, String)],
+    run: ManagedRun<'_>,
+    options: ExecutionOptions,
 ) -> Result<()> {
-    // Determine which app to run and get its manifest directory and target directory
-    let (app_to_run, manifest_dir, target_dir) = detect_bevy_app(app)?;
-
-    // Find the app binary in the workspace using the target directory
-    let app_path =
-        find_workspace_binary_with_target_dir(&app_to_run, &target_dir, profile.as_deref())?;
-    // Make the path absolute since we'll be changing directories
-    let app_path = std::fs::canonicalize(&app_path)?;
-    println!("Starting app: {}", app_path.display());
+    let ManagedRun {
+        commands,
+        before,
+        after,
+        ready_when,
+        ready_timeout,
+        token,
+        command_timeout,
+        keep_alive,
+        json_results,
+        interactive,
+        yes,
+    } = run;
+
+    // Determine which app to run, its working directory and the absolute binary path
+    let (app_to_run, manifest_dir, app_path) = resolve_app_binary(app, profile.as_deref())?;
+    status(
+        json_results,
+        format!("Starting app: {}", app_path.display()),
+    );
 
     // Pick an appropriate port: use random if default was requested, otherwise use what user
     // specified
     let port = if requested_port == DEFAULT_REMOTE_PORT {
-        pick_random_available_port().await?
+        let port = pick_random_available_port().await?;
+        status(json_results, format!("Selected random port: {}", port));
+        port
     } else {
         requested_port
     };
 
     // Use the manifest directory for the working directory and CARGO_MANIFEST_DIR
     // This ensures assets are found relative to the crate's location
-    println!("Using manifest directory: {:?}", manifest_dir);
+    status(
+        json_results,
+        format!("Using manifest directory: {:?}", manifest_dir),
+    );
+
+    // A kept-alive app outlives this process, so its output can't be piped back to us
+    let log_file = if keep_alive {
+        let log_file = detached::create_session_log(port, &app_to_run, app_args, env)?;
+        status(
+            json_results,
+            format!("App output is written to log file: {:?}", log_file),
+        );
+        Some(log_file)
+    } else {
+        None
+    };
+    let (stdout, stderr) = match &log_file {
+        Some(log_file) => {
+            let log = File::options().append(true).open(log_file)?;
+            (Stdio::from(log.try_clone()?), Stdio::from(log))
+        }
+        None => (Stdio::piped(), Stdio::piped()),
+    };
 
     // Spawn the subprocess with custom port
     let mut child = Command::new(&app_path)
         .current_dir(&manifest_dir)
         .env("CARGO_MANIFEST_DIR", &manifest_dir)
+        .envs(env.iter().map(|(key, value)| (key, value)))
         .arg("--port")
         .arg(port.to_string())
-        .kill_on_drop(true)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
+        .args(app_args)
+        .kill_on_drop(!keep_alive)
+        .stdout(stdout)
+        .stderr(stderr)
         .spawn()?;
 
-    // Set up output streaming
-    let stdout = child
-        .stdout
-        .take()
-        .ok_or_else(|| anyhow::anyhow!("Failed to get stdout"))?;
-    let stderr = child
-        .stderr
-        .take()
-        .ok_or_else(|| anyhow::anyhow!("Failed to get stderr"))?;
-
     // Spawn tasks to print stdout/stderr
-    let app_name = app_to_run.clone();
-    let stdout_task = tokio::spawn(async move {
-        let reader = BufReader::new(stdout);
-        let mut lines = reader.lines();
-        while let Some(line) = lines.next_line().await.unwrap_or(None) {
-            println!("[{}] {}", app_name, line);
-        }
-    });
-
-    let app_name = app_to_run.clone();
-    let stderr_task = tokio::spawn(async move {
-        let reader = BufReader::new(stderr);
-        let mut lines = reader.lines();
-        while let Some(line) = lines.next_line().await.unwrap_or(None) {
-            eprintln!("[{}] {}", app_name, line);
-        }
-    });
+    let mut output_tasks = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let app_name = app_to_run.clone();
+        output_tasks.push(tokio::spawn(async move {
+            let reader = BufReader::new(stdout);
+            let mut lines = reader.lines();
+            while let Some(line) = lines.next_line().await.unwrap_or(None) {
+                status(json_results, format!("[{}] {}", app_name, line));
+            }
+        }));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let app_name = app_to_run.clone();
+        output_tasks.push(tokio::spawn(async move {
+            let reader = BufReader::new(stderr);
+            let mut lines = reader.lines();
+            while let Some(line) = lines.next_line().await.unwrap_or(None) {
+                eprintln!("[{}] {}", app_name, line);
+            }
+        }));
+    }
 
     // Wait for app to start by checking if port is available
-    wait_for_port(port, Duration::from_secs(10)).await?;
-    println!(
-        "\nApp started on port {}. Ready for remote commands.\n",
-        port
+    // A kept-alive child isn't killed on drop, so stop it explicitly
+    if let Err(error) = wait_for_port(port, Duration::from_secs(10)).await {
+        child.kill().await?;
+        return Err(error);
+    }
+    status(
+        json_results,
+        format!(
+            "\nApp started on port {}. Ready for remote commands.\n",
+            port
+        ),
     );
 
-    // Execute the command list
-    if let Some(commands) = commands {
-        run_command_list(commands, port).await?;
-    } else {
-        anyhow::bail!("No commands provided for managed mode");
+    // Execute the command lists, each bounded by the command timeout if one was given
+    let client = RemoteClient::new(port).with_brp_version(options.brp_version);
+    let client = match token {
+        Some(token) => client.with_token(token),
+        None => client,
+    };
+    let mut batch = Vec::new();
+    let main_lists = async {
+        // Ensure app is ready before executing commands
+        wait_for_launched_app(
+            &client,
+            ready_when,
+            ready_timeout.unwrap_or(Duration::from_secs(LAUNCH_READY_TIMEOUT_SECS)),
+            options.poll_interval,
+        )
+        .await?;
+        if let Some(before) = &before {
+            status(json_results, "\n=== Running --before commands ===");
+            run_command_list(&client, before, json_results, &mut batch, options).await?;
+        }
+        run_command_list(&client, &commands, json_results, &mut batch, options).await
+    };
+    let mut result = bounded(command_timeout, main_lists).await;
+
+    // The prompt isn't bounded by --managed-timeout, since it waits on the user
+    if interactive && result.is_ok() {
+        result = run_interactive(&client, options, yes).await;
+    }
+
+    // Teardown runs like a finally block; a failure there only wins if everything else passed
+    if let Some(after) = &after {
+        status(json_results, "\n=== Running --after commands ===");
+        let after_list = run_command_list(&client, after, json_results, &mut batch, options);
+        let after_result = bounded(command_timeout, after_list).await;
+        result = match (result, after_result) {
+            (Ok(()), after_result) => after_result,
+            (Err(e), Ok(())) => Err(e),
+            (Err(e), Err(after_error)) => {
+                eprintln!("Error: --after commands failed: {}", after_error);
+                Err(e)
+            }
+        };
+    }
+    if json_results {
+        // A timeout can interrupt a command while its output is being collected
+        take_collected_output();
+        print_json(&Value::Array(batch))?;
     }
 
-    // Clean up
+    if let (Some(log_file), Ok(())) = (log_file.clone(), &result) {
+        let pid = child
+            .id()
+            .ok_or_else(|| anyhow::anyhow!("App exited before it could be kept alive"))?;
+        let session = DetachedSession {
+            pid,
+            port,
+            log_file,
+        };
+        detached::save_session_info(&session, &app_to_run)?;
+        status(json_results, detached::session_summary(&session));
+        return Ok(());
+    }
+
+    // Clean up, even when the commands failed or timed out
     child.kill().await?;
-    stdout_task.abort();
-    stderr_task.abort();
+    for task in output_tasks {
+        task.abort();
+    }
+    if let Some(log_file) = log_file {
+        eprintln!("App log kept at: {:?}", log_file);
+    }
 
-    Ok(())
+    result
+}
+
+/// Read commands from stdin one line at a time and run each against the managed app
+///
+/// A failing command is reported and the prompt carries on. `exit`, `quit` or end of input
+/// ends the session. Disruptive commands typed at a terminal are confirmed first, unless
+/// `yes` is set.
+async fn run_interactive(
+    client: &RemoteClient,
+    options: ExecutionOptions,
+    yes: bool,
+) -> Result<()> {
+    println!("\n=== Interactive mode: enter commands, or 'exit' to stop the app ===");
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    loop {
+        print!("{}> ", BIN_NAME);
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next_line().await? else {
+            // End of input leaves the cursor after the prompt
+            println!();
+            return Ok(());
+        };
+        let command = line.trim();
+        match command {
+            "" => continue,
+            "exit" | "quit" => return Ok(()),
+            _ => {}
+        }
+
+        // The answer comes from the same reader, so it can't be taken as the next command
+        let prompt = parse_command_string(command)
+            .ok()
+            .and_then(|parsed| pending_confirmation(&parsed, client, yes));
+        if let Some(prompt) = prompt {
+            ask_confirmation(&prompt)?;
+            let answer = lines.next_line().await?.unwrap_or_default();
+            if let Err(e) = check_confirmation(&answer) {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+        }
+        if let Err(e) = execute_command(client, command, options).await {
+            eprintln!("Error: {}", e);
+        }
+    }
+}
+
+/// Await `commands`, failing if they take longer than `limit`
+async fn bounded(
+    limit: Option<Duration>,
+    commands: impl Future<Output = Result<()>>,
+) -> Result<()> {
+    let Some(limit) = limit else {
+        return commands.await;
+    };
+    timeout(limit, commands).await.unwrap_or_else(|_| {
+        Err(anyhow::anyhow!(
+            "Managed commands did not finish within {} seconds; the app was shut down",
+            limit.as_secs()
+        ))
+    })
 }
 
 /// Pick a random available port in a safe range for managed instances
@@ -124,7 +326,6 @@ async fn pick_random_available_port() -> Result<u16> {
 
         // Check if port is available by trying to bind to it
         if is_port_available(port).await {
-            println!("Selected random port: {}", port);
             return Ok(port);
         }
         // Port is in use, try another
@@ -141,86 +342,81 @@ async fn wait_for_port(port: u16, timeout_duration: Duration) -> Result<()> {
     wait_for_port_connectable(port, timeout_duration).await
 }
 
-/// Run a comma-separated list of commands with proper JSON handling
-async fn run_command_list(commands: String, port: u16) -> Result<()> {
-    let client = RemoteClient::new(port);
+/// Print a progress message, on stderr when stdout is reserved for the JSON results
+fn status(json_results: bool, message: impl std::fmt::Display) {
+    if json_results {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
 
-    // Ensure app is ready before executing commands
-    wait_for_app_ready(&client).await?;
+/// Combine the values a command printed into the `result` of its batch entry
+fn batch_result(mut values: Vec<Value>) -> Value {
+    match values.len() {
+        0 => Value::Null,
+        1 => values.remove(0),
+        _ => Value::Array(values),
+    }
+}
 
-    let commands = parse_command_list(&commands)?;
+/// Run a comma-separated list of commands with proper JSON handling
+///
+/// With `json_results`, each command's output is collected into `batch` as a
+/// `{command, ok, result|error}` entry instead of being printed.
+async fn run_command_list(
+    client: &RemoteClient,
+    commands: &str,
+    json_results: bool,
+    batch: &mut Vec<Value>,
+    options: ExecutionOptions,
+) -> Result<()> {
+    let commands = split_command_list(commands);
+    let total = commands.len();
+    let mut failures = Vec::new();
 
-    for command in commands {
+    for (index, command) in commands.iter().enumerate() {
         let command = command.trim();
-        println!("\n=== Executing: {} ===", command);
+        status(json_results, format!("\n=== Executing: {} ===", command));
 
-        if let Some(wait_time) = command.strip_prefix("wait:") {
-            let seconds: u64 = wait_time.parse()?;
-            println!("Waiting {} seconds...", seconds);
-            sleep(Duration::from_secs(seconds)).await;
-        } else {
-            execute_command(&client, command).await?;
+        if json_results {
+            collect_output();
         }
-    }
-
-    Ok(())
-}
-
-/// Parse a command list respecting JSON structure
-fn parse_command_list(input: &str) -> Result<Vec<String>> {
-    let mut commands = Vec::new();
-    let mut current_command = String::new();
-    let mut in_json = false;
-    let mut brace_count = 0;
-    let mut in_string = false;
-    let mut escape_next = false;
-
-    for ch in input.chars() {
-        if escape_next {
-            current_command.push(ch);
-            escape_next = false;
-            continue;
-        }
-
-        match ch {
-            '\\' if in_json => {
-                current_command.push(ch);
-                escape_next = true;
-            }
-            '"' if in_json => {
-                current_command.push(ch);
-                if !escape_next {
-                    in_string = !in_string;
-                }
-            }
-            '{' if !in_string => {
-                in_json = true;
-                brace_count += 1;
-                current_command.push(ch);
-            }
-            '}' if !in_string && in_json => {
-                brace_count -= 1;
-                current_command.push(ch);
-                if brace_count == 0 {
-                    in_json = false;
+        let result = if let Some(wait_time) = command.strip_prefix("wait:") {
+            match wait_time.parse::<u64>() {
+                Ok(seconds) => {
+                    status(json_results, format!("Waiting {} seconds...", seconds));
+                    sleep(Duration::from_secs(seconds)).await;
+                    Ok(())
                 }
+                Err(e) => Err(e.into()),
             }
-            ',' if !in_json => {
-                if !current_command.trim().is_empty() {
-                    commands.push(current_command.trim().to_string());
-                }
-                current_command.clear();
-            }
-            _ => {
-                current_command.push(ch);
-            }
+        } else {
+            execute_command(client, command, options).await
+        };
+        if json_results {
+            let output = batch_result(take_collected_output());
+            batch.push(match &result {
+                Ok(()) => json!({ "command": command, "ok": true, "result": output }),
+                Err(e) => json!({ "command": command, "ok": false, "error": e.to_string() }),
+            });
         }
-    }
 
-    // Don't forget the last command
-    if !current_command.trim().is_empty() {
-        commands.push(current_command.trim().to_string());
+        if let Err(e) = result {
+            if !options.continue_on_error {
+                return Err(e);
+            }
+            let failure = format!(
+                "Command {} of {} ('{}') failed: {}",
+                index + 1,
+                total,
+                command,
+                e
+            );
+            eprintln!("Error: {}", failure);
+            failures.push(failure);
+        }
     }
 
-    Ok(commands)
+    summarize_failures(&failures, total)
 }
@@ -4,6 +4,7 @@ mod cargo_detector;
 pub mod cli_client;
 pub mod client;
 pub mod commands;
+pub mod config;
 pub mod constants;
 pub mod detached;
 pub mod error_formatter;
@@ -2,19 +2,61 @@
 //!
 //! This module provides a fluent builder API for creating JSON parameters
 //! for RPC calls, reducing duplication and improving maintainability.
+//! Parameter shapes that differ between Bevy releases are emitted according to
+//! the target [`BrpVersion`]. Only two things differ between the supported releases: the
+//! type path of the `Name` component and the `strict` flag of `bevy/query`. Every other
+//! method, including `bevy/insert` and `bevy/mutate_component`, takes the same parameters.
 
+use std::fmt;
+
+use clap::ValueEnum;
 use serde_json::{Map, Value, json};
 
+/// Bevy Remote Protocol version to shape parameters for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, ValueEnum)]
+pub enum BrpVersion {
+    /// Bevy 0.15
+    #[value(name = "0.15")]
+    V0_15,
+    /// Bevy 0.16 (newest supported)
+    #[default]
+    #[value(name = "0.16")]
+    V0_16,
+}
+
+impl fmt::Display for BrpVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BrpVersion::V0_15 => write!(f, "0.15"),
+            BrpVersion::V0_16 => write!(f, "0.16"),
+        }
+    }
+}
+
+impl BrpVersion {
+    /// Type path of Bevy's `Name` component, which moved from `bevy_core` in 0.16
+    pub fn name_component(self) -> &'static str {
+        match self {
+            BrpVersion::V0_15 => "bevy_core::name::Name",
+            BrpVersion::V0_16 => "bevy_ecs::name::Name",
+        }
+    }
+}
+
 /// Builder for JSON-RPC parameters
 #[derive(Default)]
 pub struct RpcParamsBuilder {
     params: Map<String, Value>,
+    version: BrpVersion,
 }
 
 impl RpcParamsBuilder {
-    /// Create a new empty builder
-    pub fn new() -> Self {
-        Self::default()
+    /// Create a new empty builder targeting a specific BRP version
+    pub fn for_version(version: BrpVersion) -> Self {
+        Self {
+            params: Map::new(),
+            version,
+        }
     }
 
     /// Add an entity ID parameter
@@ -43,12 +85,6 @@ impl RpcParamsBuilder {
         self
     }
 
-    /// Add a components parameter (for spawn operations)
-    pub fn components(mut self, components: Value) -> Self {
-        self.params.insert("components".to_string(), components);
-        self
-    }
-
     /// Add component data for insert operations (creates components map)
     pub fn component_data(mut self, component: &str, data: Value) -> Self {
         let mut components = serde_json::Map::new();
@@ -66,6 +102,31 @@ impl RpcParamsBuilder {
         self
     }
 
+    /// Add the query data for `bevy/query`
+    /// 0.16 added `strict`; it is sent as `false` so unknown components are ignored
+    /// the same way 0.15 ignores them.
+    pub fn query(mut self, components: Vec<&str>) -> Self {
+        self.params
+            .insert("data".to_string(), json!({ "components": components }));
+        if self.version >= BrpVersion::V0_16 {
+            self.params.insert("strict".to_string(), json!(false));
+        }
+        self
+    }
+
+    /// Add the components map for `bevy/insert` and `bevy/spawn`
+    pub fn insert_components(mut self, components: Value) -> Self {
+        self.params.insert("components".to_string(), components);
+        self
+    }
+
+    /// Add the field path and new value for `bevy/mutate_component` and `bevy/mutate_resource`
+    pub fn mutation(mut self, path: impl Into<String>, value: Value) -> Self {
+        self.params.insert("path".to_string(), json!(path.into()));
+        self.params.insert("value".to_string(), value);
+        self
+    }
+
     /// Add a parent parameter (for hierarchy operations)
     pub fn parent(mut self, parent: Value) -> Self {
         self.params.insert("parent".to_string(), parent);
@@ -96,7 +157,7 @@ mod tests {
 
     #[test]
     fn test_entity_component_builder() {
-        let params = RpcParamsBuilder::new()
+        let params = RpcParamsBuilder::default()
             .entity(123)
             .component("Transform")
             .build();
@@ -107,10 +168,39 @@ mod tests {
 
     #[test]
     fn test_custom_field() {
-        let params = RpcParamsBuilder::new()
+        let params = RpcParamsBuilder::default()
             .field("custom", json!("value"))
             .build();
 
         assert_eq!(params["custom"], json!("value"));
     }
+
+    #[test]
+    fn test_default_version_is_newest() {
+        assert_eq!(BrpVersion::default(), BrpVersion::V0_16);
+    }
+
+    #[test]
+    fn test_query_shape_per_version() {
+        let transform = "bevy_transform::components::transform::Transform";
+
+        let params = RpcParamsBuilder::for_version(BrpVersion::V0_15)
+            .query(vec![transform])
+            .build();
+        assert_eq!(params, json!({ "data": { "components": [transform] } }));
+
+        let params = RpcParamsBuilder::for_version(BrpVersion::V0_16)
+            .query(vec![transform])
+            .build();
+        assert_eq!(
+            params,
+            json!({ "data": { "components": [transform] }, "strict": false })
+        );
+    }
+
+    #[test]
+    fn test_name_component_per_version() {
+        assert_eq!(BrpVersion::V0_15.name_component(), "bevy_core::name::Name");
+        assert_eq!(BrpVersion::V0_16.name_component(), "bevy_ecs::name::Name");
+    }
 }
@@ -1,5 +1,7 @@
 //! Server-Sent Events (SSE) parsing module for handling streaming responses.
 
+use std::collections::VecDeque;
+use std::fmt;
 use std::pin::Pin;
 use std::task::{Context as TaskContext, Poll};
 
@@ -9,104 +11,192 @@ use serde_json::Value;
 use tokio_stream::Stream;
 
 /// A stream that parses SSE events from a byte stream and extracts JSON data.
+///
+/// Events follow the SSE framing rules: `data:` lines are joined with newlines until a blank
+/// line ends the event, lines starting with `:` are comments, and lines may be split across
+/// chunks at any byte, including inside a multi-byte character. An event still missing its
+/// blank line when the stream ends is discarded, as a browser would.
 pub struct SseStream<S> {
     inner: S,
-    buffer: String,
+    /// Bytes received but not yet split into complete lines
+    buffer: Vec<u8>,
+    /// `data:` values of the event being received
+    data: Vec<String>,
+    /// Events parsed from the buffer but not yet returned
+    pending: VecDeque<Result<Value>>,
+    /// Whether the inner stream has ended
+    finished: bool,
 }
 
-impl<S> SseStream<S>
+impl<S, E> SseStream<S>
 where
-    S: Stream<Item = Result<Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: fmt::Display,
 {
     /// Creates a new SSE stream from a byte stream.
     pub fn new(stream: S) -> Self {
         Self {
             inner: stream,
-            buffer: String::new(),
+            buffer: Vec::new(),
+            data: Vec::new(),
+            pending: VecDeque::new(),
+            finished: false,
         }
     }
 
-    /// Parses SSE events from the buffer and extracts JSON data.
-    fn parse_sse_events(&mut self) -> Vec<Result<Value>> {
-        let mut events = Vec::new();
-
-        // Process complete lines in the buffer
-        while let Some(line_end) = self.buffer.find('\n') {
-            let line = self.buffer[..line_end].trim_end_matches('\r');
+    /// Parses every complete line in the buffer, queueing the events they finish.
+    fn parse_sse_events(&mut self) {
+        while let Some(line_end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=line_end).collect();
+            self.parse_line(&line);
+        }
+    }
 
-            // Extract data from SSE event
-            if let Some(data) = line.strip_prefix("data: ") {
-                match serde_json::from_str::<Value>(data) {
-                    Ok(json) => events.push(Ok(json)),
-                    Err(e) => events.push(Err(anyhow::anyhow!("Failed to parse JSON: {}", e))),
-                }
+    /// Applies one complete line to the event being received.
+    fn parse_line(&mut self, line: &[u8]) {
+        let line = match std::str::from_utf8(line) {
+            Ok(line) => line.trim_end_matches(['\r', '\n']),
+            Err(e) => {
+                self.pending
+                    .push_back(Err(anyhow::anyhow!("Invalid UTF-8: {}", e)));
+                return;
             }
-            // Ignore other SSE fields like event:, id:, retry:, or empty lines
+        };
 
-            // Remove processed line from buffer
-            self.buffer.drain(..=line_end);
+        if line.is_empty() {
+            self.dispatch_event();
+            return;
         }
+        if line.starts_with(':') {
+            // Comment, often sent as a keep-alive
+            return;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        if field == "data" {
+            self.data
+                .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        // Ignore other SSE fields like event:, id: and retry:
+    }
 
-        events
+    /// Queues the event built from the `data:` lines received so far, if there were any.
+    fn dispatch_event(&mut self) {
+        if self.data.is_empty() {
+            return;
+        }
+        let data = self.data.join("\n");
+        self.data.clear();
+        self.pending.push_back(
+            serde_json::from_str(&data).map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}", e)),
+        );
     }
 }
 
-impl<S> Stream for SseStream<S>
+impl<S, E> Stream for SseStream<S>
 where
-    S: Stream<Item = Result<Bytes, reqwest::Error>> + Unpin,
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+    E: fmt::Display,
 {
     type Item = Result<Value>;
 
-    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
-        // First, check if we have any complete events in the buffer
-        let events = self.parse_sse_events();
-        if let Some(event) = events.into_iter().next() {
-            return Poll::Ready(Some(event));
-        }
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            // Return events already parsed before reading more data
+            if let Some(event) = this.pending.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+            if this.finished {
+                return Poll::Ready(None);
+            }
 
-        // If not, try to get more data from the inner stream
-        match Pin::new(&mut self.inner).poll_next(cx) {
-            Poll::Ready(Some(Ok(bytes))) => {
-                // Append new data to buffer
-                match std::str::from_utf8(&bytes) {
-                    Ok(text) => self.buffer.push_str(text),
-                    Err(e) => {
-                        return Poll::Ready(Some(Err(anyhow::anyhow!("Invalid UTF-8: {}", e))));
-                    }
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    this.buffer.extend_from_slice(&bytes);
+                    this.parse_sse_events();
                 }
-
-                // Try parsing again with the new data
-                let events = self.parse_sse_events();
-                if let Some(event) = events.into_iter().next() {
-                    Poll::Ready(Some(event))
-                } else {
-                    // Need more data to complete an event
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
+                Poll::Ready(Some(Err(e))) => {
+                    return Poll::Ready(Some(Err(anyhow::anyhow!("Stream error: {}", e))));
                 }
-            }
-            Poll::Ready(Some(Err(e))) => {
-                Poll::Ready(Some(Err(anyhow::anyhow!("Stream error: {}", e))))
-            }
-            Poll::Ready(None) => {
-                // Stream ended, check if there's any remaining data in buffer
-                if !self.buffer.trim().is_empty() {
-                    // Try to parse any remaining complete events
-                    let events = self.parse_sse_events();
-                    if let Some(event) = events.into_iter().next() {
-                        return Poll::Ready(Some(event));
-                    }
+                Poll::Ready(None) => {
+                    // A connection cut mid-event leaves a truncated payload; drop it rather
+                    // than report a parse error for data the server never finished sending
+                    this.buffer.clear();
+                    this.data.clear();
+                    this.finished = true;
                 }
-                Poll::Ready(None)
+                Poll::Pending => return Poll::Pending,
             }
-            Poll::Pending => Poll::Pending,
         }
     }
 }
 
-/// Converts a reqwest byte stream into an SSE event stream.
-pub fn parse_sse_stream(
-    stream: impl Stream<Item = Result<Bytes, reqwest::Error>> + Unpin,
+/// Converts a byte stream, such as a reqwest response body, into an SSE event stream.
+pub fn parse_sse_stream<E: fmt::Display>(
+    stream: impl Stream<Item = Result<Bytes, E>> + Unpin,
 ) -> impl Stream<Item = Result<Value>> {
     SseStream::new(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use serde_json::json;
+    use tokio_stream::StreamExt;
+
+    use super::*;
+
+    /// Parse `input` delivered in chunks of `chunk_size` bytes
+    async fn parse_chunked(input: &str, chunk_size: usize) -> Vec<Result<Value>> {
+        let chunks: Vec<Result<Bytes, Infallible>> = input
+            .as_bytes()
+            .chunks(chunk_size)
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        parse_sse_stream(tokio_stream::iter(chunks)).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_sse_framing_across_chunk_sizes() {
+        let input = ": keep-alive\n\
+            data: {\"n\": 1}\n\n\
+            event: update\r\nid: 7\r\ndata: {\"name\":\r\ndata: \"Zoë\"}\r\n\r\n\
+            data:{\"n\": 3}\n\n";
+
+        for chunk_size in [1, 3, 16, input.len()] {
+            let events: Vec<Value> = parse_chunked(input, chunk_size)
+                .await
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(
+                events,
+                [json!({"n": 1}), json!({"name": "Zoë"}), json!({"n": 3})],
+                "chunk size {}",
+                chunk_size
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_unterminated_event_is_dropped() {
+        let input = "data: {\"n\": 1}\n\ndata: {\"n\":\ndata: 2}\n";
+
+        for chunk_size in [1, 5, input.len()] {
+            let events: Vec<Value> = parse_chunked(input, chunk_size)
+                .await
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!(events, [json!({"n": 1})], "chunk size {}", chunk_size);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_invalid_json_is_an_error_event() {
+        let events = parse_chunked("data: {not json\n\ndata: 2\n\n", 64).await;
+        assert!(events[0].is_err());
+        assert_eq!(events[1].as_ref().unwrap(), &json!(2));
+    }
+}
@@ -0,0 +1,77 @@
+//! Short names for component and resource types, loaded from an alias file
+//!
+//! The file is a flat map of alias to fully qualified type path, either TOML
+//! (`transform = "bevy_transform::components::transform::Transform"`) or JSON when the
+//! file name ends in `.json`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+
+/// Aliases loaded from `--aliases`, empty when none were given
+static COMPONENT_ALIASES: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+/// Read an alias file mapping short names to fully qualified type paths
+pub fn load_component_aliases(path: &Path) -> Result<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read alias file {}", path.display()))?;
+    parse_component_aliases(&contents, path)
+}
+
+/// Parse alias file contents, as JSON for `.json` files and TOML otherwise
+fn parse_component_aliases(contents: &str, path: &Path) -> Result<HashMap<String, String>> {
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(contents)
+            .with_context(|| format!("Invalid alias file {}", path.display()))
+    } else {
+        toml::from_str(contents).with_context(|| format!("Invalid alias file {}", path.display()))
+    }
+}
+
+/// Expand type arguments through `aliases` for the rest of the run
+pub fn set_component_aliases(aliases: HashMap<String, String>) {
+    *COMPONENT_ALIASES.lock().unwrap_or_else(|e| e.into_inner()) = Some(aliases);
+}
+
+/// The full type path for `name` if it is an alias, otherwise `name` unchanged
+pub fn expand_alias(name: &str) -> String {
+    COMPONENT_ALIASES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(|aliases| aliases.get(name))
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_component_aliases_by_extension() {
+        let toml_aliases = parse_component_aliases(
+            r#"transform = "bevy_transform::components::transform::Transform""#,
+            Path::new("aliases.toml"),
+        )
+        .unwrap();
+        assert_eq!(
+            toml_aliases["transform"],
+            "bevy_transform::components::transform::Transform"
+        );
+
+        let json_aliases = parse_component_aliases(
+            r#"{"name": "bevy_ecs::name::Name"}"#,
+            Path::new("aliases.json"),
+        )
+        .unwrap();
+        assert_eq!(json_aliases["name"], "bevy_ecs::name::Name");
+
+        assert!(parse_component_aliases("name = 3", Path::new("aliases.toml")).is_err());
+    }
+}
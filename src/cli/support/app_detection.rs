@@ -2,8 +2,41 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 
+use super::binary_discovery::{find_workspace_binary_with_target_dir, prebuilt_binary};
 use crate::cli::cargo_detector::CargoDetector;
 
+/// Resolve the app to launch to its name, working directory and absolute binary path
+///
+/// A path to an existing executable is launched as-is from its own directory without any
+/// cargo metadata lookup; anything else goes through workspace detection and binary discovery.
+pub fn resolve_app_binary(
+    app_binary: Option<String>,
+    profile: Option<&str>,
+) -> Result<(String, PathBuf, PathBuf)> {
+    if let Some(app_path) = app_binary
+        .as_deref()
+        .map(prebuilt_binary)
+        .transpose()?
+        .flatten()
+    {
+        let app_name = app_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| app_path.display().to_string());
+        let working_dir = app_path
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_else(current_dir_or_dot);
+        return Ok((app_name, working_dir, app_path));
+    }
+
+    let (app_name, manifest_dir, target_dir) = detect_bevy_app(app_binary)?;
+    let app_path = find_workspace_binary_with_target_dir(&app_name, &target_dir, profile)?;
+    // Make the path absolute since the app runs from the manifest directory
+    let app_path = std::fs::canonicalize(&app_path)?;
+    Ok((app_name, manifest_dir, app_path))
+}
+
 /// Detect which Bevy app to run based on user input or auto-detection
 ///
 /// Returns a tuple of (app_name, manifest_directory, target_directory)
@@ -55,7 +88,7 @@ fn auto_detect_app(detector: Option<CargoDetector>) -> Result<(String, PathBuf,
     // Try default binary first
     if let Some(default_binary) = detector.get_default_binary() {
         if default_binary.is_bevy_app {
-            println!("Detected default Bevy app: {}", default_binary.name);
+            eprintln!("Detected default Bevy app: {}", default_binary.name);
             let manifest_dir = get_manifest_dir(&default_binary.manifest_path);
             return Ok((default_binary.name, manifest_dir, target_dir));
         }
@@ -69,7 +102,7 @@ fn auto_detect_app(detector: Option<CargoDetector>) -> Result<(String, PathBuf,
         )
     })?;
 
-    println!("Using detected Bevy app: {}", app.name);
+    eprintln!("Using detected Bevy app: {}", app.name);
     let manifest_dir = get_manifest_dir(&app.manifest_path);
     Ok((app.name.clone(), manifest_dir, target_dir))
 }
@@ -0,0 +1,252 @@
+//! `--assert` post-conditions checked against the JSON a command prints
+//!
+//! An assertion is written as `POINTER OP VALUE`, e.g. `translation/1 == 5.0`. The pointer
+//! is a JSON pointer into the printed result (the leading `/` is optional) and the value is
+//! parsed as JSON, falling back to a plain string so `== Player` works without quotes.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Mutex;
+
+use anyhow::{Result, bail};
+use serde_json::Value;
+
+/// The assertion from `--assert`, taken by the first command that runs
+static ASSERTION: Mutex<Option<Assertion>> = Mutex::new(None);
+
+/// Comparison operators, matched with a space on each side
+const OPERATORS: [(&str, Operator); 6] = [
+    ("==", Operator::Eq),
+    ("!=", Operator::Ne),
+    ("<=", Operator::Le),
+    (">=", Operator::Ge),
+    ("<", Operator::Lt),
+    (">", Operator::Gt),
+];
+
+/// How an assertion compares the actual value with the expected one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (token, _) = OPERATORS
+            .iter()
+            .find(|(_, operator)| operator == self)
+            .expect("every operator has a token");
+        write!(f, "{}", token)
+    }
+}
+
+/// A `POINTER OP VALUE` check on a command's result
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assertion {
+    pointer: String,
+    operator: Operator,
+    expected: Value,
+}
+
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.pointer, self.operator, self.expected)
+    }
+}
+
+/// Returned when an `--assert` post-condition does not hold, so the process can exit with
+/// its own code instead of the generic failure code
+#[derive(Debug)]
+pub struct AssertionFailure {
+    assertion: Assertion,
+    actual: Option<Value>,
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Assertion failed: {}", self.assertion)?;
+        writeln!(
+            f,
+            "  expected: {} {}",
+            self.assertion.operator, self.assertion.expected
+        )?;
+        match &self.actual {
+            Some(actual) => write!(f, "  actual:   {}", actual),
+            None => write!(
+                f,
+                "  actual:   <missing> (nothing at {})",
+                self.assertion.pointer
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AssertionFailure {}
+
+/// Parse an `--assert` argument such as `translation/1 == 5.0`
+pub fn parse_assertion(input: &str) -> Result<Assertion> {
+    let Some((index, token, operator)) = OPERATORS
+        .iter()
+        .filter_map(|(token, operator)| {
+            input
+                .find(&format!(" {} ", token))
+                .map(|index| (index, *token, *operator))
+        })
+        .min_by_key(|(index, ..)| *index)
+    else {
+        bail!(
+            "Invalid assertion '{}': expected POINTER OP VALUE with OP one of ==, !=, <, <=, >, >=",
+            input
+        );
+    };
+
+    let pointer = input[..index].trim();
+    let value = input[index + token.len() + 2..].trim();
+    if pointer.is_empty() || value.is_empty() {
+        bail!(
+            "Invalid assertion '{}': both a pointer and a value are required",
+            input
+        );
+    }
+
+    Ok(Assertion {
+        pointer: if pointer.starts_with('/') {
+            pointer.to_string()
+        } else {
+            format!("/{}", pointer)
+        },
+        operator,
+        expected: serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string())),
+    })
+}
+
+impl Assertion {
+    /// Check the assertion against a command's result
+    pub fn check(&self, result: &Value) -> std::result::Result<(), AssertionFailure> {
+        let actual = result.pointer(&self.pointer);
+        let holds = actual.is_some_and(|actual| match self.operator {
+            Operator::Eq => values_equal(actual, &self.expected),
+            Operator::Ne => !values_equal(actual, &self.expected),
+            Operator::Lt => compare(actual, &self.expected) == Some(Ordering::Less),
+            Operator::Le => matches!(
+                compare(actual, &self.expected),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+            Operator::Gt => compare(actual, &self.expected) == Some(Ordering::Greater),
+            Operator::Ge => matches!(
+                compare(actual, &self.expected),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+        });
+
+        if holds {
+            Ok(())
+        } else {
+            Err(AssertionFailure {
+                assertion: self.clone(),
+                actual: actual.cloned(),
+            })
+        }
+    }
+}
+
+/// Equality that treats `5` and `5.0` as the same number
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(actual), Some(expected)) => actual == expected,
+        _ => actual == expected,
+    }
+}
+
+/// Order two numbers or two strings; anything else cannot be ordered
+fn compare(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(actual), Value::Number(expected)) => {
+            actual.as_f64()?.partial_cmp(&expected.as_f64()?)
+        }
+        (Value::String(actual), Value::String(expected)) => Some(actual.cmp(expected)),
+        _ => None,
+    }
+}
+
+/// Set the assertion that the next command's result must satisfy
+pub fn set_assertion(assertion: Option<Assertion>) {
+    *ASSERTION.lock().unwrap_or_else(|e| e.into_inner()) = assertion;
+}
+
+/// Take the pending assertion, so commands run from inside another command don't check it
+pub fn take_assertion() -> Option<Assertion> {
+    ASSERTION.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_assertion() {
+        let assertion = parse_assertion("translation/1 == 5.0").unwrap();
+        assert_eq!(assertion.pointer, "/translation/1");
+        assert_eq!(assertion.operator, Operator::Eq);
+        assert_eq!(assertion.expected, json!(5.0));
+
+        let assertion = parse_assertion("/name != Player One").unwrap();
+        assert_eq!(assertion.operator, Operator::Ne);
+        assert_eq!(assertion.expected, json!("Player One"));
+
+        assert_eq!(
+            parse_assertion("value <= 10").unwrap().operator,
+            Operator::Le
+        );
+        assert!(parse_assertion("translation/1 5.0").is_err());
+        assert!(parse_assertion(" == 5").is_err());
+    }
+
+    #[test]
+    fn test_check_assertion() {
+        let transform = json!({ "translation": [1.0, 5.0, 0.0], "name": "Player" });
+
+        assert!(
+            parse_assertion("translation/1 == 5")
+                .unwrap()
+                .check(&transform)
+                .is_ok()
+        );
+        assert!(
+            parse_assertion("translation/0 < 2")
+                .unwrap()
+                .check(&transform)
+                .is_ok()
+        );
+        assert!(
+            parse_assertion("name == Player")
+                .unwrap()
+                .check(&transform)
+                .is_ok()
+        );
+        assert!(
+            parse_assertion("name > 10")
+                .unwrap()
+                .check(&transform)
+                .is_err()
+        );
+
+        let failure = parse_assertion("translation/1 >= 6")
+            .unwrap()
+            .check(&transform)
+            .unwrap_err();
+        assert_eq!(failure.actual, Some(json!(5.0)));
+        let missing = parse_assertion("scale/0 == 1")
+            .unwrap()
+            .check(&transform)
+            .unwrap_err();
+        assert!(missing.to_string().contains("<missing>"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 /// Find a binary in the workspace using the target directory from cargo metadata
 pub fn find_workspace_binary_with_target_dir(
@@ -52,10 +52,132 @@ pub fn find_workspace_binary_with_target_dir(
         "App binary '{}' not found in target directory: {}\n\
          Searched in:\n\
          - {}\n\
-         Try building the app with 'cargo build --profile {}' first.",
+         Try building the app with '{}' first.",
         name,
         target_dir.display(),
         target_dir.join(profile).join(name).display(),
-        profile
+        build_command(name, Some(profile))
     )
 }
+
+/// Resolve `app` as a prebuilt binary when it is a path to an existing file
+///
+/// Only arguments containing a path separator count as paths; bare names are left for
+/// workspace discovery. Returns `Ok(None)` when `app` is a name or the path does not exist.
+pub fn prebuilt_binary(app: &str) -> Result<Option<PathBuf>> {
+    if !app.contains('/') && !app.contains('\\') {
+        return Ok(None);
+    }
+
+    let path = Path::new(app);
+    if !path.exists() {
+        return Ok(None);
+    }
+    if !path.is_file() {
+        anyhow::bail!("App path {:?} exists but is not a file", path);
+    }
+    if !is_executable(path)? {
+        anyhow::bail!(
+            "App path {:?} exists but is not executable. Try 'chmod +x {}'.",
+            path,
+            path.display()
+        );
+    }
+
+    let path = std::fs::canonicalize(path)
+        .with_context(|| format!("Failed to resolve app path: {:?}", path))?;
+    Ok(Some(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read metadata for {:?}", path))?;
+    Ok(metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> Result<bool> {
+    Ok(path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("exe")))
+}
+
+/// Cargo command that builds `name` into the target subdirectory for `profile`
+///
+/// `debug` and `release` are the output directories of cargo's built-in `dev` and
+/// `release` profiles; any other name is a custom profile with a directory of the same name.
+pub fn build_command(name: &str, profile: Option<&str>) -> String {
+    match profile {
+        None | Some("debug") => format!("cargo build --bin {}", name),
+        Some("release") => format!("cargo build --release --bin {}", name),
+        Some(profile) => format!("cargo build --profile {} --bin {}", profile, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_command_matches_profile_directory() {
+        assert_eq!(build_command("game", None), "cargo build --bin game");
+        assert_eq!(
+            build_command("game", Some("debug")),
+            "cargo build --bin game"
+        );
+        assert_eq!(
+            build_command("game", Some("release")),
+            "cargo build --release --bin game"
+        );
+        assert_eq!(
+            build_command("game", Some("profiling")),
+            "cargo build --profile profiling --bin game"
+        );
+    }
+
+    #[test]
+    fn test_missing_binary_error_includes_build_command() {
+        let target_dir = tempfile::tempdir().unwrap();
+
+        let error =
+            find_workspace_binary_with_target_dir("game", target_dir.path(), Some("release"))
+                .unwrap_err()
+                .to_string();
+
+        assert!(
+            error.contains("cargo build --release --bin game"),
+            "unexpected error: {}",
+            error
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prebuilt_binary_requires_executable_path() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("game");
+        std::fs::write(&binary, "").unwrap();
+        let binary_arg = binary.to_str().unwrap();
+
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let error = prebuilt_binary(binary_arg).unwrap_err().to_string();
+        assert!(
+            error.contains("not executable"),
+            "unexpected error: {}",
+            error
+        );
+
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let resolved = prebuilt_binary(binary_arg).unwrap();
+        assert_eq!(resolved, Some(std::fs::canonicalize(&binary).unwrap()));
+
+        assert_eq!(prebuilt_binary("game").unwrap(), None);
+        let missing = dir.path().join("missing");
+        assert_eq!(prebuilt_binary(missing.to_str().unwrap()).unwrap(), None);
+    }
+}
@@ -0,0 +1,56 @@
+//! Confirmation prompts for disruptive commands typed at a terminal
+
+use std::io::{BufRead, Write};
+
+use anyhow::{Result, bail};
+
+/// Ask `prompt` on stderr and fail unless the answer read from stdin is yes
+///
+/// Callers only prompt when stdin is a terminal, so scripts never block here.
+pub fn require_confirmation(prompt: &str) -> Result<()> {
+    ask_confirmation(prompt)?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    check_confirmation(&answer)
+}
+
+/// Ask `prompt` on stderr, for callers that read the answer from their own input
+pub fn ask_confirmation(prompt: &str) -> Result<()> {
+    eprint!("{} [y/N] ", prompt);
+    std::io::stderr().flush()?;
+    Ok(())
+}
+
+/// Fail unless `answer` accepts the prompt
+pub fn check_confirmation(answer: &str) -> Result<()> {
+    if !is_yes(answer) {
+        bail!("Cancelled; pass --yes to skip this confirmation");
+    }
+    Ok(())
+}
+
+/// Whether a typed answer accepts the prompt
+fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_yes() {
+        assert!(is_yes("y\n"));
+        assert!(is_yes(" YES "));
+        assert!(!is_yes("\n"));
+        assert!(!is_yes("no"));
+    }
+
+    #[test]
+    fn test_check_confirmation() {
+        assert!(check_confirmation("yes\n").is_ok());
+        let error = check_confirmation("n\n").unwrap_err();
+        assert!(error.to_string().contains("--yes"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Structural comparison of two JSON values
+//!
+//! Differences are reported as `{only_left, only_right, changed}` lists whose `path`s are
+//! JSON pointers into the compared values.
+
+use serde_json::{Value, json};
+
+/// Every difference between `left` and `right`, grouped by kind and in path order within each
+///
+/// Objects are compared key by key and arrays index by index, and `5` and `5.0` are the
+/// same number. The result is `{only_left, only_right, changed}`: a key or element missing
+/// from `right` is listed under `only_left` as `{path, value}`, one missing from `left` under
+/// `only_right`, and any other mismatch under `changed` as `{path, left, right}`.
+pub fn json_diff(left: &Value, right: &Value) -> Value {
+    let mut diff = JsonDiff::default();
+    diff_at(String::new(), left, right, &mut diff);
+    json!({
+        "only_left": diff.only_left,
+        "only_right": diff.only_right,
+        "changed": diff.changed
+    })
+}
+
+/// Number of differences in a `json_diff` result
+pub fn diff_count(diff: &Value) -> usize {
+    ["only_left", "only_right", "changed"]
+        .iter()
+        .filter_map(|kind| diff[*kind].as_array())
+        .map(Vec::len)
+        .sum()
+}
+
+/// The differences found so far by `diff_at`, one list per kind
+#[derive(Default)]
+struct JsonDiff {
+    only_left: Vec<Value>,
+    only_right: Vec<Value>,
+    changed: Vec<Value>,
+}
+
+fn diff_at(path: String, left: &Value, right: &Value, diff: &mut JsonDiff) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys: Vec<&String> = left.keys().chain(right.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                diff_entry(path, left.get(key), right.get(key), diff);
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                let path = format!("{}/{}", path, index);
+                diff_entry(path, left.get(index), right.get(index), diff);
+            }
+        }
+        (left, right) => {
+            let same_number = matches!(
+                (left.as_f64(), right.as_f64()),
+                (Some(left), Some(right)) if left == right
+            );
+            if left != right && !same_number {
+                diff.changed.push(json!({
+                    "path": path,
+                    "left": left,
+                    "right": right
+                }));
+            }
+        }
+    }
+}
+
+fn diff_entry(path: String, left: Option<&Value>, right: Option<&Value>, diff: &mut JsonDiff) {
+    match (left, right) {
+        (Some(left), Some(right)) => diff_at(path, left, right, diff),
+        (Some(left), None) => diff.only_left.push(json!({ "path": path, "value": left })),
+        (None, Some(right)) => diff
+            .only_right
+            .push(json!({ "path": path, "value": right })),
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_diff() {
+        let left = json!({
+            "translation": [1.0, 5.0, 0.0],
+            "scale": [1.0, 1.0, 1.0],
+            "name": "Player"
+        });
+        let no_differences = json!({ "only_left": [], "only_right": [], "changed": [] });
+
+        assert_eq!(json_diff(&left, &left), no_differences);
+        assert_eq!(diff_count(&no_differences), 0);
+        assert_eq!(
+            json_diff(&json!({ "value": 5 }), &json!({ "value": 5.0 })),
+            no_differences
+        );
+
+        let right = json!({
+            "translation": [1.0, 3.0, 0.0],
+            "name": "Player",
+            "a/b": true
+        });
+        assert_eq!(
+            json_diff(&left, &right),
+            json!({
+                "only_left": [{ "path": "/scale", "value": [1.0, 1.0, 1.0] }],
+                "only_right": [{ "path": "/a~1b", "value": true }],
+                "changed": [{ "path": "/translation/1", "left": 5.0, "right": 3.0 }]
+            })
+        );
+        assert_eq!(diff_count(&json_diff(&left, &right)), 3);
+
+        assert_eq!(
+            json_diff(&json!([1]), &json!([1, 2])),
+            json!({
+                "only_left": [],
+                "only_right": [{ "path": "/1", "value": 2 }],
+                "changed": []
+            })
+        );
+        assert_eq!(
+            json_diff(&json!({ "a": 1 }), &json!([1]))["changed"],
+            json!([{ "path": "", "left": { "a": 1 }, "right": [1] }])
+        );
+    }
+}
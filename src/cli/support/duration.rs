@@ -0,0 +1,49 @@
+//! Human-friendly durations such as `30m` or `24h` for command-line flags
+
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+
+/// Parse a whole number followed by a unit: `s`, `m`, `h` or `d` (e.g., `30m`, `24h`)
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split);
+    let Ok(amount) = amount.parse::<u64>() else {
+        bail!(
+            "Invalid duration '{}': expected a number and a unit, e.g. 30m or 24h",
+            input
+        );
+    };
+    let seconds_per_unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => bail!(
+            "Invalid duration '{}': the unit must be s, m, h or d (e.g. 30m or 24h)",
+            input
+        ),
+    };
+    Ok(Duration::from_secs(amount.saturating_mul(seconds_per_unit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_duration("24h").unwrap(), Duration::from_secs(86400));
+        assert_eq!(parse_duration("2d").unwrap(), Duration::from_secs(172800));
+
+        assert!(parse_duration("24").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1.5h").is_err());
+        assert!(parse_duration("3w").is_err());
+    }
+}
@@ -4,9 +4,238 @@
 //! entity IDs from command-line arguments, ensuring consistent error handling
 //! and type conversion across all BRP commands that work with entities.
 
-use anyhow::Result;
+use std::fmt;
+use std::io::Read;
+
+use anyhow::{Result, anyhow, bail};
+use clap::ValueEnum;
+use serde_json::{Value, json};
+
+/// Number base used to print entity IDs in command output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum IdBase {
+    /// Decimal numbers, as returned by BRP
+    #[default]
+    Dec,
+    /// `0x`-prefixed hexadecimal strings
+    Hex,
+}
+
+impl fmt::Display for IdBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdBase::Dec => write!(f, "dec"),
+            IdBase::Hex => write!(f, "hex"),
+        }
+    }
+}
 
 /// Parse entity ID from the first argument
 pub fn parse_entity_arg(args: &[&str]) -> Result<u64> {
     args[0].parse().map_err(Into::into)
 }
+
+/// Prefix that marks an entity argument as a `Name` to look up instead of an ID
+pub const ENTITY_NAME_PREFIX: &str = "name:";
+
+/// Check that an entity argument is a numeric ID or `name:<NAME>`
+///
+/// The argument is kept as written; names are looked up against the app when the
+/// command runs.
+pub fn parse_entity_ref(arg: &str) -> Result<String> {
+    match arg.strip_prefix(ENTITY_NAME_PREFIX) {
+        Some("") => bail!(
+            "Entity name after '{}' must not be empty",
+            ENTITY_NAME_PREFIX
+        ),
+        Some(_) => Ok(arg.to_string()),
+        None if arg.parse::<u64>().is_ok() => Ok(arg.to_string()),
+        None => bail!(
+            "Invalid entity '{}': expected an entity ID or {}<NAME>",
+            arg,
+            ENTITY_NAME_PREFIX
+        ),
+    }
+}
+
+/// Read entity IDs from JSON piped on stdin
+pub async fn read_entity_ids_from_stdin() -> Result<Vec<u64>> {
+    // Stdin reads block, so keep them off the runtime's worker threads
+    let input = tokio::task::spawn_blocking(|| {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input).map(|_| input)
+    })
+    .await??;
+    parse_entity_ids(&input)
+}
+
+/// Extract entity IDs from JSON produced by other commands
+///
+/// Accepts a raw array of IDs, the array of `{"entity": ...}` objects returned by `query`,
+/// or the `{"entities": [...]}` object returned by `list_entities`. IDs may also be the
+/// `0x`-prefixed strings printed by `--id-base hex`.
+pub fn parse_entity_ids(input: &str) -> Result<Vec<u64>> {
+    let value: Value = serde_json::from_str(input)
+        .map_err(|e| anyhow!("Expected JSON with entity IDs on stdin: {}", e))?;
+
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(obj) => match obj.get("entities").and_then(|e| e.as_array()) {
+            Some(items) => items,
+            None => bail!("Expected a JSON array or an object with an 'entities' array"),
+        },
+        _ => bail!("Expected a JSON array or an object with an 'entities' array"),
+    };
+
+    items
+        .iter()
+        .map(|item| {
+            entity_id_value(item)
+                .or_else(|| item.get("entity").and_then(entity_id_value))
+                .ok_or_else(|| anyhow!("Could not read an entity ID from {}", item))
+        })
+        .collect()
+}
+
+/// Read an entity ID written as a number or as a `0x`-prefixed hex string
+fn entity_id_value(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| {
+        let hex = value.as_str()?.strip_prefix("0x")?;
+        u64::from_str_radix(hex, 16).ok()
+    })
+}
+
+/// Fields whose value is a single entity ID
+const ENTITY_ID_FIELDS: [&str; 5] = ["entity", "child", "parent", "source", "new_entity"];
+/// Fields whose value is an array of entity IDs
+const ENTITY_ID_LIST_FIELDS: [&str; 2] = ["entities", "children"];
+
+/// Split an entity ID into its index (low 32 bits) and generation (high 32 bits)
+pub fn decode_entity_id(entity: u64) -> Value {
+    json!({
+        "raw": entity,
+        "index": entity & 0xFFFF_FFFF,
+        "generation": entity >> 32,
+    })
+}
+
+/// Replace entity IDs anywhere in `value` with `{raw, index, generation}` objects
+pub fn decode_entity_ids(value: &mut Value) {
+    map_entity_ids(value, &decode_entity_id);
+}
+
+/// Replace entity IDs anywhere in `value` with `0x`-prefixed hex strings
+pub fn hex_entity_ids(value: &mut Value) {
+    map_entity_ids(value, &|entity| json!(format!("{:#x}", entity)));
+}
+
+/// Replace every entity ID in `value` with the result of `render`
+///
+/// Only numbers stored under known entity fields are touched, so component data such as
+/// a `value` or `counter` field is left alone.
+fn map_entity_ids(value: &mut Value, render: &impl Fn(u64) -> Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if let Some(entity) = field.as_u64() {
+                    if ENTITY_ID_FIELDS.contains(&key.as_str()) {
+                        *field = render(entity);
+                    }
+                    continue;
+                }
+                if ENTITY_ID_LIST_FIELDS.contains(&key.as_str()) {
+                    if let Value::Array(items) = field {
+                        for item in items.iter_mut() {
+                            if let Some(entity) = item.as_u64() {
+                                *item = render(entity);
+                            }
+                        }
+                    }
+                }
+                map_entity_ids(field, render);
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| map_entity_ids(item, render)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entity_ids() {
+        let entity = (3u64 << 32) | 7;
+        let mut value = json!({
+            "entity": entity,
+            "parent": null,
+            "children": [entity],
+            "components": { "my_game::Score": { "value": 42 } },
+            "results": [{ "entity": entity, "status": "ok" }]
+        });
+        decode_entity_ids(&mut value);
+
+        let decoded = json!({ "raw": entity, "index": 7, "generation": 3 });
+        assert_eq!(value["entity"], decoded);
+        assert!(value["parent"].is_null());
+        assert_eq!(value["children"], json!([decoded]));
+        assert_eq!(value["components"]["my_game::Score"]["value"], 42);
+        assert_eq!(value["results"][0]["entity"], decoded);
+    }
+
+    #[test]
+    fn test_hex_entity_ids() {
+        let entity = (1u64 << 32) | 255;
+        let mut value = json!({
+            "entity": entity,
+            "children": [7],
+            "components": { "my_game::Score": { "value": 42 } }
+        });
+        hex_entity_ids(&mut value);
+
+        assert_eq!(value["entity"], json!("0x1000000ff"));
+        assert_eq!(value["children"], json!(["0x7"]));
+        assert_eq!(value["components"]["my_game::Score"]["value"], 42);
+    }
+
+    #[test]
+    fn test_parse_entity_ids_raw_array() {
+        assert_eq!(parse_entity_ids("[1, 2, 3]").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_entity_ids_query_result() {
+        let input = r#"[{"entity": 10, "components": {}}, {"entity": 20, "components": {}}]"#;
+        assert_eq!(parse_entity_ids(input).unwrap(), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_parse_entity_ids_list_entities_result() {
+        let input = r#"{"entities": [{"entity": 7, "components": []}], "total_count": 1}"#;
+        assert_eq!(parse_entity_ids(input).unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn test_parse_entity_ids_hex_strings() {
+        let input = r#"[{"entity": "0x100000007", "components": {}}, "0xa"]"#;
+        assert_eq!(parse_entity_ids(input).unwrap(), vec![(1 << 32) | 7, 10]);
+    }
+
+    #[test]
+    fn test_parse_entity_ids_rejects_invalid_input() {
+        assert!(parse_entity_ids("not json").is_err());
+        assert!(parse_entity_ids(r#"{"status": "ok"}"#).is_err());
+        assert!(parse_entity_ids(r#"[{"name": "no entity"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_entity_ref() {
+        assert_eq!(parse_entity_ref("12345").unwrap(), "12345");
+        assert_eq!(parse_entity_ref("name:Player").unwrap(), "name:Player");
+        assert!(parse_entity_ref("name:").is_err());
+        assert!(parse_entity_ref("Player").is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! `--expect` golden-file checks of the JSON a command prints
+//!
+//! The golden file is the left side of a `json_diff` and the command's result the right, so
+//! differences are reported as `{only_left, only_right, changed}` lists whose `path`s are
+//! JSON pointers into the result.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use super::diff::{diff_count, json_diff};
+
+/// The golden file from `--expect`, taken by the first command that runs
+static EXPECTED_OUTPUT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Returned when a command's result differs from its `--expect` golden file, so the process
+/// can exit with the assertion failure code instead of the generic failure code
+#[derive(Debug)]
+pub struct ExpectationFailure {
+    path: PathBuf,
+    diff: Value,
+}
+
+impl fmt::Display for ExpectationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = diff_count(&self.diff);
+        writeln!(
+            f,
+            "Output does not match {} ({} difference{})",
+            self.path.display(),
+            count,
+            if count == 1 { "" } else { "s" }
+        )?;
+        let diff = serde_json::to_string_pretty(&self.diff).map_err(|_| fmt::Error)?;
+        write!(f, "{}", diff)
+    }
+}
+
+impl std::error::Error for ExpectationFailure {}
+
+/// Compare a command's result with the JSON in the golden file at `path`
+pub fn check_expected_output(path: &Path, actual: &Value) -> Result<()> {
+    let golden = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read golden file {}", path.display()))?;
+    let expected: Value = serde_json::from_str(&golden)
+        .with_context(|| format!("Golden file {} is not valid JSON", path.display()))?;
+
+    let diff = json_diff(&expected, actual);
+    if diff_count(&diff) == 0 {
+        Ok(())
+    } else {
+        Err(ExpectationFailure {
+            path: path.to_path_buf(),
+            diff,
+        }
+        .into())
+    }
+}
+
+/// Set the golden file that the next command's result must match
+pub fn set_expected_output(path: Option<PathBuf>) {
+    *EXPECTED_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Take the pending golden file, so commands run from inside another command don't check it
+pub fn take_expected_output() -> Option<PathBuf> {
+    EXPECTED_OUTPUT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_check_expected_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("golden.json");
+        fs::write(&path, r#"{ "exists": true }"#).unwrap();
+
+        assert!(check_expected_output(&path, &json!({ "exists": true })).is_ok());
+        let error = check_expected_output(&path, &json!({ "exists": false })).unwrap_err();
+        let failure = error.downcast_ref::<ExpectationFailure>().unwrap();
+        assert_eq!(diff_count(&failure.diff), 1);
+        assert!(error.to_string().contains("\"path\": \"/exists\""));
+
+        let missing = dir.path().join("missing.json");
+        assert!(check_expected_output(&missing, &json!({})).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+//! Session journal of executed commands
+//!
+//! Each line is `<unix timestamp>\t<ok|failed>\t<command>`, where the command is the
+//! `format_command` string, so `replay` can feed it straight back to the parser.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+
+/// Journal file commands are appended to, if `--journal` was given
+static JOURNAL_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+const STATUS_OK: &str = "ok";
+const STATUS_FAILED: &str = "failed";
+
+/// A command read back from a journal
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// Command string as accepted by `parse_command_string`
+    pub command: String,
+    /// Whether the command succeeded when it was recorded
+    pub succeeded: bool,
+}
+
+/// Append every executed command to `path`, or stop journaling with `None`
+pub fn set_journal_path(path: Option<PathBuf>) {
+    *JOURNAL_PATH.lock().unwrap_or_else(|e| e.into_inner()) = path;
+}
+
+/// Append a command and its outcome to the journal, if one is configured
+///
+/// A journal that can't be written only produces a warning, so it never fails the
+/// command it records.
+pub fn record_command(command: &str, succeeded: bool) {
+    let path = JOURNAL_PATH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let Some(path) = path else {
+        return;
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    let status = if succeeded { STATUS_OK } else { STATUS_FAILED };
+    // Commands are single-line strings, but keep a stray newline from splitting the entry
+    let line = format!(
+        "{:.3}\t{}\t{}\n",
+        timestamp,
+        status,
+        command.replace('\n', " ")
+    );
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(e) = written {
+        eprintln!(
+            "Warning: could not write to journal {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Read the commands recorded in a journal, in order
+pub fn read_journal(path: &Path) -> Result<Vec<JournalEntry>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read journal {}", path.display()))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse_journal_line(line)
+                .with_context(|| format!("Invalid journal entry on line {}: '{}'", index + 1, line))
+        })
+        .collect()
+}
+
+fn parse_journal_line(line: &str) -> Result<JournalEntry> {
+    let mut fields = line.splitn(3, '\t');
+    let (Some(_timestamp), Some(status), Some(command)) =
+        (fields.next(), fields.next(), fields.next())
+    else {
+        anyhow::bail!("expected '<timestamp>\\t<ok|failed>\\t<command>'");
+    };
+
+    let succeeded = match status {
+        STATUS_OK => true,
+        STATUS_FAILED => false,
+        other => anyhow::bail!("unknown status '{}'", other),
+    };
+    Ok(JournalEntry {
+        command: command.to_string(),
+        succeeded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_journal_line() {
+        assert_eq!(
+            parse_journal_line("1760000000.000\tok\tinsert 42 {\"a::B\": {\"x\": 1}}").unwrap(),
+            JournalEntry {
+                command: "insert 42 {\"a::B\": {\"x\": 1}}".to_string(),
+                succeeded: true,
+            }
+        );
+        assert!(
+            !parse_journal_line("1760000000.000\tfailed\tdestroy 7")
+                .unwrap()
+                .succeeded
+        );
+        assert!(parse_journal_line("destroy 7").is_err());
+        assert!(parse_journal_line("1760000000.000\tmaybe\tdestroy 7").is_err());
+    }
+
+    #[test]
+    fn test_read_journal_skips_blank_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.journal");
+        std::fs::write(
+            &path,
+            "1760000000.000\tok\tlist\n\n1760000001.000\tfailed\tdestroy 7\n",
+        )
+        .unwrap();
+
+        let entries = read_journal(&path).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "list");
+        assert!(!entries[1].succeeded);
+    }
+}
@@ -1,5 +1,215 @@
-use anyhow::{Result, bail};
-use serde_json::Value;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result, bail};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde_json::{Value, json};
+
+use super::entity::{IdBase, decode_entity_ids, hex_entity_ids};
+use super::table::{OutputFormat, csv_table, output_format};
+
+/// Whether command output is printed as single-line JSON instead of pretty-printed
+static COMPACT_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Whether entity IDs in command output are expanded into index/generation objects
+static DECODE_ENTITY_IDS: AtomicBool = AtomicBool::new(false);
+
+/// Switch command output between single-line (`true`) and pretty-printed (`false`) JSON
+pub fn set_compact_output(compact: bool) {
+    COMPACT_OUTPUT.store(compact, Ordering::Relaxed);
+}
+
+/// Whether entity IDs in command output are printed as hex strings
+static HEX_ENTITY_IDS: AtomicBool = AtomicBool::new(false);
+
+/// Expand entity IDs in command output into `{raw, index, generation}` objects
+pub fn set_decode_entity_ids(decode: bool) {
+    DECODE_ENTITY_IDS.store(decode, Ordering::Relaxed);
+}
+
+/// Decimal places floats in command output are rounded to, if any
+static ROUND_FLOATS: Mutex<Option<u32>> = Mutex::new(None);
+
+/// Round floats in command output to `digits` decimal places, or print them exactly with `None`
+pub fn set_round_floats(digits: Option<u32>) {
+    *ROUND_FLOATS.lock().unwrap_or_else(|e| e.into_inner()) = digits;
+}
+
+/// Round every float in `value` to `digits` decimal places, leaving integers alone
+fn round_floats(value: &mut Value, digits: u32) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let Some(float) = number.as_f64() else {
+                return;
+            };
+            let scale = 10f64.powi(digits as i32);
+            // Rounding tiny negatives yields -0.0, which reads like a sign error
+            let rounded = (float * scale).round() / scale + 0.0;
+            if let Some(rounded) = serde_json::Number::from_f64(rounded) {
+                *number = rounded;
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| round_floats(item, digits)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|field| round_floats(field, digits)),
+        _ => {}
+    }
+}
+
+/// Choose the number base entity IDs are printed in
+pub fn set_id_base(base: IdBase) {
+    HEX_ENTITY_IDS.store(base == IdBase::Hex, Ordering::Relaxed);
+}
+
+/// Whether `print_json` keeps a copy of the last value it printed
+static CAPTURE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Last value printed while capturing, before any entity ID rewriting
+static CAPTURED_OUTPUT: Mutex<Option<Value>> = Mutex::new(None);
+
+/// Start keeping the last value printed by `print_json`, for `take_captured_output`
+pub fn capture_output() {
+    CAPTURE_OUTPUT.store(true, Ordering::Relaxed);
+}
+
+/// Take the last value printed since `capture_output` was called
+pub fn take_captured_output() -> Option<Value> {
+    CAPTURED_OUTPUT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+}
+
+/// Values `print_json` collected instead of printing, while `collect_output` is active
+static COLLECTED_OUTPUT: Mutex<Option<Vec<Value>>> = Mutex::new(None);
+
+/// Collect values passed to `print_json` instead of printing them, until
+/// `take_collected_output` is called
+pub fn collect_output() {
+    *COLLECTED_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()) = Some(Vec::new());
+}
+
+/// Stop collecting and return the values `print_json` received since `collect_output`
+pub fn take_collected_output() -> Vec<Value> {
+    COLLECTED_OUTPUT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .unwrap_or_default()
+}
+
+/// Command whose results `print_json` wraps as `{"command", "result"}` for
+/// `--emit-command-echo`
+static COMMAND_ECHO: Mutex<Option<String>> = Mutex::new(None);
+
+/// Label printed results with `command` until it is changed again, returning the previous label
+pub fn set_command_echo(command: Option<String>) -> Option<String> {
+    std::mem::replace(
+        &mut *COMMAND_ECHO.lock().unwrap_or_else(|e| e.into_inner()),
+        command,
+    )
+}
+
+/// Where `--output-file` sends command output
+///
+/// Results are streamed into a temporary file next to `path` as they are printed, and
+/// `finish_output_file` renames it into place once the command is done.
+struct OutputFile {
+    path: PathBuf,
+    /// The temporary file, created by the first result so a run that prints nothing leaves
+    /// an existing file alone
+    temp: Option<(PathBuf, BufWriter<fs::File>)>,
+}
+
+/// The `--output-file` sink; `None` prints to stdout
+static OUTPUT_FILE: Mutex<Option<OutputFile>> = Mutex::new(None);
+
+/// Send command output to `path` instead of stdout, or back to stdout with `None`
+pub fn set_output_file(path: Option<PathBuf>) {
+    *OUTPUT_FILE.lock().unwrap_or_else(|e| e.into_inner()) =
+        path.map(|path| OutputFile { path, temp: None });
+}
+
+/// The hidden file next to `path` that output is written to before it replaces `path`
+fn temp_path_for(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Output file {:?} has no file name", path))?;
+    Ok(path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    )))
+}
+
+impl OutputFile {
+    /// Append one result to the temporary file, creating it first if needed
+    fn write(&mut self, text: &str) -> Result<()> {
+        if self.temp.is_none() {
+            let temp_path = temp_path_for(&self.path)?;
+            let file = fs::File::create(&temp_path)
+                .with_context(|| format!("Failed to create output file {:?}", temp_path))?;
+            self.temp = Some((temp_path, BufWriter::new(file)));
+        }
+        let Some((temp_path, writer)) = self.temp.as_mut() else {
+            unreachable!("the temporary file was just created");
+        };
+        writeln!(writer, "{}", text)
+            .with_context(|| format!("Failed to write output file {:?}", temp_path))
+    }
+
+    /// Move the temporary file over `path`, so readers see either the old file or the new
+    /// one, never a partial write
+    fn finish(self) -> Result<()> {
+        let Some((temp_path, writer)) = self.temp else {
+            return Ok(());
+        };
+        let written = writer
+            .into_inner()
+            .map_err(|e| e.into_error())
+            .and_then(|file| file.sync_all())
+            .with_context(|| format!("Failed to write output file {:?}", temp_path))
+            .and_then(|()| {
+                fs::rename(&temp_path, &self.path)
+                    .with_context(|| format!("Failed to move output into {:?}", self.path))
+            });
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        written
+    }
+}
+
+/// Move everything written to the `--output-file` into place
+///
+/// Called once the command has finished, whether or not it succeeded; later output goes to
+/// stdout again.
+pub fn finish_output_file() -> Result<()> {
+    let output_file = OUTPUT_FILE.lock().unwrap_or_else(|e| e.into_inner()).take();
+    output_file.map_or(Ok(()), OutputFile::finish)
+}
+
+/// Print one formatted result to stdout, or append it to the `--output-file`
+fn emit(text: &str) -> Result<()> {
+    let mut output_file = OUTPUT_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    match output_file.as_mut() {
+        Some(output_file) => output_file.write(text),
+        None => {
+            println!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+/// Print already-rendered text output, such as a table, to stdout or the `--output-file`
+pub fn print_text(text: &str) -> Result<()> {
+    emit(text)
+}
 
 /// Parse a JSON string and validate it's an object
 ///
@@ -35,13 +245,301 @@ pub fn parse_json_value(json_str: &str) -> Result<Value> {
     Ok(serde_json::from_str(json_str)?)
 }
 
-/// Format a JSON value with pretty printing
+/// Deep-merge `patch` into `target`
+///
+/// Objects are merged key by key, recursively. Any other patch value, including arrays
+/// and `null`, replaces the target value at that position.
+pub fn merge_json(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+/// Look up a dot-separated field path such as `translation.x` or `points.0` in a value
+///
+/// Numeric segments index into arrays. An empty path returns the value itself.
+pub fn value_at_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    if path.is_empty() {
+        return Some(value);
+    }
+    path.split('.')
+        .try_fold(value, |current, segment| match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        })
+}
+
+/// Format a JSON value, pretty-printed unless compact output was requested
+///
+/// Object keys are always written in sorted order: `serde_json` is built without its
+/// `preserve_order` feature, so `Map` is a `BTreeMap` and output is byte-stable across
+/// runs. Enabling that feature anywhere in the dependency tree would break this.
 pub fn format_json(value: &serde_json::Value) -> Result<String> {
-    Ok(serde_json::to_string_pretty(value)?)
+    if COMPACT_OUTPUT.load(Ordering::Relaxed) {
+        Ok(serde_json::to_string(value)?)
+    } else {
+        Ok(serde_json::to_string_pretty(value)?)
+    }
 }
 
-/// Print a JSON value with pretty formatting
-pub fn print_json(value: &serde_json::Value) -> Result<()> {
-    println!("{}", format_json(value)?);
+/// Write a JSON value to a file using the configured formatting, gzip-compressed if `gzip`
+///
+/// The value is serialized straight into the file rather than through an intermediate
+/// string, so a large registry schema isn't held in memory twice. Returns the size of the
+/// written file in bytes.
+pub fn write_json_file(path: &Path, value: &Value, gzip: bool) -> Result<u64> {
+    let file =
+        fs::File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    if gzip {
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+        write_json(&mut encoder, value)?;
+        writer = encoder.finish()?;
+    } else {
+        write_json(&mut writer, value)?;
+    }
+    writer
+        .flush()
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(fs::metadata(path)?.len())
+}
+
+/// Serialize a JSON value into `writer`, pretty-printed unless compact output was requested
+fn write_json(mut writer: impl Write, value: &Value) -> Result<()> {
+    if COMPACT_OUTPUT.load(Ordering::Relaxed) {
+        serde_json::to_writer(&mut writer, value)?;
+    } else {
+        serde_json::to_writer_pretty(&mut writer, value)?;
+    }
+    writeln!(writer)?;
     Ok(())
 }
+
+/// Print a JSON value to stdout, or the `--output-file`, using the configured formatting
+pub fn print_json(value: &serde_json::Value) -> Result<()> {
+    print_json_as(value, COMPACT_OUTPUT.load(Ordering::Relaxed))
+}
+
+/// Print a JSON value on a single line whatever the configured formatting, for NDJSON streams
+pub fn print_json_line(value: &serde_json::Value) -> Result<()> {
+    print_json_as(value, true)
+}
+
+fn print_json_as(value: &serde_json::Value, compact: bool) -> Result<()> {
+    if CAPTURE_OUTPUT.load(Ordering::Relaxed) {
+        *CAPTURED_OUTPUT.lock().unwrap_or_else(|e| e.into_inner()) = Some(value.clone());
+    }
+    if let Some(collected) = COLLECTED_OUTPUT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+    {
+        collected.push(value.clone());
+        return Ok(());
+    }
+    let command = COMMAND_ECHO
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    // A CSV result stays a bare row set, since the echo wrapper isn't tabular
+    let format = output_format();
+    let mut value = match command {
+        Some(command) if format != OutputFormat::Csv => {
+            json!({ "command": command, "result": value })
+        }
+        _ => value.clone(),
+    };
+    if DECODE_ENTITY_IDS.load(Ordering::Relaxed) {
+        decode_entity_ids(&mut value);
+    } else if HEX_ENTITY_IDS.load(Ordering::Relaxed) {
+        hex_entity_ids(&mut value);
+    }
+    if let Some(digits) = *ROUND_FLOATS.lock().unwrap_or_else(|e| e.into_inner()) {
+        round_floats(&mut value, digits);
+    }
+    match format {
+        OutputFormat::Csv => emit(&csv_table(&value)?),
+        OutputFormat::Ron => emit(&format_ron(&value, compact)?),
+        _ if compact => emit(&serde_json::to_string(&value)?),
+        _ => emit(&serde_json::to_string_pretty(&value)?),
+    }
+}
+
+/// Serialize a JSON value as RON
+///
+/// The value is converted as-is, so objects become RON maps with string keys rather than
+/// structs, and enum variants or tuples BRP serialized as JSON stay in their JSON shape.
+fn format_ron(value: &Value, compact: bool) -> Result<String> {
+    if compact {
+        Ok(ron::to_string(value)?)
+    } else {
+        Ok(ron::ser::to_string_pretty(
+            value,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_write_json_file_gzip_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schema.json.gz");
+        let schema =
+            json!({ "my_game::Health": { "kind": "Struct", "reflectTypes": ["Component"] } });
+
+        let size = write_json_file(&path, &schema, true).unwrap();
+
+        assert_eq!(size, fs::metadata(&path).unwrap().len());
+        let mut decompressed = String::new();
+        GzDecoder::new(fs::File::open(&path).unwrap())
+            .read_to_string(&mut decompressed)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&decompressed).unwrap(),
+            schema
+        );
+    }
+
+    #[test]
+    fn test_merge_json_nested_component() {
+        let mut transform = json!({
+            "translation": { "x": 1.0, "y": 2.0, "z": 3.0 },
+            "rotation": [0.0, 0.0, 0.0, 1.0],
+            "scale": { "x": 1.0, "y": 1.0, "z": 1.0 }
+        });
+
+        merge_json(
+            &mut transform,
+            json!({
+                "translation": { "y": 5.0 },
+                "rotation": [0.0, 0.6, 0.0, 0.8],
+                "extra": null
+            }),
+        );
+
+        assert_eq!(
+            transform,
+            json!({
+                "translation": { "x": 1.0, "y": 5.0, "z": 3.0 },
+                "rotation": [0.0, 0.6, 0.0, 0.8],
+                "scale": { "x": 1.0, "y": 1.0, "z": 1.0 },
+                "extra": null
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_floats_leaves_integers_alone() {
+        let mut value = json!({
+            "entity": 4294967297u64,
+            "rotation": [0.0, 0.70710677, -0.00001, 0.9999999],
+            "scale": { "x": 1.23456, "count": 3 }
+        });
+
+        round_floats(&mut value, 3);
+
+        assert_eq!(
+            value,
+            json!({
+                "entity": 4294967297u64,
+                "rotation": [0.0, 0.707, 0.0, 1.0],
+                "scale": { "x": 1.235, "count": 3 }
+            })
+        );
+        assert!(value["entity"].is_u64());
+        assert!(value["rotation"][3].is_f64());
+    }
+
+    #[test]
+    fn test_merge_json_replaces_non_objects() {
+        let mut value = json!({ "a": 1 });
+        merge_json(&mut value, json!("replaced"));
+        assert_eq!(value, json!("replaced"));
+    }
+
+    #[test]
+    fn test_value_at_path() {
+        let transform = json!({
+            "translation": { "x": 1.0, "y": 2.0 },
+            "points": [[0, 1], [2, 3]]
+        });
+
+        assert_eq!(
+            value_at_path(&transform, "translation.y"),
+            Some(&json!(2.0))
+        );
+        assert_eq!(value_at_path(&transform, "points.1.0"), Some(&json!(2)));
+        assert_eq!(value_at_path(&transform, ""), Some(&transform));
+        assert_eq!(value_at_path(&transform, "translation.w"), None);
+        assert_eq!(value_at_path(&transform, "points.x"), None);
+    }
+
+    #[test]
+    fn test_output_file_replaced_when_finished() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.json");
+        fs::write(&path, "old\n").unwrap();
+
+        let mut output_file = OutputFile {
+            path: path.clone(),
+            temp: None,
+        };
+        output_file.write("[1]").unwrap();
+        output_file.write("[2]").unwrap();
+        // Nothing replaces the old file until the command is done
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old\n");
+        output_file.finish().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1]\n[2]\n");
+        // The temporary file is renamed away, not left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_format_ron() {
+        let value = json!({ "translation": [1.0, 2.5, 0.0], "name": "Player" });
+
+        assert_eq!(
+            format_ron(&value, true).unwrap(),
+            r#"{"name":"Player","translation":[1.0,2.5,0.0]}"#
+        );
+        assert!(
+            format_ron(&value, false)
+                .unwrap()
+                .contains("\"name\": \"Player\"")
+        );
+    }
+
+    #[test]
+    fn test_format_json_sorts_keys() {
+        let value: Value =
+            serde_json::from_str(r#"{"b": 1, "a": {"z": true, "y": [{"d": 0, "c": 0}]}}"#).unwrap();
+
+        let formatted = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(formatted, r#"{"a":{"y":[{"c":0,"d":0}],"z":true},"b":1}"#);
+        assert_eq!(
+            format_json(&json!({ "b": 1, "a": 2 })).unwrap(),
+            "{\n  \"a\": 2,\n  \"b\": 1\n}"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! `--server-log-level`, turned into a `RUST_LOG` filter for apps started by the tool
+
+use std::fmt;
+
+use clap::ValueEnum;
+
+/// Environment variable Bevy's `LogPlugin` reads its filter from
+const RUST_LOG: &str = "RUST_LOG";
+
+/// Directives Bevy's `LogPlugin` applies by default, kept so a raised level doesn't flood the
+/// log with GPU driver output
+const QUIET_DEPENDENCIES: &str = "wgpu=error,naga=warn";
+
+/// Log level for an app started in managed or detached mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ServerLogLevel {
+    /// Errors only
+    Error,
+    /// Warnings and errors
+    Warn,
+    /// Bevy's default level
+    Info,
+    /// Debug output, e.g. from the app's own systems
+    Debug,
+    /// Everything, including per-frame tracing
+    Trace,
+}
+
+impl fmt::Display for ServerLogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerLogLevel::Error => write!(f, "error"),
+            ServerLogLevel::Warn => write!(f, "warn"),
+            ServerLogLevel::Info => write!(f, "info"),
+            ServerLogLevel::Debug => write!(f, "debug"),
+            ServerLogLevel::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// The app environment from `--env` with `RUST_LOG` set for `level`
+///
+/// An explicit `--env RUST_LOG=...` wins over the level, since it can say more than a level
+/// can.
+pub fn with_server_log_level(
+    env: &[(String, String)],
+    level: Option<ServerLogLevel>,
+) -> Vec<(String, String)> {
+    let mut env = env.to_vec();
+    if let Some(level) = level.filter(|_| !env.iter().any(|(key, _)| key == RUST_LOG)) {
+        env.push((
+            RUST_LOG.to_string(),
+            format!("{},{}", level, QUIET_DEPENDENCIES),
+        ));
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_server_log_level() {
+        let env = vec![("WGPU_BACKEND".to_string(), "vulkan".to_string())];
+
+        assert_eq!(
+            with_server_log_level(&env, Some(ServerLogLevel::Debug)),
+            [
+                ("WGPU_BACKEND".to_string(), "vulkan".to_string()),
+                (
+                    "RUST_LOG".to_string(),
+                    "debug,wgpu=error,naga=warn".to_string()
+                ),
+            ]
+        );
+        assert_eq!(with_server_log_level(&env, None), env);
+
+        let explicit = vec![("RUST_LOG".to_string(), "my_game=trace".to_string())];
+        assert_eq!(
+            with_server_log_level(&explicit, Some(ServerLogLevel::Debug)),
+            explicit
+        );
+    }
+}
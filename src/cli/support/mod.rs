@@ -4,17 +4,48 @@
 //! organized into logical submodules.
 
 // Module declarations
+mod aliases;
 mod app_detection;
+mod assertion;
 mod binary_discovery;
+mod confirm;
+mod diff;
+mod duration;
 mod entity;
+mod expect;
+mod journal;
 mod json;
+mod log_level;
+mod mutation;
 mod polling;
 mod port_utils;
+mod readiness;
+mod table;
 
 // Re-export public functions from submodules
-pub use app_detection::detect_bevy_app;
-pub use binary_discovery::find_workspace_binary_with_target_dir;
-pub use entity::parse_entity_arg;
-pub use json::{format_json, parse_json_object, parse_json_value, print_json};
+pub use aliases::{expand_alias, load_component_aliases, set_component_aliases};
+pub use app_detection::{detect_bevy_app, resolve_app_binary};
+pub use assertion::{Assertion, AssertionFailure, parse_assertion, set_assertion, take_assertion};
+pub use binary_discovery::{build_command, find_workspace_binary_with_target_dir};
+pub use confirm::{ask_confirmation, check_confirmation, require_confirmation};
+pub use diff::json_diff;
+pub use duration::parse_duration;
+pub use entity::{
+    ENTITY_NAME_PREFIX, IdBase, parse_entity_arg, parse_entity_ref, read_entity_ids_from_stdin,
+};
+pub use expect::{
+    ExpectationFailure, check_expected_output, set_expected_output, take_expected_output,
+};
+pub use journal::{read_journal, record_command, set_journal_path};
+pub use json::{
+    capture_output, collect_output, finish_output_file, format_json, merge_json, parse_json_object,
+    parse_json_value, print_json, print_json_line, print_text, set_command_echo,
+    set_compact_output, set_decode_entity_ids, set_id_base, set_output_file, set_round_floats,
+    take_captured_output, take_collected_output, value_at_path, write_json_file,
+};
+pub use log_level::{ServerLogLevel, with_server_log_level};
+pub use mutation::patch_mutations;
 pub use polling::poll_until_ready;
 pub use port_utils::{is_connection_error, is_port_available, wait_for_port_connectable};
+pub use readiness::{ReadyWhen, parse_ready_regex, parse_ready_when, wait_for_log_match};
+pub use table::{OutputFormat, methods_table, set_output_format, table_output};
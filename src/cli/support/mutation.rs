@@ -0,0 +1,69 @@
+//! Patch key handling for `mutate_component` and `mutate_resource`
+//!
+//! Each key of a JSON patch is sent to BRP as a reflection path, so keys may
+//! address nested fields (`audio.volume`), list elements (`levels[1]`) or tuple
+//! fields (`.0`) as well as top-level fields.
+
+use anyhow::{Result, bail};
+use serde_json::Value;
+
+/// Split a JSON patch into `(reflection path, value)` pairs, one per mutation
+pub fn patch_mutations(patch: &Value) -> Result<Vec<(String, Value)>> {
+    let Some(obj) = patch.as_object() else {
+        bail!("Patch must be a JSON object with field names and values");
+    };
+    obj.iter()
+        .map(|(key, value)| Ok((reflect_path(key)?, value.clone())))
+        .collect()
+}
+
+/// Turn a patch key into a BRP reflection path
+///
+/// Keys that already start with an accessor (`.`, `[` or `#`) are passed through;
+/// anything else is treated as a field name and gets a leading `.`
+pub fn reflect_path(key: &str) -> Result<String> {
+    let key = key.trim();
+    if key.is_empty() {
+        bail!("Patch keys must name a field path, e.g. \"audio.volume\" or \"levels[1]\"");
+    }
+    if key.starts_with(['.', '[', '#']) {
+        Ok(key.to_string())
+    } else {
+        Ok(format!(".{}", key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_reflect_path_prefixes_field_names() {
+        assert_eq!(reflect_path("counter").unwrap(), ".counter");
+        assert_eq!(reflect_path("audio.volume").unwrap(), ".audio.volume");
+        assert_eq!(reflect_path("levels[1]").unwrap(), ".levels[1]");
+    }
+
+    #[test]
+    fn test_reflect_path_passes_accessors_through() {
+        assert_eq!(reflect_path(".translation.x").unwrap(), ".translation.x");
+        assert_eq!(reflect_path("[0]").unwrap(), "[0]");
+        assert_eq!(reflect_path("#1").unwrap(), "#1");
+    }
+
+    #[test]
+    fn test_reflect_path_rejects_empty_key() {
+        assert!(reflect_path(" ").is_err());
+    }
+
+    #[test]
+    fn test_patch_mutations_requires_object() {
+        assert!(patch_mutations(&json!([1, 2])).is_err());
+        assert_eq!(
+            patch_mutations(&json!({ "settings.audio.volume": 0.5 })).unwrap(),
+            vec![(".settings.audio.volume".to_string(), json!(0.5))]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! `--ready-when` strategies for deciding when a launched app can take commands
+
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use regex::Regex;
+
+use super::polling::poll_until_ready;
+
+/// Prefix of the `--ready-when` value that waits for query results
+const QUERY_PREFIX: &str = "query:";
+
+/// When an app started with `--managed-commands` or `--detached` counts as ready
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadyWhen {
+    /// The BRP port accepts connections
+    Port,
+    /// The app answers BRP requests
+    Brp,
+    /// A query for this component returns at least one entity
+    Query(String),
+}
+
+impl fmt::Display for ReadyWhen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadyWhen::Port => write!(f, "port"),
+            ReadyWhen::Brp => write!(f, "brp"),
+            ReadyWhen::Query(component) => write!(f, "{}{}", QUERY_PREFIX, component),
+        }
+    }
+}
+
+/// Parse a `--ready-when` argument: `port`, `brp` or `query:COMPONENT`
+pub fn parse_ready_when(input: &str) -> Result<ReadyWhen> {
+    match input {
+        "port" => Ok(ReadyWhen::Port),
+        "brp" => Ok(ReadyWhen::Brp),
+        _ => match input.strip_prefix(QUERY_PREFIX).map(str::trim) {
+            Some("") => bail!("--ready-when query: requires a component type name"),
+            Some(component) => Ok(ReadyWhen::Query(component.to_string())),
+            None => bail!(
+                "Invalid --ready-when '{}': expected port, brp or query:COMPONENT",
+                input
+            ),
+        },
+    }
+}
+
+/// Parse an `--app-ready-regex` pattern
+pub fn parse_ready_regex(input: &str) -> Result<Regex> {
+    Regex::new(input).map_err(|e| anyhow::anyhow!("Invalid --app-ready-regex '{}': {}", input, e))
+}
+
+/// Whether a line of `log`, after the first `skip_bytes` bytes, matches `pattern`
+///
+/// Skipping lets the session log's own header be ignored, so only app output can match.
+fn log_has_match(log: &[u8], skip_bytes: u64, pattern: &Regex) -> bool {
+    let start = usize::try_from(skip_bytes)
+        .unwrap_or(usize::MAX)
+        .min(log.len());
+    String::from_utf8_lossy(&log[start..])
+        .lines()
+        .any(|line| pattern.is_match(line))
+}
+
+/// Wait until the app writes a line matching `pattern` to `log_file` after `skip_bytes`
+pub async fn wait_for_log_match(
+    log_file: &Path,
+    skip_bytes: u64,
+    pattern: &Regex,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<()> {
+    poll_until_ready(
+        || async {
+            let log = tokio::fs::read(log_file).await?;
+            if log_has_match(&log, skip_bytes, pattern) {
+                Ok(())
+            } else {
+                bail!("No log line matches {} yet", pattern)
+            }
+        },
+        timeout,
+        poll_interval,
+        format!(
+            "App did not log a line matching '{}' within {} seconds",
+            pattern,
+            timeout.as_secs()
+        ),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ready_when() {
+        assert_eq!(parse_ready_when("port").unwrap(), ReadyWhen::Port);
+        assert_eq!(parse_ready_when("brp").unwrap(), ReadyWhen::Brp);
+
+        let query = parse_ready_when("query:my_game::AssetsLoaded").unwrap();
+        assert_eq!(query, ReadyWhen::Query("my_game::AssetsLoaded".to_string()));
+        assert_eq!(query.to_string(), "query:my_game::AssetsLoaded");
+
+        assert!(parse_ready_when("query:").is_err());
+        assert!(parse_ready_when("assets").is_err());
+    }
+
+    #[test]
+    fn test_log_has_match_skips_header() {
+        let pattern = parse_ready_regex(r"^Ready!?$").unwrap();
+        let header = "=== Session ===\nReady\n";
+        let log = format!("{}Loading assets\nReady!\n", header);
+
+        assert!(log_has_match(log.as_bytes(), header.len() as u64, &pattern));
+        assert!(!log_has_match(
+            header.as_bytes(),
+            header.len() as u64,
+            &pattern
+        ));
+        assert!(log_has_match(header.as_bytes(), 0, &pattern));
+        assert!(parse_ready_regex("(unclosed").is_err());
+    }
+}
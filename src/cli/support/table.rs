@@ -0,0 +1,294 @@
+//! Plain-text table rendering for commands whose JSON output reads better as a table,
+//! and CSV rendering of entity rows for spreadsheets
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::sync::Mutex;
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// How command output is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// JSON, as configured by --compact/--pretty
+    #[default]
+    Json,
+    /// Aligned plain-text table for commands that support it, JSON for the rest
+    Table,
+    /// Comma-separated rows for array-of-object results such as query and list_entities
+    Csv,
+    /// RON, as used in Bevy scenes and assets, converted from the JSON result
+    Ron,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Table => write!(f, "table"),
+            OutputFormat::Csv => write!(f, "csv"),
+            OutputFormat::Ron => write!(f, "ron"),
+        }
+    }
+}
+
+/// How command output is rendered, from `--format`
+static OUTPUT_FORMAT: Mutex<OutputFormat> = Mutex::new(OutputFormat::Json);
+
+/// Choose how command output is rendered
+pub fn set_output_format(format: OutputFormat) {
+    *OUTPUT_FORMAT.lock().unwrap_or_else(|e| e.into_inner()) = format;
+}
+
+/// The `--format` command output is rendered in
+pub fn output_format() -> OutputFormat {
+    *OUTPUT_FORMAT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Whether `--format table` was requested
+pub fn table_output() -> bool {
+    output_format() == OutputFormat::Table
+}
+
+/// Error for `--format csv` on a result that has no rows
+const CSV_SHAPE_ERROR: &str = "--format csv only applies to tabular results (an array of objects, \
+     as printed by query and list_entities); use --format json for this command";
+
+/// Render an array of objects, or an object wrapping one in `entities`, as CSV
+///
+/// Each object becomes a row whose nested objects are flattened into dotted column names.
+/// Component data under `components` is flattened without that prefix and after the row's
+/// other fields, giving columns such as
+/// `bevy_transform::components::transform::Transform.translation`. Columns are the union
+/// over all rows in first-seen order, left blank where a row has no value; arrays are written
+/// as compact JSON.
+pub fn csv_table(value: &Value) -> Result<String> {
+    let rows = match value {
+        Value::Array(rows) => rows,
+        Value::Object(obj) => match obj.get("entities") {
+            Some(Value::Array(rows)) => rows,
+            _ => bail!(CSV_SHAPE_ERROR),
+        },
+        _ => bail!(CSV_SHAPE_ERROR),
+    };
+
+    let mut flattened = Vec::with_capacity(rows.len());
+    for row in rows {
+        let Value::Object(row) = row else {
+            bail!(CSV_SHAPE_ERROR);
+        };
+        let mut cells = Vec::new();
+        let components = match row.get("components") {
+            Some(Value::Object(components)) => Some(components),
+            _ => None,
+        };
+        for (key, value) in row {
+            if components.is_none() || key != "components" {
+                flatten_cell(key, value, &mut cells);
+            }
+        }
+        for (component, data) in components.into_iter().flatten() {
+            flatten_cell(component, data, &mut cells);
+        }
+        flattened.push(cells);
+    }
+
+    let mut columns: Vec<String> = Vec::new();
+    let mut seen = HashSet::new();
+    for (column, _) in flattened.iter().flatten() {
+        if seen.insert(column.clone()) {
+            columns.push(column.clone());
+        }
+    }
+
+    let mut lines = vec![
+        columns
+            .iter()
+            .map(|column| csv_field(column))
+            .collect::<Vec<_>>()
+            .join(","),
+    ];
+    for cells in flattened {
+        let cells: BTreeMap<String, String> = cells.into_iter().collect();
+        lines.push(
+            columns
+                .iter()
+                .map(|column| {
+                    cells
+                        .get(column)
+                        .map(|cell| csv_field(cell))
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Push `value` as one cell named `name`, or one cell per leaf if it is a non-empty object
+fn flatten_cell(name: &str, value: &Value, cells: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(obj) if !obj.is_empty() => {
+            for (key, value) in obj {
+                flatten_cell(&format!("{}.{}", name, key), value, cells);
+            }
+        }
+        Value::Null => cells.push((name.to_string(), String::new())),
+        Value::String(text) => cells.push((name.to_string(), text.clone())),
+        _ => cells.push((name.to_string(), value.to_string())),
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Render an `rpc.discover` method list as a table grouped by namespace
+///
+/// Each namespace gets a heading followed by its methods sorted by name, with the parameter
+/// names of each method; optional parameters are marked with `?`.
+pub fn methods_table(methods: &Value) -> String {
+    let mut groups: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for method in methods.as_array().into_iter().flatten() {
+        let name = method
+            .get("name")
+            .and_then(|n| n.as_str())
+            .or_else(|| method.as_str())
+            .unwrap_or_default();
+        let namespace = name.split(['/', '.']).next().unwrap_or_default();
+        groups
+            .entry(namespace.to_string())
+            .or_default()
+            .push((name.to_string(), params_summary(method)));
+    }
+
+    let name_width = groups
+        .values()
+        .flatten()
+        .map(|(name, _)| name.len())
+        .chain(["METHOD".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut lines = vec![format!("  {:<name_width$}  PARAMS", "METHOD")];
+    for (namespace, mut methods) in groups {
+        methods.sort();
+        lines.push(String::new());
+        lines.push(format!("{}:", namespace));
+        for (name, params) in methods {
+            lines.push(
+                format!("  {:<name_width$}  {}", name, params)
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+    }
+    lines.join("\n")
+}
+
+/// Comma-separated parameter names of a discovered method, `?` marking optional ones
+fn params_summary(method: &Value) -> String {
+    method
+        .get("params")
+        .and_then(|params| params.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|param| {
+            let name = param.get("name")?.as_str()?;
+            let required = param
+                .get("required")
+                .and_then(|r| r.as_bool())
+                .unwrap_or(true);
+            Some(if required {
+                name.to_string()
+            } else {
+                format!("{}?", name)
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_methods_table_groups_and_sorts_by_namespace() {
+        let methods = json!([
+            { "name": "rpc.discover", "params": [] },
+            {
+                "name": "bevy/query",
+                "params": [
+                    { "name": "data", "required": true },
+                    { "name": "filter", "required": false }
+                ]
+            },
+            { "name": "bevy/get", "params": [{ "name": "entity" }] }
+        ]);
+
+        let table = methods_table(&methods);
+
+        assert_eq!(
+            table,
+            [
+                "  METHOD        PARAMS",
+                "",
+                "bevy:",
+                "  bevy/get      entity",
+                "  bevy/query    data, filter?",
+                "",
+                "rpc:",
+                "  rpc.discover",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_csv_table_flattens_component_fields() {
+        let entities = json!({
+            "entities": [
+                {
+                    "entity": 1,
+                    "components": {
+                        "my_game::Health": { "current": 10, "max": 20 },
+                        "bevy_ecs::name::Name": "Player, one"
+                    }
+                },
+                {
+                    "entity": 2,
+                    "components": { "my_game::Health": { "current": 5, "max": 20 } }
+                }
+            ]
+        });
+
+        let csv = csv_table(&entities).unwrap();
+
+        assert_eq!(
+            csv,
+            [
+                "entity,bevy_ecs::name::Name,my_game::Health.current,my_game::Health.max",
+                "1,\"Player, one\",10,20",
+                "2,,5,20",
+            ]
+            .join("\n")
+        );
+        assert!(
+            csv_table(&json!({ "status": "ok" }))
+                .unwrap_err()
+                .to_string()
+                .contains("only applies to tabular results")
+        );
+    }
+}
@@ -23,10 +23,11 @@
 //! commands to Bevy apps that have the plugin installed. See the CLI documentation
 //! for usage details.
 
+mod auth_proxy;
 mod plugin;
 
 // Public API
-pub use plugin::BrpToolPlugin;
+pub use plugin::{BrpBoundPort, BrpMethodHandler, BrpToolPlugin};
 
 /// Default port for remote control connections
 ///
@@ -35,6 +36,11 @@ pub use plugin::BrpToolPlugin;
 /// while apps with BrpToolPlugin add custom methods (screenshot, shutdown) on the same port.
 pub const DEFAULT_REMOTE_PORT: u16 = 15702;
 
+/// Prefix of the stdout line announcing the port chosen by `BrpToolPlugin::with_port(0)`
+///
+/// The full line is `BRP_TOOL_PORT=<port>`.
+pub const BOUND_PORT_ANNOUNCEMENT: &str = "BRP_TOOL_PORT=";
+
 // CLI modules are exposed for testing purposes
 #[allow(missing_docs)]
 pub mod cli;
@@ -2,17 +2,45 @@
 
 mod cli;
 
+use std::ffi::OsString;
+use std::time::Duration;
+
 use anyhow::Result;
 use bevy_brp_tool::DEFAULT_REMOTE_PORT;
-use clap::Parser;
-use cli::commands::{Cli, extract_command_from_error, format_command, parse_command_string};
+use clap::FromArgMatches;
+use cli::client::{RemoteClient, UrlScheme};
+use cli::commands::{Cli, Commands, ExecutionOptions, extract_command_from_error, format_command};
 use cli::constants::BIN_NAME;
 use cli::{cli_client, commands, detached, error_formatter, help, managed, support};
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = match Cli::try_parse() {
-        Ok(cli) => cli,
+async fn main() {
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let parsed = Cli::command_for_args(&args)
+        .try_get_matches_from(&args)
+        .and_then(|matches| Ok((Cli::from_arg_matches(&matches)?, matches)));
+    let cli = match parsed {
+        Ok((mut cli, matches)) => {
+            cli.apply_interactive_default();
+
+            // Explicit flags win over BRP_* environment variables, which win over the config
+            // file; a missing file is fine
+            let configured = cli::config::load_config().and_then(|config| {
+                config.apply(&mut cli, &matches);
+                cli::config::apply_env_overrides(&mut cli, &matches, |name| {
+                    std::env::var(name).ok()
+                })
+            });
+            if let Err(error) = configured {
+                error_formatter::display_error(
+                    &error,
+                    error_formatter::should_use_color(cli.no_color),
+                    cli.verbose,
+                );
+                std::process::exit(1);
+            }
+            cli
+        }
         Err(error) => {
             // Check if this is a missing arguments error for a subcommand
             let error_msg = error.to_string();
@@ -34,9 +62,40 @@ async fn main() -> Result<()> {
         }
     };
 
+    support::set_compact_output(cli.compact && !cli.pretty);
+    support::set_decode_entity_ids(cli.decode_entity_ids);
+    support::set_id_base(cli.id_base);
+    support::set_output_file(cli.output_file.clone());
+    support::set_output_format(cli.output_format);
+    support::set_round_floats(cli.round);
+    support::set_assertion(cli.assert.clone());
+    support::set_expected_output(cli.expect.clone());
+    support::set_journal_path(cli.journal.clone());
+    let use_color = error_formatter::should_use_color(cli.no_color);
+    let verbose = cli.verbose;
+
+    // Output sent to --output-file is moved into place even when the command failed
+    let result = run(cli).await;
+    let finished = support::finish_output_file();
+    if let Err(error) = result.and(finished) {
+        error_formatter::display_error(&error, use_color, verbose);
+        std::process::exit(error_formatter::exit_code(&error));
+    }
+}
+
+/// Run the CLI with parsed arguments
+async fn run(cli: Cli) -> Result<()> {
+    if let Some(path) = &cli.aliases {
+        support::set_component_aliases(support::load_component_aliases(path)?);
+    }
+
     // Handle --list-commands flag
     if cli.list_commands {
-        help::display_all_commands();
+        if cli.json {
+            help::display_all_commands_json()?;
+        } else {
+            help::display_all_commands();
+        }
         return Ok(());
     }
 
@@ -80,13 +139,23 @@ async fn main() -> Result<()> {
 
     // Handle --cleanup-logs flag
     if cli.cleanup_logs {
-        detached::cleanup_all_logs().await?;
+        detached::cleanup_all_logs(cli.older_than).await?;
         return Ok(());
     }
 
+    // Handle --replay-sse flag
+    if let Some(path) = &cli.replay_sse {
+        return commands::replay_sse(path).await;
+    }
+
     // Handle --detect flag
     if cli.detect {
-        match help::display_detected_app(cli.profile.as_deref()) {
+        let detected = if cli.json {
+            help::display_detected_app_json(cli.profile.as_deref())
+        } else {
+            help::display_detected_app(cli.profile.as_deref())
+        };
+        match detected {
             Ok(()) => {}
             Err(e) => {
                 eprintln!("Error detecting app: {}", e);
@@ -103,11 +172,38 @@ async fn main() -> Result<()> {
     }
 
     // Validate that --detached doesn't have commands
-    if cli.detached && (cli.managed_commands.is_some() || cli.command.is_some()) {
+    if cli.detached
+        && (cli.managed_commands.is_some() || cli.commands.is_some() || cli.command.is_some())
+    {
         eprintln!("Error: --detached cannot be used with commands. It only starts the app.");
         std::process::exit(1);
     }
 
+    // Validate that --commands is only used against an already running app
+    if cli.commands.is_some() && cli.managed_commands.is_some() {
+        eprintln!("Error: Cannot use --commands and --managed-commands together");
+        eprintln!("  Use --commands to run against a running app");
+        eprintln!("  Or --managed-commands to start the app and run commands");
+        std::process::exit(1);
+    }
+
+    // Validate that --assert only checks a single command's result
+    if cli.assert.is_some() && (cli.commands.is_some() || cli.managed_commands.is_some()) {
+        eprintln!("Error: --assert cannot be used with --commands or --managed-commands");
+        eprintln!(
+            "  Use: {} <command> --assert '<POINTER OP VALUE>'",
+            BIN_NAME
+        );
+        std::process::exit(1);
+    }
+
+    // Validate that --expect only checks a single command's result
+    if cli.expect.is_some() && (cli.commands.is_some() || cli.managed_commands.is_some()) {
+        eprintln!("Error: --expect cannot be used with --commands or --managed-commands");
+        eprintln!("  Use: {} <command> --expect <PATH>", BIN_NAME);
+        std::process::exit(1);
+    }
+
     // Validate that --app is only used with --detached or --managed-commands
     if cli.app.is_some() && !cli.detached && cli.managed_commands.is_none() {
         eprintln!("Error: --app/-a can only be used with --detached/-d or --managed-commands/-m");
@@ -116,150 +212,317 @@ async fn main() -> Result<()> {
         std::process::exit(1);
     }
 
-    // Handle command precedence: --managed-commands takes priority over direct command
-    let (effective_commands, direct_command) = match (&cli.managed_commands, &cli.command) {
-        (Some(commands), Some(cmd)) => {
-            // Both provided - warn and use --managed-commands
+    // Validate that --app-args is only used when we launch the app
+    if cli.app_args.is_some() && !cli.detached && cli.managed_commands.is_none() {
+        eprintln!("Error: --app-args can only be used with --detached/-d or --managed-commands/-m");
+        eprintln!("  Use: {} --app-args '<ARGS>' -d", BIN_NAME);
+        eprintln!("  Or:  {} --app-args '<ARGS>' -m '<commands>'", BIN_NAME);
+        std::process::exit(1);
+    }
+    // Validate that --env is only used when we launch the app
+    if !cli.env.is_empty() && !cli.detached && cli.managed_commands.is_none() {
+        eprintln!("Error: --env can only be used with --detached/-d or --managed-commands/-m");
+        eprintln!("  Use: {} --env KEY=VALUE -d", BIN_NAME);
+        eprintln!("  Or:  {} --env KEY=VALUE -m '<commands>'", BIN_NAME);
+        std::process::exit(1);
+    }
+    // Validate that --server-log-level is only used when we launch the app
+    if cli.server_log_level.is_some() && !cli.detached && cli.managed_commands.is_none() {
+        eprintln!(
+            "Error: --server-log-level can only be used with --detached/-d or --managed-commands/-m"
+        );
+        eprintln!("  Use: {} --server-log-level debug -d", BIN_NAME);
+        eprintln!(
+            "  Or:  {} --server-log-level debug -m '<commands>'",
+            BIN_NAME
+        );
+        std::process::exit(1);
+    }
+    // Validate that --managed-timeout is only used with --managed-commands
+    if cli.managed_timeout.is_some() && cli.managed_commands.is_none() {
+        eprintln!("Error: --managed-timeout can only be used with --managed-commands/-m");
+        eprintln!(
+            "  Use: {} --managed-timeout <SECONDS> -m '<commands>'",
+            BIN_NAME
+        );
+        std::process::exit(1);
+    }
+
+    // Validate that --wait-ready-timeout is only used when we start the app
+    if cli.wait_ready_timeout.is_some() && !cli.detached && cli.managed_commands.is_none() {
+        eprintln!(
+            "Error: --wait-ready-timeout can only be used with --detached/-d or --managed-commands/-m"
+        );
+        eprintln!("  Use: {} --wait-ready-timeout <SECONDS> -d", BIN_NAME);
+        eprintln!(
+            "  Or:  {} --wait-ready-timeout <SECONDS> -m '<commands>'",
+            BIN_NAME
+        );
+        std::process::exit(1);
+    }
+
+    // Validate that --app-ready-regex is only used with --detached, the mode with a log file
+    if cli.app_ready_regex.is_some() && !cli.detached {
+        eprintln!("Error: --app-ready-regex can only be used with --detached/-d");
+        eprintln!("  Use: {} --app-ready-regex '<PATTERN>' -d", BIN_NAME);
+        std::process::exit(1);
+    }
+
+    // Validate that --keep-alive is only used with --managed-commands
+    if cli.keep_alive && cli.managed_commands.is_none() {
+        eprintln!("Error: --keep-alive can only be used with --managed-commands/-m");
+        eprintln!("  Use: {} --keep-alive -m '<commands>'", BIN_NAME);
+        std::process::exit(1);
+    }
+
+    // Validate that --before/--after are only used with --managed-commands
+    if (cli.before.is_some() || cli.after.is_some()) && cli.managed_commands.is_none() {
+        eprintln!("Error: --before and --after can only be used with --managed-commands/-m");
+        eprintln!(
+            "  Use: {} --before '<commands>' -m '<commands>' --after '<commands>'",
+            BIN_NAME
+        );
+        std::process::exit(1);
+    }
+
+    // Validate that --managed-json is only used with --managed-commands
+    if cli.managed_json && cli.managed_commands.is_none() {
+        eprintln!("Error: --managed-json can only be used with --managed-commands/-m");
+        eprintln!("  Use: {} --managed-json -m '<commands>'", BIN_NAME);
+        std::process::exit(1);
+    }
+
+    // Launched apps always run on this machine, so they can't be combined with a remote host
+    let remote_url = remote_base_url(&cli);
+    if remote_url.is_some() && (cli.detached || cli.managed_commands.is_some()) {
+        eprintln!(
+            "Error: --host/--scheme/--url cannot be used with --detached/-d or --managed-commands/-m"
+        );
+        eprintln!("  Launched apps listen on http://localhost; use --port to choose the port");
+        std::process::exit(1);
+    }
+    let app_args = match cli.app_args.as_deref() {
+        Some(args) => shlex::split(args)
+            .ok_or_else(|| anyhow::anyhow!("Invalid --app-args '{}': unbalanced quotes", args))?,
+        None => Vec::new(),
+    };
+    let app_env = support::with_server_log_level(&cli.env, cli.server_log_level);
+
+    // Handle command precedence: a command list takes priority over a direct command
+    let list_flag = if cli.managed_commands.is_some() {
+        Some("--managed-commands")
+    } else if cli.commands.is_some() {
+        Some("--commands")
+    } else {
+        None
+    };
+    let direct_command = match (list_flag, &cli.command) {
+        (Some(flag), Some(cmd)) => {
+            // Both provided - warn and use the command list
             eprintln!(
-                "Warning: Direct command '{}' used with --managed-commands - direct command '{}' ignored",
+                "Warning: Direct command '{}' used with {} - direct command '{}' ignored",
                 format_command(cmd.clone()),
+                flag,
                 format_command(cmd.clone())
             );
-            (Some(commands.clone()), None)
+            None
         }
-        (Some(commands), None) => (Some(commands.clone()), None),
-        (None, Some(cmd)) => (None, Some(cmd.clone())),
-        (None, None) => (None, None),
+        (_, cmd) => cmd.clone(),
+    };
+
+    let options = ExecutionOptions {
+        continue_on_error: cli.continue_on_error,
+        skip_ready_check: cli.no_ready_check,
+        brp_version: cli.brp_version,
+        // Undo only has something to reverse within a single command list
+        record_undo: cli.commands.is_some() || cli.managed_commands.is_some(),
+        poll_interval: cli.poll_interval.map(Duration::from_millis),
+        max_concurrency: cli.max_concurrency,
+        strict_types: cli.strict_types,
+        echo_commands: cli.emit_command_echo,
+        keepalive_interval: cli.keepalive_interval.map(Duration::from_secs),
     };
 
     if cli.detached {
         // Detached mode: start app in background with temp log file
-        let session = detached::start_detached(cli.app, cli.port, cli.profile).await?;
-        println!("\nDetached session started:");
-        println!("  PID: {}", session.pid);
-        println!("  Port: {}", session.port);
-        println!("  Log file: {:?}", session.log_file);
-        println!("\nUse '{} --info' to get session details", BIN_NAME);
-        println!("Use '{} shutdown' to stop the app", BIN_NAME);
+        let session = detached::start_detached(
+            cli.app,
+            cli.port,
+            cli.profile,
+            &app_args,
+            &app_env,
+            detached::DetachedReadiness {
+                ready_when: &cli.ready_when,
+                log_pattern: cli.app_ready_regex.as_ref(),
+                poll_interval: options.poll_interval,
+                timeout: cli.wait_ready_timeout.map(Duration::from_secs),
+                token: cli.token.as_deref(),
+            },
+        )
+        .await?;
+        println!("{}", detached::session_summary(&session));
         return Ok(());
-    } else if cli.managed_commands.is_some() {
+    } else if let Some(commands) = cli.managed_commands {
         // Managed commands mode: start app and execute commands directly
+        managed::run_managed(
+            cli.app,
+            cli.port,
+            cli.profile,
+            &app_args,
+            &app_env,
+            managed::ManagedRun {
+                commands,
+                before: cli.before,
+                after: cli.after,
+                ready_when: &cli.ready_when,
+                ready_timeout: cli.wait_ready_timeout.map(Duration::from_secs),
+                token: cli.token.clone(),
+                command_timeout: cli.managed_timeout.map(Duration::from_secs),
+                keep_alive: cli.keep_alive,
+                json_results: cli.managed_json,
+                interactive: cli.interactive,
+                yes: cli.yes,
+            },
+            options,
+        )
+        .await?;
+    } else if let Some(commands) = cli.commands {
+        // Standalone mode: execute a command list against an existing app
+        let client = connect(
+            remote_url.as_deref(),
+            cli.port,
+            cli.connect_to_newest,
+            cli.no_instance_check,
+            cli.token.as_deref(),
+        )
+        .await?
+        .with_brp_version(options.brp_version);
 
-        // Commands come from --managed-commands flag
-        let commands = cli.managed_commands.clone();
+        cli_client::execute_command_list(&client, &commands, options).await?;
+    } else if let Some(command) = direct_command {
+        // Standalone mode: execute single direct command against an existing app
+        // Config and validate_json only read local input and kill only touches the detached
+        // session, so none of them needs a responsive app
+        let client = if matches!(
+            command,
+            Commands::Config { .. } | Commands::Kill | Commands::ValidateJson { .. }
+        ) {
+            RemoteClient::new(cli.port)
+        } else {
+            connect(
+                remote_url.as_deref(),
+                cli.port,
+                cli.connect_to_newest,
+                cli.no_instance_check,
+                cli.token.as_deref(),
+            )
+            .await?
+        };
+        let client = client.with_brp_version(options.brp_version);
 
-        managed::run_managed(cli.app, commands, cli.port, cli.profile).await?;
+        if let Some(prompt) = commands::pending_confirmation(&command, &client, cli.yes) {
+            support::require_confirmation(&prompt)?;
+        }
+        commands::execute_standalone_command(&client, command, options).await?;
     } else {
-        // Standalone mode: connect to existing app
-
-        // Handle both --commands and direct commands
-        if let Some(commands) = effective_commands {
-            // First check if app is running
-            let running_instances = cli_client::detect_running_instances(cli.port).await?;
-
-            match running_instances.len() {
-                0 => {
-                    eprintln!(
-                        "Error: No app is running on port {}. Start the app first or use --managed mode.",
-                        cli.port
-                    );
-                    std::process::exit(1);
-                }
-                1 => {
-                    // Execute multiple commands from --commands flag
-                    let client = cli::client::RemoteClient::new(cli.port);
-
-                    // Parse and execute each command in the comma-separated list
-                    for command_str in commands.split(',') {
-                        let command_str = command_str.trim();
-                        if command_str.is_empty() {
-                            continue;
-                        }
-
-                        // Handle special "wait:N" command
-                        if command_str.starts_with("wait:") {
-                            if let Some(seconds_str) = command_str.strip_prefix("wait:") {
-                                if let Ok(seconds) = seconds_str.parse::<u64>() {
-                                    tokio::time::sleep(tokio::time::Duration::from_secs(seconds))
-                                        .await;
-                                    continue;
-                                }
-                            }
-                            eprintln!("Invalid wait command: {}", command_str);
-                            continue;
-                        }
-
-                        // Parse and execute the command
-                        match parse_command_string(command_str) {
-                            Ok(parsed_command) => {
-                                if let Err(e) =
-                                    commands::execute_standalone_command(&client, parsed_command)
-                                        .await
-                                {
-                                    eprintln!("Error executing command '{}': {}", command_str, e);
-                                    std::process::exit(1);
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to parse command '{}': {}", command_str, e);
-                                std::process::exit(1);
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    // Multiple instances detected
-                    eprintln!(
-                        "Error: Multiple app instances detected on ports: {:?}",
-                        running_instances
-                    );
-                    eprintln!("Please specify which instance to connect to using --port <PORT>");
-                    eprintln!("\nAvailable instances:");
-                    for port in &running_instances {
-                        eprintln!("  - Port {}", port);
-                    }
-                    std::process::exit(1);
+        // No commands provided
+        eprintln!("Error: No command specified. Use --help for usage information.");
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Base URL from `--url`, or from `--scheme`/`--host`/`--port` when they point somewhere other
+/// than a plain-HTTP app on this machine; `None` means use local instance detection
+fn remote_base_url(cli: &Cli) -> Option<String> {
+    if let Some(url) = &cli.url {
+        return Some(url.clone());
+    }
+
+    let is_local = matches!(
+        cli.host.as_str(),
+        "localhost" | "127.0.0.1" | "::1" | "[::1]"
+    );
+    if is_local && cli.scheme == UrlScheme::Http {
+        return None;
+    }
+
+    // Bare IPv6 addresses need brackets before a port can be appended
+    let host = if cli.host.contains(':') && !cli.host.starts_with('[') {
+        format!("[{}]", cli.host)
+    } else {
+        cli.host.clone()
+    };
+    Some(format!("{}://{}:{}", cli.scheme, host, cli.port))
+}
+
+/// Connect to the remote app if one was given, otherwise to the single local instance
+///
+/// Remote hosts bypass instance detection, which only scans ports on localhost, and so does
+/// `no_instance_check`, which connects to `port` whether or not anything answers there.
+async fn connect(
+    remote_url: Option<&str>,
+    port: u16,
+    connect_to_newest: bool,
+    no_instance_check: bool,
+    token: Option<&str>,
+) -> Result<RemoteClient> {
+    let client = match remote_url {
+        Some(url) => RemoteClient::with_url(url)?,
+        None if no_instance_check => RemoteClient::new(port),
+        None => RemoteClient::new(resolve_running_instance(port, connect_to_newest, token).await?),
+    };
+    Ok(match token {
+        Some(token) => client.with_token(token),
+        None => client,
+    })
+}
+
+/// Find the single running app instance to connect to in standalone mode
+///
+/// Exits with an error message if no instance or more than one instance is detected, unless
+/// `connect_to_newest` picks the most recently started detached session among them.
+async fn resolve_running_instance(
+    port: u16,
+    connect_to_newest: bool,
+    token: Option<&str>,
+) -> Result<u16> {
+    let running_instances = cli_client::detect_running_instances(port, token).await?;
+
+    match running_instances.len() {
+        0 => {
+            eprintln!(
+                "Error: No app is running on port {}. Start the app first or use --managed mode.",
+                port
+            );
+            std::process::exit(1);
+        }
+        1 => Ok(running_instances[0]),
+        _ => {
+            // Multiple instances detected
+            if connect_to_newest {
+                if let Some(newest) = detached::newest_session_port(&running_instances) {
+                    return Ok(newest);
                 }
+                eprintln!(
+                    "Error: --connect-to-newest found no detached session among ports: {:?}",
+                    running_instances
+                );
+                eprintln!("Only apps started with --detached record a start time");
+                std::process::exit(1);
             }
-        } else if let Some(command) = direct_command {
-            // Execute single direct command
-
-            // Detect running instances
-            let running_instances = cli_client::detect_running_instances(cli.port).await?;
-
-            match running_instances.len() {
-                0 => {
-                    eprintln!(
-                        "Error: No app is running on port {}. Start the app first or use --managed mode.",
-                        cli.port
-                    );
-                    std::process::exit(1);
-                }
-                1 => {
-                    // Exactly one instance - proceed normally
-                    let client = cli::client::RemoteClient::new(running_instances[0]);
-                    commands::execute_standalone_command(&client, command).await?;
-                }
-                _ => {
-                    // Multiple instances detected
-                    eprintln!(
-                        "Error: Multiple app instances detected on ports: {:?}",
-                        running_instances
-                    );
-                    eprintln!("Please specify which instance to connect to using --port <PORT>");
-                    eprintln!("\nAvailable instances:");
-                    for port in &running_instances {
-                        eprintln!("  - Port {}", port);
-                    }
-                    std::process::exit(1);
-                }
+            eprintln!(
+                "Error: Multiple app instances detected on ports: {:?}",
+                running_instances
+            );
+            eprintln!("Please specify which instance to connect to using --port <PORT>");
+            eprintln!("or pass --connect-to-newest to use the most recently started one");
+            eprintln!("\nAvailable instances:");
+            for port in &running_instances {
+                eprintln!("  - Port {}", port);
             }
-        } else {
-            // No commands provided
-            eprintln!("Error: No command specified. Use --help for usage information.");
             std::process::exit(1);
         }
     }
-
-    Ok(())
 }
@@ -1,27 +1,153 @@
 //! Bevy plugin implementation for remote control functionality
 
+use std::collections::HashMap;
+use std::io::{self, Cursor, Seek, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener};
+use std::sync::Arc;
+
+use base64::prelude::{BASE64_STANDARD, Engine as _};
 use bevy::prelude::*;
 use bevy::remote::http::RemoteHttpPlugin;
 use bevy::remote::{BrpError, BrpResult, RemotePlugin, error_codes};
 use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::window::PrimaryWindow;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageFormat, ImageResult};
 use serde_json::{Value, json};
 
-use crate::DEFAULT_REMOTE_PORT;
+use crate::auth_proxy::spawn_auth_proxy;
+use crate::{BOUND_PORT_ANNOUNCEMENT, DEFAULT_REMOTE_PORT};
 
 /// Command prefix for BRP Tool specific commands
 const BRP_TOOL_COMMAND_PREFIX: &str = "brp_tool/";
 
+/// Handler for a custom BRP method added with [`BrpToolPlugin::with_method`]
+///
+/// It receives the request's params (`None` when the request has none) and exclusive access
+/// to the world, and returns the JSON result or a [`BrpError`] that is sent back to the caller.
+pub type BrpMethodHandler = Arc<dyn Fn(Option<Value>, &mut World) -> BrpResult + Send + Sync>;
+
+/// Port the remote control server listens on, inserted by [`BrpToolPlugin`]
+///
+/// With port `0` this is the port the OS assigned, so read it from here rather than from
+/// the plugin.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrpBoundPort(pub u16);
+
 /// Plugin that adds remote control capabilities to a Bevy app
 #[derive(Default)]
 pub struct BrpToolPlugin {
-    /// Optional custom port for remote control connections
+    /// Optional custom port for remote control connections; `0` picks a free port
     pub port: Option<u16>,
+    /// Address the server listens on, `127.0.0.1` when unset
+    ///
+    /// See [`BrpToolPlugin::with_bind_address`] before setting this to anything else.
+    pub bind_address: Option<IpAddr>,
+    /// Shared secret clients must send as `Authorization: Bearer <token>`; none when unset
+    pub token: Option<String>,
+    /// Custom methods installed alongside the built-in `brp_tool/` methods
+    methods: Vec<(String, BrpMethodHandler)>,
 }
 
 impl BrpToolPlugin {
     /// Create plugin with custom port
+    ///
+    /// Port `0` asks the OS for a free port, which is useful when many apps run side by side.
+    /// The chosen port is stored in [`BrpBoundPort`] and printed to stdout at startup as a
+    /// `BRP_TOOL_PORT=<port>` line (see [`BOUND_PORT_ANNOUNCEMENT`](crate::BOUND_PORT_ANNOUNCEMENT)).
+    /// The port is held until just before the server binds it, but another process can still
+    /// take it in between; if clients then can't connect, restart the app.
     pub fn with_port(port: u16) -> Self {
-        Self { port: Some(port) }
+        Self {
+            port: Some(port),
+            ..default()
+        }
+    }
+
+    /// Listen on `address` instead of localhost, e.g. `0.0.0.0` to control an app in a
+    /// container or on another machine
+    ///
+    /// **Security:** without [`with_token`](Self::with_token), anyone who can reach the
+    /// address can read and modify every reflected component and resource, spawn and
+    /// destroy entities, take screenshots and shut the app down. Set a token whenever you
+    /// bind to a non-loopback address. Traffic is never encrypted, so keep to a trusted
+    /// network, a firewall or an SSH tunnel, and never do this in a build you ship to
+    /// players.
+    ///
+    /// ```no_run
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use bevy::prelude::*;
+    /// use bevy_brp_tool::BrpToolPlugin;
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(BrpToolPlugin::default().with_bind_address(Ipv4Addr::UNSPECIFIED))
+    ///     .run();
+    /// ```
+    pub fn with_bind_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.bind_address = Some(address.into());
+        self
+    }
+
+    /// Reject requests that lack an `Authorization: Bearer <token>` header with 401
+    ///
+    /// Bevy's HTTP server can't check headers itself, so with a token it moves to a private
+    /// port on `127.0.0.1` and a small proxy on the configured address and port checks each
+    /// request before passing it on. The private port is still reachable from the same
+    /// machine, so the token guards network access rather than local processes. If the
+    /// proxy can't listen, an error is logged and remote control stays off.
+    ///
+    /// The token travels in plain text over HTTP: pair it with a trusted network, an SSH
+    /// tunnel or a TLS-terminating proxy. Clients pass it with `brp --token <TOKEN>` or the
+    /// `BRP_TOKEN` environment variable.
+    ///
+    /// ```no_run
+    /// use std::net::Ipv4Addr;
+    ///
+    /// use bevy::prelude::*;
+    /// use bevy_brp_tool::BrpToolPlugin;
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(
+    ///         BrpToolPlugin::default()
+    ///             .with_bind_address(Ipv4Addr::UNSPECIFIED)
+    ///             .with_token(std::env::var("BRP_TOKEN").expect("BRP_TOKEN is not set")),
+    ///     )
+    ///     .run();
+    /// ```
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Add a custom BRP method, callable by `name` like any built-in method
+    ///
+    /// Namespace the name after your crate (e.g. `my_game/debug_info`) so it can't clash
+    /// with `bevy/` or `brp_tool/` methods. Call it from the CLI with `brp raw <name>`.
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_brp_tool::BrpToolPlugin;
+    /// use serde_json::json;
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(
+    ///         BrpToolPlugin::default().with_method("my_game/entity_count", |_params, world| {
+    ///             Ok(json!({ "count": world.entities().len() }))
+    ///         }),
+    ///     )
+    ///     .run();
+    /// ```
+    pub fn with_method(
+        mut self,
+        name: impl Into<String>,
+        handler: impl Fn(Option<Value>, &mut World) -> BrpResult + Send + Sync + 'static,
+    ) -> Self {
+        self.methods.push((name.into(), Arc::new(handler)));
+        self
     }
 }
 
@@ -32,33 +158,141 @@ impl Plugin for BrpToolPlugin {
         // for screenshots). For now, we'll just add our custom methods.
 
         // Add Bevy's remote plugins with our custom methods
-        let remote_plugin = RemotePlugin::default()
+        let mut remote_plugin = RemotePlugin::default()
             .with_method(
                 format!("{}screenshot", BRP_TOOL_COMMAND_PREFIX),
                 screenshot_handler,
             )
+            .with_method(
+                format!("{}screenshot_data", BRP_TOOL_COMMAND_PREFIX),
+                screenshot_data_handler,
+            )
             .with_method(
                 format!("{}shutdown", BRP_TOOL_COMMAND_PREFIX),
                 shutdown_handler,
             );
+        for (name, handler) in &self.methods {
+            let handler = Arc::clone(handler);
+            remote_plugin = remote_plugin.with_method(
+                name.clone(),
+                move |In(params): In<Option<Value>>, world: &mut World| handler(params, world),
+            );
+        }
 
-        let http_plugin = if let Some(port) = self.port {
-            RemoteHttpPlugin::default().with_port(port)
-        } else {
-            RemoteHttpPlugin::default()
+        let address = self.bind_address.unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let auto_port = self.port == Some(0);
+        let port = self.port.unwrap_or(DEFAULT_REMOTE_PORT);
+        let listening = match &self.token {
+            Some(token) => start_auth_proxy(app, address, port, token),
+            None => match port {
+                0 => reserve_free_port(address).map(|(port, reservation)| {
+                    app.insert_resource(reservation);
+                    port
+                }),
+                port => Ok(port),
+            }
+            .map(|port| {
+                let http_plugin = RemoteHttpPlugin::default()
+                    .with_address(address)
+                    .with_port(port);
+                (port, http_plugin)
+            }),
+        };
+        let (port, http_plugin) = match listening {
+            Ok(listening) => listening,
+            Err(error) => {
+                // Never fall back to an unguarded server when the token proxy can't start
+                error!(
+                    "Remote control disabled: failed to listen on {}:{}: {}",
+                    address, port, error
+                );
+                return;
+            }
         };
+        let token_required = self.token.is_some();
 
-        app.add_plugins((remote_plugin, http_plugin));
+        app.add_plugins((remote_plugin, http_plugin))
+            .init_resource::<InlineScreenshots>()
+            .insert_resource(BrpBoundPort(port))
+            .add_systems(PreStartup, release_port_reservation);
 
-        let port = self.port.unwrap_or(DEFAULT_REMOTE_PORT);
+        let custom_methods: Vec<String> =
+            self.methods.iter().map(|(name, _)| name.clone()).collect();
         app.add_systems(Startup, move |_world: &mut World| {
-            setup_remote_methods(port);
+            if auto_port {
+                // A launcher reads this line to learn where to connect
+                println!("{}{}", BOUND_PORT_ANNOUNCEMENT, port);
+            }
+            setup_remote_methods(address, port, token_required, &custom_methods);
         });
     }
 }
 
-fn setup_remote_methods(port: u16) {
-    info!("Remote control enabled on http://localhost:{}", port);
+/// Listen on `address` and `port` through the token-checking proxy, moving Bevy's server to a
+/// private loopback port behind it
+fn start_auth_proxy(
+    app: &mut App,
+    address: IpAddr,
+    port: u16,
+    token: &str,
+) -> io::Result<(u16, RemoteHttpPlugin)> {
+    let listener = TcpListener::bind((address, port))?;
+    let port = listener.local_addr()?.port();
+    let (upstream_port, reservation) = reserve_free_port(IpAddr::V4(Ipv4Addr::LOCALHOST))?;
+    spawn_auth_proxy(listener, upstream_port, token.to_string())?;
+    app.insert_resource(reservation);
+    Ok((port, RemoteHttpPlugin::default().with_port(upstream_port)))
+}
+
+/// A free port held open by [`BrpToolPlugin`] until Bevy's HTTP server is about to bind it
+#[derive(Resource)]
+struct PortReservation {
+    _listener: TcpListener,
+}
+
+/// Ask the OS for a free port on the interface the HTTP server listens on
+///
+/// Bevy's HTTP server binds by port number from a task started in `Startup`, so it can't
+/// take over a listener. The port stays bound by the returned reservation until
+/// `release_port_reservation` drops it in `PreStartup`, right before the server starts.
+/// Another process could still take the port in that brief gap, in which case Bevy's
+/// server fails to start and clients can't connect.
+fn reserve_free_port(address: IpAddr) -> io::Result<(u16, PortReservation)> {
+    let listener = TcpListener::bind((address, 0))?;
+    let port = listener.local_addr()?.port();
+    Ok((
+        port,
+        PortReservation {
+            _listener: listener,
+        },
+    ))
+}
+
+/// Free the port held by [`PortReservation`] just before Bevy's HTTP server binds it
+fn release_port_reservation(mut commands: Commands) {
+    commands.remove_resource::<PortReservation>();
+}
+
+fn setup_remote_methods(
+    address: IpAddr,
+    port: u16,
+    token_required: bool,
+    custom_methods: &[String],
+) {
+    if token_required {
+        info!(
+            "Remote control enabled on http://{}:{} (bearer token required)",
+            address, port
+        );
+    } else if address.is_loopback() {
+        info!("Remote control enabled on http://localhost:{}", port);
+    } else {
+        warn!(
+            "Remote control enabled on http://{}:{} without a token, so anyone who can reach \
+             this address can control the app - see BrpToolPlugin::with_token",
+            address, port
+        );
+    }
     trace!("Available endpoints:");
     trace!("  - rpc.discover - Discover all available methods");
     trace!("  - bevy/query - Query entities and components");
@@ -69,7 +303,11 @@ fn setup_remote_methods(port: u16) {
     trace!("  - bevy/insert - Insert components");
     trace!("  - bevy/remove - Remove components");
     trace!("  - brp_tool/screenshot - Take a screenshot");
+    trace!("  - brp_tool/screenshot_data - Collect an inline screenshot");
     trace!("  - brp_tool/shutdown - Shutdown the app");
+    for name in custom_methods {
+        trace!("  - {} - Custom method", name);
+    }
 }
 
 /// Handler for shutdown
@@ -83,37 +321,225 @@ fn shutdown_handler(In(_): In<Option<Value>>, world: &mut World) -> BrpResult {
     }))
 }
 
+/// Default JPEG quality when `format` is `jpg` and no `quality` is given
+const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+/// Sub-rectangle of the window to capture, in physical pixels
+#[derive(Clone, Copy)]
+struct ScreenshotRegion {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+/// Encoding requested for a screenshot
+#[derive(Clone, Copy)]
+enum ScreenshotFormat {
+    Png,
+    Jpeg { quality: u8 },
+}
+
+fn invalid_params(message: impl Into<String>) -> BrpError {
+    BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: message.into(),
+        data: None,
+    }
+}
+
+/// Parse the optional `region` parameter as `[x, y, width, height]`
+fn parse_region(params: Option<&Value>) -> Result<Option<ScreenshotRegion>, BrpError> {
+    let Some(region) = params.and_then(|v| v.get("region")) else {
+        return Ok(None);
+    };
+
+    let values: Option<Vec<u32>> = region.as_array().and_then(|items| {
+        items
+            .iter()
+            .map(|v| v.as_u64().and_then(|n| u32::try_from(n).ok()))
+            .collect()
+    });
+
+    match values.as_deref() {
+        Some(&[x, y, width, height]) => Ok(Some(ScreenshotRegion {
+            x,
+            y,
+            width,
+            height,
+        })),
+        _ => Err(invalid_params(
+            "'region' must be an array of four non-negative integers: [x, y, width, height]",
+        )),
+    }
+}
+
+/// Parse the optional `format` and `quality` parameters
+fn parse_format(params: Option<&Value>) -> Result<Option<ScreenshotFormat>, BrpError> {
+    let format = params
+        .and_then(|v| v.get("format"))
+        .and_then(|v| v.as_str());
+    let quality = params.and_then(|v| v.get("quality"));
+
+    match (format, quality) {
+        (None, None) => Ok(None),
+        (Some("png"), None) => Ok(Some(ScreenshotFormat::Png)),
+        (Some("png"), Some(_)) | (None, Some(_)) => Err(invalid_params(
+            "'quality' is only supported with format 'jpg'",
+        )),
+        (Some("jpg" | "jpeg"), quality) => {
+            let quality = match quality {
+                None => DEFAULT_JPEG_QUALITY,
+                Some(q) => q
+                    .as_u64()
+                    .filter(|q| (1..=100).contains(q))
+                    .map(|q| q as u8)
+                    .ok_or_else(|| invalid_params("'quality' must be between 1 and 100"))?,
+            };
+            Ok(Some(ScreenshotFormat::Jpeg { quality }))
+        }
+        (Some(other), _) => Err(invalid_params(format!(
+            "Unsupported screenshot format '{}': expected 'png' or 'jpg'",
+            other
+        ))),
+    }
+}
+
+/// Check that a region lies within the primary window
+fn validate_region(world: &mut World, region: ScreenshotRegion) -> Result<(), BrpError> {
+    let (window_width, window_height) = world
+        .query_filtered::<&Window, With<PrimaryWindow>>()
+        .iter(world)
+        .next()
+        .map(|w| {
+            (
+                w.resolution.physical_width(),
+                w.resolution.physical_height(),
+            )
+        })
+        .ok_or_else(|| invalid_params("Region capture requires a primary window"))?;
+
+    let fits = region.width > 0
+        && region.height > 0
+        && u64::from(region.x) + u64::from(region.width) <= u64::from(window_width)
+        && u64::from(region.y) + u64::from(region.height) <= u64::from(window_height);
+
+    if !fits {
+        return Err(invalid_params(format!(
+            "Region [{}, {}, {}, {}] is outside the {}x{} window",
+            region.x, region.y, region.width, region.height, window_width, window_height
+        )));
+    }
+
+    Ok(())
+}
+
+/// Crop a captured image to the requested region, if any
+fn crop_to_region(image: DynamicImage, region: Option<ScreenshotRegion>) -> DynamicImage {
+    match region {
+        Some(r) => image.crop_imm(r.x, r.y, r.width, r.height),
+        None => image,
+    }
+}
+
+/// Encode an image in an explicit format
+fn encode_image(
+    image: &DynamicImage,
+    format: ScreenshotFormat,
+    writer: &mut (impl Write + Seek),
+) -> ImageResult<()> {
+    match format {
+        ScreenshotFormat::Png => image.write_to(writer, ImageFormat::Png),
+        // JPEG has no alpha channel
+        ScreenshotFormat::Jpeg { quality } => DynamicImage::ImageRgb8(image.to_rgb8())
+            .write_with_encoder(JpegEncoder::new_with_quality(writer, quality)),
+    }
+}
+
+/// Write an image to `path`
+fn save_screenshot(
+    image: &DynamicImage,
+    path: &str,
+    format: Option<ScreenshotFormat>,
+) -> Result<(), String> {
+    std::fs::create_dir_all(
+        std::path::Path::new(path)
+            .parent()
+            .unwrap_or(std::path::Path::new(".")),
+    )
+    .map_err(|e| format!("Failed to create directory for screenshot {}: {}", path, e))?;
+
+    let result = match format {
+        // Without an explicit format the file extension decides
+        None => image.save(path),
+        Some(format) => {
+            let file = std::fs::File::create(path)
+                .map_err(|e| format!("Failed to create {}: {}", path, e))?;
+            encode_image(image, format, &mut std::io::BufWriter::new(file))
+        }
+    };
+
+    result.map_err(|e| format!("Failed to save screenshot to {}: {}", path, e))
+}
+
+/// Screenshots captured for `inline` requests, waiting to be collected through
+/// `brp_tool/screenshot_data`
+///
+/// A capture is `None` until the frame has been captured and encoded.
+#[derive(Resource, Default)]
+struct InlineScreenshots {
+    next_id: u64,
+    captures: HashMap<u64, Option<Result<Vec<u8>, String>>>,
+}
+
 /// Handler for taking screenshots
 fn screenshot_handler(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
-    // Get the path from params
+    let inline = params
+        .as_ref()
+        .and_then(|v| v.get("inline"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    // Get the path from params - optional when the image is returned inline
     let path = params
         .as_ref()
         .and_then(|v| v.get("path"))
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| BrpError {
-            code: error_codes::INVALID_PARAMS,
-            message: "Missing 'path' parameter".to_string(),
-            data: None,
-        })?;
+        .and_then(|v| v.as_str());
+    if path.is_none() && !inline {
+        return Err(invalid_params("Missing 'path' parameter"));
+    }
+
+    let region = parse_region(params.as_ref())?;
+    let format = parse_format(params.as_ref())?;
+    if let Some(region) = region {
+        validate_region(world, region)?;
+    }
 
     // Convert to absolute path
-    let path_buf = std::path::Path::new(path);
-    let absolute_path = if path_buf.is_absolute() {
-        path_buf.to_path_buf()
-    } else {
-        std::env::current_dir()
-            .map_err(|e| BrpError {
-                code: error_codes::INTERNAL_ERROR,
-                message: format!("Failed to get current directory: {}", e),
-                data: None,
-            })?
-            .join(path_buf)
+    let absolute_path_str = match path {
+        Some(path) => {
+            let path_buf = std::path::Path::new(path);
+            let absolute_path = if path_buf.is_absolute() {
+                path_buf.to_path_buf()
+            } else {
+                std::env::current_dir()
+                    .map_err(|e| BrpError {
+                        code: error_codes::INTERNAL_ERROR,
+                        message: format!("Failed to get current directory: {}", e),
+                        data: None,
+                    })?
+                    .join(path_buf)
+            };
+            Some(absolute_path.to_string_lossy().to_string())
+        }
+        None => None,
     };
 
-    let absolute_path_str = absolute_path.to_string_lossy().to_string();
-
     // Log the full path before attempting screenshot
-    info!("Screenshot requested for: {}", absolute_path_str);
+    info!(
+        "Screenshot requested for: {}",
+        absolute_path_str.as_deref().unwrap_or("inline")
+    );
 
     // Check if we have a primary window
     let window_exists = world.query::<&Window>().iter(world).any(|w| {
@@ -128,51 +554,114 @@ fn screenshot_handler(In(params): In<Option<Value>>, world: &mut World) -> BrpRe
         warn!("No windows found in the world!");
     }
 
+    // Reserve a slot for the encoded bytes when returning the image inline
+    let capture_id = inline.then(|| {
+        let mut inline_screenshots = world.resource_mut::<InlineScreenshots>();
+        let id = inline_screenshots.next_id;
+        inline_screenshots.next_id += 1;
+        inline_screenshots.captures.insert(id, None);
+        id
+    });
+
     // Spawn a screenshot entity with a custom observer for debugging
     let path_for_observer = absolute_path_str.clone();
     let entity = world
         .spawn((
             Screenshot::primary_window(),
-            Name::new(format!("Screenshot_{}", absolute_path_str)),
+            Name::new(format!(
+                "Screenshot_{}",
+                absolute_path_str.as_deref().unwrap_or("inline")
+            )),
         ))
-        .observe(move |trigger: Trigger<ScreenshotCaptured>| {
-            info!(
-                "Screenshot captured! Attempting to save to: {}",
-                path_for_observer
-            );
-            let img = trigger.event().0.clone();
-            match img.try_into_dynamic() {
-                Ok(dyn_img) => {
-                    match std::fs::create_dir_all(
-                        std::path::Path::new(&path_for_observer)
-                            .parent()
-                            .unwrap_or(std::path::Path::new(".")),
-                    ) {
-                        Ok(_) => match dyn_img.save(&path_for_observer) {
-                            Ok(_) => {
-                                info!("Screenshot successfully saved to: {}", path_for_observer)
-                            }
-                            Err(e) => {
-                                error!("Failed to save screenshot to {}: {}", path_for_observer, e)
-                            }
-                        },
-                        Err(e) => error!(
-                            "Failed to create directory for screenshot {}: {}",
-                            path_for_observer, e
-                        ),
+        .observe(
+            move |trigger: Trigger<ScreenshotCaptured>,
+                  mut inline_screenshots: ResMut<InlineScreenshots>| {
+                info!("Screenshot captured!");
+                let img = trigger.event().0.clone();
+                let dyn_img = match img.try_into_dynamic() {
+                    Ok(dyn_img) => crop_to_region(dyn_img, region),
+                    Err(e) => {
+                        let message =
+                            format!("Failed to convert screenshot to dynamic image: {}", e);
+                        error!("{}", message);
+                        if let Some(id) = capture_id {
+                            inline_screenshots.captures.insert(id, Some(Err(message)));
+                        }
+                        return;
+                    }
+                };
+
+                if let Some(path) = &path_for_observer {
+                    match save_screenshot(&dyn_img, path, format) {
+                        Ok(()) => info!("Screenshot successfully saved to: {}", path),
+                        Err(e) => error!("{}", e),
                     }
                 }
-                Err(e) => error!("Failed to convert screenshot to dynamic image: {}", e),
-            }
-        })
+
+                if let Some(id) = capture_id {
+                    let mut bytes = Cursor::new(Vec::new());
+                    let encoded = encode_image(
+                        &dyn_img,
+                        format.unwrap_or(ScreenshotFormat::Png),
+                        &mut bytes,
+                    )
+                    .map(|()| bytes.into_inner())
+                    .map_err(|e| format!("Failed to encode screenshot: {}", e));
+                    inline_screenshots.captures.insert(id, Some(encoded));
+                }
+            },
+        )
         .id();
 
     info!("Screenshot entity spawned with ID: {:?}", entity);
 
-    Ok(json!({
+    let mut response = json!({
         "success": true,
-        "path": absolute_path_str,
         "working_directory": std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("unknown")).to_string_lossy(),
         "note": "Screenshot capture initiated. The file will be saved asynchronously."
-    }))
+    });
+    if let Some(path) = absolute_path_str {
+        response["path"] = json!(path);
+    }
+    if let Some(id) = capture_id {
+        response["capture_id"] = json!(id);
+        response["format"] = json!(match format {
+            Some(ScreenshotFormat::Jpeg { .. }) => "jpg",
+            _ => "png",
+        });
+    }
+
+    Ok(response)
+}
+
+/// Handler for collecting the bytes of an inline screenshot
+fn screenshot_data_handler(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let capture_id = params
+        .as_ref()
+        .and_then(|v| v.get("capture_id"))
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| invalid_params("Missing 'capture_id' parameter"))?;
+
+    let mut inline_screenshots = world.resource_mut::<InlineScreenshots>();
+    match inline_screenshots.captures.remove(&capture_id) {
+        None => Err(invalid_params(format!(
+            "Unknown screenshot capture_id {}",
+            capture_id
+        ))),
+        Some(None) => {
+            // Not captured yet - keep the slot for the next poll
+            inline_screenshots.captures.insert(capture_id, None);
+            Ok(json!({ "ready": false }))
+        }
+        Some(Some(Ok(bytes))) => Ok(json!({
+            "ready": true,
+            "size": bytes.len(),
+            "data": BASE64_STANDARD.encode(&bytes),
+        })),
+        Some(Some(Err(message))) => Err(BrpError {
+            code: error_codes::INTERNAL_ERROR,
+            message,
+            data: None,
+        }),
+    }
 }
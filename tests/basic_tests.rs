@@ -1,7 +1,13 @@
 //! Basic connectivity and discovery tests for bevy_brp_tool
 
 mod support;
+use std::ffi::OsString;
+
 use anyhow::Result;
+use bevy_brp_tool::cli::commands::{Cli, Commands};
+use bevy_brp_tool::cli::support::OutputFormat;
+use clap::FromArgMatches;
+use serde_json::json;
 use support::*;
 
 #[tokio::test]
@@ -21,6 +27,30 @@ async fn test_server_ready() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_health_summary() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner.run_command_with_app(&["health"], &app).await?;
+
+    // Verify
+    assert!(output.success(), "Health command should succeed");
+    let json = output.parse_json()?;
+    assert_eq!(json["responsive"], true);
+    assert!(json["method_count"].as_u64().unwrap_or(0) > 0);
+    assert!(
+        json["entity_count"].as_u64().unwrap_or(0) >= 4,
+        "Should count the entities from setup"
+    );
+    assert!(json["resource_count"].as_u64().unwrap_or(0) >= 1);
+    assert_eq!(json["errors"], serde_json::json!({}));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_methods_discovery() -> Result<()> {
     // Setup
@@ -59,6 +89,7 @@ async fn test_methods_discovery() -> Result<()> {
 
     // BRP Tool methods
     assert!(method_names.contains(&"brp_tool/screenshot"));
+    assert!(method_names.contains(&"brp_tool/screenshot_data"));
     assert!(method_names.contains(&"brp_tool/shutdown"));
 
     Ok(())
@@ -115,6 +146,47 @@ async fn test_cli_list_commands() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_list_commands_json() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner.run_command(&["--list-commands", "--json"]).await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "--list-commands --json failed: {}",
+        output.stderr
+    );
+    let catalog = output.parse_json()?;
+    let commands = catalog["commands"]
+        .as_array()
+        .expect("Expected a commands array");
+    let destroy = commands
+        .iter()
+        .find(|command| command["name"] == "destroy")
+        .expect("destroy should be in the catalog");
+    assert_eq!(destroy["brp_method"], "bevy/destroy");
+    assert_eq!(destroy["category"], "entity");
+    assert_eq!(destroy["arguments"][0]["name"], "<ENTITY_ID>");
+    assert!(
+        destroy["options"]
+            .as_array()
+            .expect("Expected an options array")
+            .iter()
+            .any(|option| option["name"] == "--from-stdin")
+    );
+    let ready = commands
+        .iter()
+        .find(|command| command["name"] == "ready")
+        .expect("ready should be in the catalog");
+    assert!(ready["brp_method"].is_null());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_help_for_specific_command() -> Result<()> {
     // Setup
@@ -130,6 +202,79 @@ async fn test_cli_help_for_specific_command() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_subcommand_help_matches_help_for() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let long_help = runner.run_command(&["spawn", "--help"]).await?;
+    let short_help = runner.run_command(&["spawn", "-h"]).await?;
+
+    // Verify - `--help` carries the examples from the help file, `-h` stays terse
+    assert!(long_help.success());
+    assert!(long_help.stdout_contains("EXAMPLES:"));
+    assert!(long_help.stdout_contains("spawn --with-name Player"));
+    assert!(short_help.success());
+    assert!(!short_help.stdout_contains("EXAMPLES:"));
+
+    Ok(())
+}
+
+#[test]
+fn test_every_subcommand_has_detailed_help() {
+    let command = Cli::command_with_detailed_help();
+    for subcommand in command.get_subcommands() {
+        assert!(
+            subcommand.get_after_long_help().is_some(),
+            "{} --help is missing its detailed help",
+            subcommand.get_name()
+        );
+    }
+}
+
+#[test]
+fn test_mutate_component_from_stdin_drops_entity_argument() {
+    let args: Vec<OsString> = [
+        "brp",
+        "mutate_component",
+        "--from-stdin",
+        "bevy_core::name::Name",
+        r#""Renamed""#,
+    ]
+    .into_iter()
+    .map(Into::into)
+    .collect();
+    let matches = Cli::command_for_args(&args)
+        .try_get_matches_from(&args)
+        .expect("mutate_component --from-stdin should parse without an entity");
+    let cli = Cli::from_arg_matches(&matches).expect("matches should convert");
+
+    assert_eq!(
+        cli.command,
+        Some(Commands::MutateComponent {
+            entity: None,
+            component: "bevy_core::name::Name".to_string(),
+            patch: r#""Renamed""#.to_string(),
+            from_stdin: true,
+        })
+    );
+}
+
+#[test]
+fn test_format_is_accepted_after_the_command_name() {
+    let args: Vec<OsString> = ["brp", "methods", "--format", "table"]
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let matches = Cli::command_for_args(&args)
+        .try_get_matches_from(&args)
+        .expect("--format should parse after the command name");
+    let cli = Cli::from_arg_matches(&matches).expect("matches should convert");
+
+    assert_eq!(cli.output_format, OutputFormat::Table);
+}
+
 #[tokio::test]
 async fn test_cli_ready_command_without_app() -> Result<()> {
     // Setup
@@ -150,6 +295,52 @@ async fn test_cli_ready_command_without_app() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_validate_json_without_app() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+    let port = allocate_test_port().to_string();
+
+    // Execute - nothing is listening on the port, validation must not need it
+    let valid = runner
+        .run_command(&[
+            "--port",
+            &port,
+            "validate_json",
+            "spawn",
+            r#"{"bevy_ecs::name::Name": "Player"}"#,
+        ])
+        .await?;
+    let invalid = runner
+        .run_command(&[
+            "--port",
+            &port,
+            "validate_json",
+            "insert",
+            r#"{"Transform": {}}"#,
+        ])
+        .await?;
+
+    // Verify
+    assert!(
+        valid.success(),
+        "valid payload should pass: {}",
+        valid.stderr
+    );
+    assert_eq!(
+        valid.parse_json()?["types"],
+        json!(["bevy_ecs::name::Name"])
+    );
+    assert!(!invalid.success(), "short type names should be rejected");
+    assert!(
+        invalid.stderr_contains("not a fully qualified type path"),
+        "Should explain why: {}",
+        invalid.stderr
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_ready_command_with_app() -> Result<()> {
     // Setup
@@ -241,3 +432,436 @@ async fn test_cli_workflows() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_commands_list_standalone() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - a command list against the running app, including a wait
+    let output = runner
+        .run_command_with_app(&["--commands", "ready,wait:0,list"], &app)
+        .await?;
+
+    // Verify
+    assert!(output.success(), "Command list should succeed");
+    assert!(output.stdout_contains("\"ready\": true"));
+    assert!(output.stdout_contains(&test_component_type()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_commands_list_emit_command_echo() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "--compact",
+                "--emit-command-echo",
+                "--commands",
+                "ready, list",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - each result is labeled with the command that produced it
+    assert!(
+        output.success(),
+        "Command list should succeed: {}",
+        output.stderr
+    );
+    let results: Vec<serde_json::Value> = output
+        .stdout
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["command"], "ready");
+    assert_eq!(results[0]["result"]["ready"], true);
+    assert_eq!(results[1]["command"], "list");
+    assert!(results[1]["result"].is_array());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_commands_list_reports_failing_position() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - second command is invalid
+    let output = runner
+        .run_command_with_app(&["--commands", "ready,destroy not_a_number,list"], &app)
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "Command list should fail");
+    assert!(
+        output.stderr_contains("Command 2 of 3"),
+        "Should report the failing command position: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_commands_list_continue_on_error() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - second command fails but the third should still run
+    let output = runner
+        .run_command_with_app(
+            &[
+                "--continue-on-error",
+                "--commands",
+                "ready,destroy not_a_number,list",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        !output.success(),
+        "Command list should still exit non-zero when a command fails"
+    );
+    assert!(
+        output.stdout_contains(&test_component_type()),
+        "Commands after the failure should still run"
+    );
+    assert!(
+        output.stderr_contains("1 of 3 commands failed"),
+        "Should summarize failures: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_no_ready_check() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - the flag is global, so it may follow the subcommand
+    let output = runner
+        .run_command_with_app(&["list", "--no-ready-check"], &app)
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "list with --no-ready-check should succeed"
+    );
+    assert!(output.stdout_contains(&test_component_type()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_compact_output() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let compact = runner
+        .run_command_with_app(&["list", "--compact"], &app)
+        .await?;
+    let pretty = runner
+        .run_command_with_app(&["list", "--pretty"], &app)
+        .await?;
+
+    // Verify - same data, but compact output is a single line
+    assert!(compact.success(), "list with --compact should succeed");
+    assert!(pretty.success(), "list with --pretty should succeed");
+    assert_eq!(compact.stdout.trim().lines().count(), 1);
+    assert!(pretty.stdout.trim().lines().count() > 1);
+    assert_eq!(compact.parse_json()?, pretty.parse_json()?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_app_args_requires_launched_app() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - standalone mode doesn't launch an app to pass arguments to
+    let output = runner
+        .run_command(&["--app-args", "--level 3", "ready"])
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "--app-args without -d or -m should fail");
+    assert!(
+        output.stderr_contains("--app-args can only be used with"),
+        "Should explain where --app-args applies: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_managed_timeout_requires_managed_commands() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - there is no managed session for the timeout to bound
+    let output = runner
+        .run_command(&["--managed-timeout", "10", "ready"])
+        .await?;
+
+    // Verify
+    assert!(
+        !output.success(),
+        "--managed-timeout without -m should fail"
+    );
+    assert!(
+        output.stderr_contains("--managed-timeout can only be used with"),
+        "Should explain where --managed-timeout applies: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_poll_interval_rejects_zero() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - a zero interval would poll in a busy loop
+    let output = runner
+        .run_command(&["--poll-interval", "0", "ready"])
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "--poll-interval 0 should fail");
+    assert!(
+        output.stderr_contains("--poll-interval"),
+        "Should name the rejected option: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_max_concurrency_rejects_zero() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - no requests could ever be sent
+    let output = runner
+        .run_command(&["--max-concurrency", "0", "list_entities"])
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "--max-concurrency 0 should fail");
+    assert!(
+        output.stderr_contains("--max-concurrency"),
+        "Should name the rejected option: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_remote_host_rejects_launched_app() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - launched apps always run on localhost
+    let output = runner
+        .run_command(&["--host", "192.0.2.1", "-m", "ready"])
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "--host with -m should fail");
+    assert!(
+        output.stderr_contains("cannot be used with --detached/-d or --managed-commands/-m"),
+        "Should explain that remote hosts can't be launched: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_env_rejects_malformed_entry() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command(&["--env", "RUST_LOG", "-m", "ready"])
+        .await?;
+
+    // Verify - rejected before any app is launched
+    assert!(!output.success(), "--env without '=' should fail");
+    assert!(
+        output.stderr_contains("expected KEY=VALUE"),
+        "Should explain the expected format: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_interactive_rejects_managed_json() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - the prompt needs stdout, which --managed-json reserves for the results
+    let output = runner
+        .run_command(&["--interactive", "--managed-json", "-m", "ready"])
+        .await?;
+
+    // Verify - rejected before any app is launched
+    assert!(
+        !output.success(),
+        "--interactive with --managed-json should fail"
+    );
+    assert!(
+        output.stderr_contains("--managed-json"),
+        "Should name the conflicting option: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_methods_grep_and_group() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - filter only
+    let output = runner
+        .run_command_with_app(&["methods", "--grep", "watch"], &app)
+        .await?;
+    assert!(output.success(), "methods --grep should succeed");
+    let response = output.parse_json()?;
+    let names: Vec<&str> = response["methods"]
+        .as_array()
+        .expect("Expected methods array")
+        .iter()
+        .filter_map(|m| m["name"].as_str())
+        .collect();
+    assert!(!names.is_empty(), "Should find watch methods");
+    assert!(names.iter().all(|name| name.contains("watch")));
+
+    // Execute - group by namespace
+    let output = runner
+        .run_command_with_app(&["methods", "--group"], &app)
+        .await?;
+    assert!(output.success(), "methods --group should succeed");
+    let response = output.parse_json()?;
+    let groups = response["methods"]
+        .as_object()
+        .expect("Expected methods grouped into an object");
+    assert!(groups.contains_key("bevy"));
+    assert!(groups.contains_key("brp_tool"));
+    assert!(
+        groups["bevy"]
+            .as_array()
+            .expect("Expected array of bevy methods")
+            .iter()
+            .all(|m| m["name"].as_str().unwrap_or_default().starts_with("bevy"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_methods_format_table() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["--format", "table", "methods", "--grep", "watch"], &app)
+        .await?;
+
+    // Verify - a header, namespace headings and one row per matching method
+    assert!(
+        output.success(),
+        "methods --format table failed: {}",
+        output.stderr
+    );
+    let lines: Vec<&str> = output.stdout.lines().collect();
+    assert!(lines[0].trim_start().starts_with("METHOD"));
+    assert!(lines.contains(&"bevy:"));
+    assert!(
+        lines
+            .iter()
+            .any(|line| line.trim_start().starts_with("bevy/get+watch"))
+    );
+    assert!(
+        lines
+            .iter()
+            .filter(|line| line.starts_with("  ") && !line.contains("METHOD"))
+            .all(|line| line.contains("watch"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_assert_rejects_command_lists() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+
+    // Execute - a list has no single result for the assertion to check
+    let output = runner
+        .run_command(&["--commands", "ready", "--assert", "/ready == true"])
+        .await?;
+
+    // Verify - rejected before any command runs
+    assert!(!output.success(), "--assert with --commands should fail");
+    assert!(
+        output.stderr_contains("--assert cannot be used with --commands"),
+        "Should name the conflicting option: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_token_proxy_bind_failure_skips_remote_control() -> Result<()> {
+    use bevy::prelude::*;
+    use bevy_brp_tool::{BrpBoundPort, BrpToolPlugin};
+
+    // Setup - hold the port the proxy wants
+    let taken = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = taken.local_addr()?.port();
+
+    // Execute
+    let mut app = App::new();
+    app.add_plugins(BrpToolPlugin::with_port(port).with_token("s3cret"));
+
+    // Verify - the plugin logs and leaves remote control off rather than panicking
+    assert!(app.world().get_resource::<BrpBoundPort>().is_none());
+
+    Ok(())
+}
@@ -323,3 +323,34 @@ async fn test_specific_bug_scenario_simulation() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `--detect --json` prints every field whether or not an app is found
+#[tokio::test]
+async fn test_detect_json_output() -> Result<()> {
+    let runner = CliTestRunner::new()?;
+
+    let output = runner.run_command(&["--detect", "--json"]).await?;
+    assert!(output.success(), "stderr: {}", output.stderr);
+    let detected = output.parse_json()?;
+    for field in [
+        "app_name",
+        "binary_path",
+        "manifest_dir",
+        "target_dir",
+        "built",
+    ] {
+        assert!(
+            detected.get(field).is_some(),
+            "--detect --json should include '{}': {}",
+            field,
+            detected
+        );
+    }
+    assert_eq!(detected["built"], detected["binary_path"].is_string());
+
+    // --json on its own has nothing to format
+    let output = runner.run_command(&["--json"]).await?;
+    assert!(!output.success(), "--json without --detect should fail");
+
+    Ok(())
+}
@@ -2,78 +2,379 @@
 
 use anyhow::Result;
 use bevy_brp_tool::cli::commands::{Commands, format_command, parse_command_string};
+use bevy_brp_tool::cli::support::parse_assertion;
 
 /// Test that all commands can be formatted and then parsed back to the same value
 #[test]
 fn test_round_trip_consistency() -> Result<()> {
     let test_commands = vec![
-        Commands::Destroy { entity: 12345 },
+        Commands::Ancestors { entity: 12345 },
+        Commands::Subtree { entity: 12345 },
+        Commands::Snapshot { baseline: None },
+        Commands::Snapshot {
+            baseline: Some("before.json".to_string()),
+        },
+        Commands::Bench {
+            method:     None,
+            iterations: None,
+        },
+        Commands::Bench {
+            method:     Some("bevy/query".to_string()),
+            iterations: Some(50),
+        },
+        Commands::Capabilities,
+        Commands::Clone {
+            entity:   12345,
+            count:    Some(3),
+            ids_only: false,
+        },
+        Commands::Clone {
+            entity:   12345,
+            count:    None,
+            ids_only: true,
+        },
+        // Parsed, since the config action enum isn't exported
+        parse_command_string("config path")?,
+        Commands::Destroy {
+            entity:     Some(12345),
+            from_stdin: false,
+            recursive:  false,
+        },
+        Commands::Destroy {
+            entity:     None,
+            from_stdin: true,
+            recursive:  false,
+        },
+        Commands::Destroy {
+            entity:     Some(12345),
+            from_stdin: false,
+            recursive:  true,
+        },
+        Commands::Exists { entity: 12345 },
+        Commands::Get {
+            entity:         Some(12345),
+            components:     vec!["bevy_transform::components::transform::Transform".to_string()],
+            from_stdin:     false,
+            retry_on_empty: false,
+            timeout:        None,
+        },
+        Commands::Get {
+            entity:         Some(12345),
+            components:     vec![
+                "bevy_transform::components::transform::Transform".to_string(),
+                "bevy_core::name::Name".to_string(),
+            ],
+            from_stdin:     false,
+            retry_on_empty: false,
+            timeout:        None,
+        },
+        Commands::Get {
+            entity:         None,
+            components:     vec!["bevy_transform::components::transform::Transform".to_string()],
+            from_stdin:     true,
+            retry_on_empty: false,
+            timeout:        None,
+        },
         Commands::Get {
-            entity:    12345,
-            component: "bevy_transform::components::transform::Transform".to_string(),
+            entity:         Some(12345),
+            components:     vec!["bevy_core::name::Name".to_string()],
+            from_stdin:     false,
+            retry_on_empty: true,
+            timeout:        Some(5),
         },
         Commands::GetResource {
             resource: "bevy_time::time::Time".to_string(),
+            field:    None,
+        },
+        Commands::GetResource {
+            resource: "my_game::Settings".to_string(),
+            field:    Some("audio/volume".to_string()),
+        },
+        Commands::GetResourceWatch {
+            resource:     "bevy_time::time::Time".to_string(),
+            interval:     None,
+            changes_only: false,
+            line:         false,
+        },
+        Commands::GetResourceWatch {
+            resource:     "my_game::resources::Score".to_string(),
+            interval:     Some(100),
+            changes_only: true,
+            line:         true,
         },
         Commands::GetWatch {
-            entity:     12345,
-            components: vec![
+            entity:              12345,
+            components:          vec![
                 "bevy_transform::components::transform::Transform".to_string(),
                 "bevy_core::name::Name".to_string(),
             ],
+            reconnect:           false,
+            reconnect_max:       None,
+            changes_only:        false,
+            line:                false,
+            filter:              None,
+            max_events:          None,
+            count_matching_only: false,
+        },
+        Commands::GetWatch {
+            entity:              12345,
+            components:          vec!["bevy_core::name::Name".to_string()],
+            reconnect:           true,
+            reconnect_max:       Some(3),
+            changes_only:        true,
+            line:                true,
+            filter:              None,
+            max_events:          None,
+            count_matching_only: false,
+        },
+        Commands::GetWatch {
+            entity:              12345,
+            components:          vec!["my_game::Health".to_string()],
+            reconnect:           false,
+            reconnect_max:       None,
+            changes_only:        false,
+            line:                false,
+            filter:              Some(parse_assertion("components/my_game::Health/current < 10")?),
+            max_events:          Some(1),
+            count_matching_only: true,
         },
         Commands::Insert {
-            entity:     12345,
+            entity:     Some(12345),
             components: r#"{"bevy_core::name::Name": "TestEntity"}"#.to_string(),
+            entities:   None,
+            if_missing: false,
+            merge:      false,
+        },
+        Commands::Insert {
+            entity:     None,
+            components: r#"{"bevy_core::name::Name": "TestEntity"}"#.to_string(),
+            entities:   Some(vec![12345, 67890]),
+            if_missing: false,
+            merge:      false,
+        },
+        Commands::Insert {
+            entity:     None,
+            components: r#"{"bevy_core::name::Name": "TestEntity"}"#.to_string(),
+            entities:   Some(vec![12345, 67890]),
+            if_missing: true,
+            merge:      false,
+        },
+        Commands::Insert {
+            entity:     Some(12345),
+            components: r#"{"bevy_transform::components::transform::Transform": {"translation": {"y": 5.0}}}"#.to_string(),
+            entities:   None,
+            if_missing: false,
+            merge:      true,
         },
         Commands::InsertResource {
             data: r#"{"my_game::GameSettings": {"difficulty": "hard"}}"#.to_string(),
         },
-        Commands::List,
-        Commands::ListResources,
-        Commands::ListEntities,
+        Commands::List {
+            sort:    false,
+            no_sort: false,
+        },
+        Commands::List {
+            sort:    false,
+            no_sort: true,
+        },
+        Commands::ListResources {
+            sort:    true,
+            no_sort: false,
+        },
+        Commands::ListEntities {
+            components_only: false,
+            with_data:       false,
+            jsonl:           false,
+        },
+        Commands::ListEntities {
+            components_only: false,
+            with_data:       true,
+            jsonl:           false,
+        },
+        Commands::ListEntities {
+            components_only: false,
+            with_data:       true,
+            jsonl:           true,
+        },
         Commands::ListEntity { entity: 12345 },
-        Commands::ListWatch { entity: 12345 },
-        Commands::Methods,
+        Commands::ListWatch {
+            entity:              12345,
+            reconnect:           false,
+            reconnect_max:       None,
+            changes_only:        false,
+            line:                false,
+            filter:              None,
+            max_events:          None,
+            count_matching_only: false,
+        },
+        Commands::ListWatch {
+            entity:              12345,
+            reconnect:           true,
+            reconnect_max:       None,
+            changes_only:        true,
+            line:                false,
+            filter:              Some(parse_assertion("removed/0 == my_game::Shield")?),
+            max_events:          Some(5),
+            count_matching_only: false,
+        },
+        Commands::Health,
+        Commands::Methods {
+            grep:  None,
+            group: false,
+        },
+        Commands::Methods {
+            grep:  Some("bevy/".to_string()),
+            group: true,
+        },
+        Commands::MutateComponent {
+            entity:     Some(12345),
+            component:  "bevy_transform::components::transform::Transform".to_string(),
+            patch:      r#"{"translation": [10.0, 0.0, 0.0]}"#.to_string(),
+            from_stdin: false,
+        },
         Commands::MutateComponent {
-            entity:    12345,
-            component: "bevy_transform::components::transform::Transform".to_string(),
-            patch:     r#"{"translation": [10.0, 0.0, 0.0]}"#.to_string(),
+            entity:     None,
+            component:  "bevy_transform::components::transform::Transform".to_string(),
+            patch:      r#"{"translation": [10.0, 0.0, 0.0]}"#.to_string(),
+            from_stdin: true,
         },
         Commands::MutateResource {
             resource: "my_game::GameSettings".to_string(),
             patch:    r#"{"difficulty": "easy"}"#.to_string(),
         },
         Commands::Query {
-            components: vec![
+            components:    vec![
                 "bevy_transform::components::transform::Transform".to_string(),
                 "bevy_core::name::Name".to_string(),
             ],
+            limit:         None,
+            offset:        None,
+            select:        vec![],
+            fail_if_empty: false,
+        },
+        Commands::Query {
+            components:    vec!["bevy_core::name::Name".to_string()],
+            limit:         Some(50),
+            offset:        Some(100),
+            select:        vec![],
+            fail_if_empty: false,
+        },
+        Commands::Query {
+            components:    vec!["bevy_transform::components::transform::Transform".to_string()],
+            limit:         None,
+            offset:        None,
+            select:        vec![
+                "bevy_transform::components::transform::Transform.translation.x".to_string(),
+                "bevy_transform::components::transform::Transform.scale".to_string(),
+            ],
+            fail_if_empty: true,
         },
         Commands::Ready,
         Commands::Remove {
-            entity:    12345,
-            component: "bevy_core::name::Name".to_string(),
+            entity:     Some(12345),
+            component:  "bevy_core::name::Name".to_string(),
+            from_stdin: false,
         },
         Commands::RemoveResource {
             resource: "my_game::GameSettings".to_string(),
         },
         Commands::Reparent {
-            child:  12345,
-            parent: "67890".to_string(),
+            child:    Some("12345".to_string()),
+            parent:   "67890".to_string(),
+            children: None,
+        },
+        Commands::Reparent {
+            child:    Some("name:Child".to_string()),
+            parent:   "name:Parent".to_string(),
+            children: None,
+        },
+        Commands::Reparent {
+            child:    None,
+            parent:   "67890".to_string(),
+            children: Some(vec![12345, 23456]),
+        },
+        Commands::Screenshot {
+            path:         Some("./screenshot.png".to_string()),
+            output_dir:   None,
+            region:       None,
+            image_format: None,
+            quality:      None,
+            inline:       false,
+        },
+        Commands::Screenshot {
+            path:         Some("./screenshot.jpg".to_string()),
+            output_dir:   None,
+            region:       Some(vec![0, 0, 640, 480]),
+            image_format: Some("jpg".to_string()),
+            quality:      Some(80),
+            inline:       false,
         },
         Commands::Screenshot {
-            path: "./screenshot.png".to_string(),
+            path:         None,
+            output_dir:   None,
+            region:       None,
+            image_format: None,
+            quality:      None,
+            inline:       true,
         },
+        Commands::Screenshot {
+            path:         None,
+            output_dir:   Some("./shots".to_string()),
+            region:       None,
+            image_format: Some("jpg".to_string()),
+            quality:      None,
+            inline:       false,
+        },
+        Commands::Kill,
         Commands::Shutdown,
         Commands::Spawn {
             components: r#"{"bevy_transform::components::transform::Transform": {"translation": [0, 0, 0]}}"#.to_string(),
+            with_name:  None,
+            force:      false,
+            id_only:    false,
+        },
+        Commands::Spawn {
+            components: r#"{"bevy_ecs::name::Name": "Old"}"#.to_string(),
+            with_name:  Some("Player".to_string()),
+            force:      true,
+            id_only:    false,
+        },
+        Commands::Spawn {
+            components: r#"{"bevy_ecs::name::Name": "Player"}"#.to_string(),
+            with_name:  None,
+            force:      false,
+            id_only:    true,
         },
         Commands::Schema {
             with_crates:    Some(vec!["bevy".to_string()]),
             without_crates: None,
             with_types:     None,
             without_types:  Some(vec!["Component".to_string()]),
+            out:            None,
+            gzip:           false,
+        },
+        Commands::Schema {
+            with_crates:    None,
+            without_crates: None,
+            with_types:     None,
+            without_types:  None,
+            out:            Some("/tmp/schema.json.gz".to_string()),
+            gzip:           true,
+        },
+        Commands::Describe {
+            type_name: "bevy_transform::components::transform::Transform".to_string(),
+        },
+        Commands::Replay {
+            path: "session.journal".to_string(),
+        },
+        Commands::Undo,
+        // Parsed, since the payload kind enum isn't exported
+        parse_command_string(
+            r#"validate_json insert_resource '{"my_game::Settings": {"volume": 0.5}}'"#,
+        )?,
+        Commands::WaitFor {
+            components: vec!["bevy_core::name::Name".to_string()],
+            timeout:    Some(10),
         },
         // Note: Raw commands are excluded from round-trip testing
         // because they have special parsing semantics and don't follow normal
@@ -108,6 +409,47 @@ fn test_list_entity_round_trip() -> Result<()> {
 }
 
 /// Test that formatting uses Display trait
+#[test]
+fn test_spawn_with_name_containing_spaces_round_trip() -> Result<()> {
+    let cmd = Commands::Spawn {
+        components: r#"{"bevy_transform::components::transform::Transform": {}}"#.to_string(),
+        with_name:  Some("Main Camera".to_string()),
+        force:      false,
+        id_only:    false,
+    };
+    let formatted = format_command(cmd.clone());
+    let parsed = parse_command_string(&formatted)?;
+
+    assert_eq!(cmd, parsed);
+    assert!(formatted.starts_with("spawn --with-name 'Main Camera' {"));
+
+    Ok(())
+}
+
+#[test]
+fn test_watch_filter_with_spaces_round_trip() -> Result<()> {
+    let cmd = Commands::ListWatch {
+        entity:              12345,
+        reconnect:           false,
+        reconnect_max:       None,
+        changes_only:        false,
+        line:                false,
+        filter:              Some(parse_assertion(r#"/name == "Main Camera""#)?),
+        max_events:          None,
+        count_matching_only: false,
+    };
+    let formatted = format_command(cmd.clone());
+    let parsed = parse_command_string(&formatted)?;
+
+    assert_eq!(cmd, parsed);
+    assert_eq!(
+        formatted,
+        r#"list+watch 12345 --filter '/name == "Main Camera"'"#
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_format_uses_display_trait() {
     let cmd = Commands::ListEntity { entity: 42 };
@@ -128,3 +470,45 @@ fn test_parse_uses_fromstr_trait() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `raw --stream` parses the flag and keeps the method and params
+#[test]
+fn test_raw_stream_round_trip() -> Result<()> {
+    let parsed = parse_command_string("raw --stream bevy/get+watch {\"entity\":42}")?;
+    assert_eq!(
+        parsed,
+        Commands::Raw {
+            stream: true,
+            params_file: None,
+            params_stdin: false,
+            args: vec!["bevy/get+watch".to_string(), "{\"entity\":42}".to_string()],
+        }
+    );
+
+    let reparsed = parse_command_string(&format_command(parsed.clone()))?;
+    assert_eq!(parsed, reparsed);
+
+    Ok(())
+}
+
+/// Test that `raw --params-file` keeps the method name as the only trailing argument
+#[test]
+fn test_raw_params_file_round_trip() -> Result<()> {
+    let parsed = parse_command_string("raw --params-file params.json bevy/query")?;
+    assert_eq!(
+        parsed,
+        Commands::Raw {
+            stream: false,
+            params_file: Some("params.json".to_string()),
+            params_stdin: false,
+            args: vec!["bevy/query".to_string()],
+        }
+    );
+
+    let reparsed = parse_command_string(&format_command(parsed.clone()))?;
+    assert_eq!(parsed, reparsed);
+
+    assert!(parse_command_string("raw --params-file a.json --params-stdin bevy/query").is_err());
+
+    Ok(())
+}
@@ -226,6 +226,121 @@ async fn test_insert_component_replace() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_insert_if_missing_keeps_existing_component() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let spawn_json = json!({
+        &test_component_type(): {
+            "value": 100,
+            "name": "original",
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &spawn_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = extract_entity_id(&output.parse_json()?)?;
+
+    // Execute - the entity already has TestComponent, so nothing should change
+    let component_json = json!({
+        &test_component_type(): {
+            "value": 200,
+            "name": "replaced",
+            "enabled": false
+        }
+    });
+    let output = runner
+        .run_command_with_app(
+            &[
+                "insert",
+                "--if-missing",
+                &entity_id.to_string(),
+                &component_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(output.success(), "insert --if-missing should succeed");
+    assert_eq!(output.parse_json()?["status"], json!("already present"));
+
+    let output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), &test_component_type()],
+            &app,
+        )
+        .await?;
+    assert!(output.success());
+    let component = output.parse_json()?;
+    assert_eq!(component.get("value").and_then(|v| v.as_i64()), Some(100));
+    assert_eq!(
+        component.get("name").and_then(|v| v.as_str()),
+        Some("original")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_insert_merge_updates_one_field() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let spawn_json = json!({
+        &test_component_type(): {
+            "value": 100,
+            "name": "original",
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &spawn_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = extract_entity_id(&output.parse_json()?)?;
+
+    // Execute - only the name is given, so the other fields must be kept
+    let patch_json = json!({ &test_component_type(): { "name": "merged" } });
+    let output = runner
+        .run_command_with_app(
+            &[
+                "insert",
+                "--merge",
+                &entity_id.to_string(),
+                &patch_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "insert --merge should succeed: {}",
+        output.stderr
+    );
+
+    let output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), &test_component_type()],
+            &app,
+        )
+        .await?;
+    assert!(output.success());
+    assert_eq!(
+        output.parse_json()?,
+        json!({ "value": 100, "name": "merged", "enabled": true })
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_remove_component_exists() -> Result<()> {
     // Setup
@@ -742,3 +857,182 @@ async fn test_cli_mutate_component_with_secondary_component() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_get_with_assert() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_json = json!({
+        &test_component_type(): {
+            "value": 42,
+            "name": "Asserted",
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = extract_entity_id(&output.parse_json()?)?.to_string();
+
+    // Execute
+    let passing = runner
+        .run_command_with_app(
+            &[
+                "get",
+                &entity_id,
+                &test_component_type(),
+                "--assert",
+                "value == 42",
+            ],
+            &app,
+        )
+        .await?;
+    let failing = runner
+        .run_command_with_app(
+            &[
+                "get",
+                &entity_id,
+                &test_component_type(),
+                "--assert",
+                "value > 50",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - the result is printed either way and a failed assertion has its own exit code
+    assert!(passing.success(), "stderr: {}", passing.stderr);
+    assert_eq!(passing.parse_json()?["value"], 42);
+    assert_eq!(failing.exit_code(), Some(4), "stderr: {}", failing.stderr);
+    assert!(failing.stderr_contains("Assertion failed: /value > 50"));
+    assert!(failing.stderr_contains("actual:   42"));
+    assert_eq!(failing.parse_json()?["value"], 42);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_get_with_expect() -> Result<()> {
+    // Setup - a golden file saved from the entity's component
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_json = json!({
+        &test_component_type(): {
+            "value": 42,
+            "name": "Golden",
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = extract_entity_id(&output.parse_json()?)?.to_string();
+
+    let dir = tempfile::tempdir()?;
+    let matching = dir.path().join("matching.json");
+    std::fs::write(
+        &matching,
+        json!({ "value": 42, "name": "Golden", "enabled": true }).to_string(),
+    )?;
+    let stale = dir.path().join("stale.json");
+    std::fs::write(
+        &stale,
+        json!({ "value": 41, "name": "Golden", "enabled": true }).to_string(),
+    )?;
+
+    // Execute
+    let mut results = Vec::new();
+    for golden in [&matching, &stale] {
+        let golden = golden.to_string_lossy();
+        results.push(
+            runner
+                .run_command_with_app(
+                    &[
+                        "get",
+                        &entity_id,
+                        &test_component_type(),
+                        "--expect",
+                        &golden,
+                    ],
+                    &app,
+                )
+                .await?,
+        );
+    }
+
+    // Verify - a mismatch prints the result, then the diff, with the assertion exit code
+    let (passing, failing) = (&results[0], &results[1]);
+    assert!(passing.success(), "stderr: {}", passing.stderr);
+    assert_eq!(failing.exit_code(), Some(4), "stderr: {}", failing.stderr);
+    assert!(failing.stderr_contains("1 difference"));
+    assert!(failing.stderr_contains("\"path\": \"/value\""));
+    assert_eq!(failing.parse_json()?["value"], 42);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_retry_on_empty_waits_for_component() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_json = json!({
+        &test_component_type(): {
+            "value": 5,
+            "name": "LateSecondary",
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = extract_entity_id(&output.parse_json()?)?.to_string();
+    let secondary_type = secondary_component_type();
+    let secondary_json = json!({ &secondary_type: { "data": [1.0, 2.0] } });
+
+    // Execute - insert the component while get is already polling for it
+    let get_args = [
+        "get",
+        &entity_id,
+        &secondary_type,
+        "--retry-on-empty",
+        "--timeout",
+        "10",
+    ];
+    let (output, insert_output) =
+        tokio::join!(runner.run_command_with_app(&get_args, &app), async {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            runner
+                .run_command_with_app(&["insert", &entity_id, &secondary_json.to_string()], &app)
+                .await
+        });
+    let (output, insert_output) = (output?, insert_output?);
+
+    // Verify
+    assert!(insert_output.success(), "stderr: {}", insert_output.stderr);
+    assert!(output.success(), "stderr: {}", output.stderr);
+    assert_eq!(output.parse_json()?, json!({ "data": [1.0, 2.0] }));
+
+    // A component that never arrives fails once the timeout elapses
+    let output = runner
+        .run_command_with_app(
+            &[
+                "get",
+                &entity_id,
+                "bevy_ecs::hierarchy::ChildOf",
+                "--retry-on-empty",
+                "--timeout",
+                "1",
+            ],
+            &app,
+        )
+        .await?;
+    assert!(!output.success());
+    assert!(output.stderr_contains("Timed out after 1 seconds"));
+
+    Ok(())
+}
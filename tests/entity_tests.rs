@@ -32,6 +32,94 @@ async fn test_cli_list_entities() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_list_entities_with_data() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let names_only = runner
+        .run_command_with_app(&["list_entities", "--components-only"], &app)
+        .await?;
+    let with_data = runner
+        .run_command_with_app(&["list_entities", "--with-data"], &app)
+        .await?;
+
+    // Verify - names-only entries carry no data, full entries carry every listed component
+    assert!(
+        names_only.success(),
+        "list_entities --components-only should succeed"
+    );
+    assert!(
+        with_data.success(),
+        "list_entities --with-data should succeed"
+    );
+
+    let names_only = names_only.parse_json()?;
+    assert!(
+        names_only["entities"]
+            .as_array()
+            .expect("Expected entities array")
+            .iter()
+            .all(|entity| entity.get("data").is_none())
+    );
+
+    let with_data = with_data.parse_json()?;
+    let entities = with_data["entities"]
+        .as_array()
+        .expect("Expected entities array");
+    let test_entity = entities
+        .iter()
+        .find(|entity| entity["data"].get(test_component_type()).is_some())
+        .expect("Should include data for the test component");
+    assert!(test_entity["data"][test_component_type()].is_object());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_list_entities_jsonl() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["list_entities", "--with-data", "--jsonl"], &app)
+        .await?;
+
+    // Verify - one entity object per line, with no wrapper
+    assert!(
+        output.success(),
+        "list_entities --with-data --jsonl should succeed"
+    );
+    let entities = output
+        .stdout
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(
+        entities.len() >= 4,
+        "Should have at least 4 entities from setup"
+    );
+    assert!(entities.iter().all(|entity| entity["entity"].is_u64()));
+    assert!(
+        entities
+            .iter()
+            .any(|entity| entity["data"].get(test_component_type()).is_some()),
+        "Should include data for the test component"
+    );
+    assert!(
+        entities
+            .windows(2)
+            .all(|pair| pair[0]["entity"].as_u64() < pair[1]["entity"].as_u64()),
+        "Lines should be sorted by entity ID"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_spawn_entity() -> Result<()> {
     // Setup
@@ -93,6 +181,114 @@ async fn test_cli_spawn_entity_multiple_components() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_spawn_with_name() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 1,
+            "name": "Named",
+            "enabled": true
+        }
+    });
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "spawn",
+                "--with-name",
+                "Player",
+                &components_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "spawn --with-name should succeed: {}",
+        output.stderr
+    );
+    let entity_id = extract_entity_id(&output.parse_json()?)?;
+    let get_output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), "bevy_ecs::name::Name"],
+            &app,
+        )
+        .await?;
+    assert_eq!(get_output.parse_json()?, json!("Player"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_spawn_with_name_rejects_existing_name() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let components_json = json!({ "bevy_ecs::name::Name": "Original" });
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "spawn",
+                "--with-name",
+                "Player",
+                &components_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        !output.success(),
+        "spawn should not silently replace the Name"
+    );
+    assert!(
+        output.stderr_contains("--force"),
+        "Should suggest --force: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_spawn_id_only() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let components_json = json!({ "bevy_ecs::name::Name": "Scripted" });
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["spawn", "--id-only", &components_json.to_string()], &app)
+        .await?;
+
+    // Verify - stdout is the bare entity ID, usable with later commands
+    assert!(
+        output.success(),
+        "spawn --id-only should succeed: {}",
+        output.stderr
+    );
+    let entity_id: u64 = output.stdout.trim().parse()?;
+    let get_output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), "bevy_ecs::name::Name"],
+            &app,
+        )
+        .await?;
+    assert_eq!(get_output.parse_json()?, json!("Scripted"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_get_entity() -> Result<()> {
     // Setup
@@ -138,6 +334,52 @@ async fn test_cli_get_entity() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_get_multiple_components() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 321,
+            "name": "MultiGetEntity",
+            "enabled": false
+        },
+        &secondary_component_type(): {
+            "data": [1.0, 2.0]
+        }
+    });
+    let spawn_output = runner
+        .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+        .await?;
+    let entity_id = extract_entity_id(&spawn_output.parse_json()?)?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "get",
+                &entity_id.to_string(),
+                &test_component_type(),
+                &secondary_component_type(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - several components come back as a {component: data} map
+    assert!(
+        output.success(),
+        "get with several components should succeed"
+    );
+    let json = output.parse_json()?;
+    assert_eq!(json[test_component_type()]["value"], json!(321));
+    assert_eq!(json[secondary_component_type()]["data"], json!([1.0, 2.0]));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_insert_component() -> Result<()> {
     // Setup
@@ -204,6 +446,129 @@ async fn test_cli_insert_component() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_insert_component_multiple_entities() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut entity_ids = Vec::new();
+    for name in ["BulkInsertA", "BulkInsertB"] {
+        let components_json = json!({
+            &test_component_type(): {
+                "value": 1,
+                "name": name,
+                "enabled": true
+            }
+        });
+        let spawn_output = runner
+            .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+            .await?;
+        entity_ids.push(extract_entity_id(&spawn_output.parse_json()?)?);
+    }
+    let entities_arg = format!("{},{}", entity_ids[0], entity_ids[1]);
+
+    // Execute - insert secondary component on both entities
+    let component_json = json!({
+        &secondary_component_type(): {
+            "data": [1.0, 2.0]
+        }
+    });
+
+    let output = runner
+        .run_command_with_app(
+            &[
+                "insert",
+                "--entities",
+                &entities_arg,
+                &component_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(output.success(), "insert --entities should succeed");
+
+    for entity_id in entity_ids {
+        let get_output = runner
+            .run_command_with_app(
+                &["get", &entity_id.to_string(), &secondary_component_type()],
+                &app,
+            )
+            .await?;
+        assert!(
+            get_output.success(),
+            "Each entity should have the inserted component"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_insert_component_multiple_entities_partial_failure() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 2,
+            "name": "BulkInsertPartial",
+            "enabled": true
+        }
+    });
+    let spawn_output = runner
+        .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+        .await?;
+    let entity_id = extract_entity_id(&spawn_output.parse_json()?)?;
+    let entities_arg = format!("{},999999", entity_id);
+
+    let component_json = json!({
+        &secondary_component_type(): {
+            "data": [3.0]
+        }
+    });
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "insert",
+                "--entities",
+                &entities_arg,
+                &component_json.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - the valid entity is reported ok, the missing one as an error
+    assert!(
+        !output.success(),
+        "insert --entities should fail when any entity fails"
+    );
+    assert!(output.stderr_contains("Insert failed on 1 of 2 entities"));
+
+    let json = output.parse_json()?;
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .expect("Expected results array");
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[0].get("status").and_then(|s| s.as_str()),
+        Some("ok")
+    );
+    assert_eq!(
+        results[1].get("status").and_then(|s| s.as_str()),
+        Some("error")
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_remove_component() -> Result<()> {
     // Setup
@@ -313,6 +678,199 @@ async fn test_cli_destroy_entity() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_exists() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 5,
+            "name": "ExistsTestEntity",
+            "enabled": true
+        }
+    });
+    let spawn_output = runner
+        .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+        .await?;
+    let entity_id = extract_entity_id(&spawn_output.parse_json()?)?;
+
+    // Execute
+    let before = runner
+        .run_command_with_app(&["exists", &entity_id.to_string()], &app)
+        .await?;
+    runner
+        .run_command_with_app(&["destroy", &entity_id.to_string()], &app)
+        .await?;
+    let after = runner
+        .run_command_with_app(&["exists", &entity_id.to_string()], &app)
+        .await?;
+
+    // Verify - a missing entity is a false result, not an error
+    assert!(before.success(), "exists should succeed: {}", before.stderr);
+    assert_eq!(before.parse_json()?, json!({ "exists": true }));
+    assert!(after.success(), "exists should succeed: {}", after.stderr);
+    assert_eq!(after.parse_json()?, json!({ "exists": false }));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_destroy_from_stdin() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut entity_ids = Vec::new();
+    for name in ["StdinDestroyA", "StdinDestroyB"] {
+        let components_json = json!({
+            &test_component_type(): {
+                "value": 3,
+                "name": name,
+                "enabled": true
+            }
+        });
+        let spawn_output = runner
+            .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+            .await?;
+        entity_ids.push(extract_entity_id(&spawn_output.parse_json()?)?);
+    }
+
+    // Execute - pipe the IDs in the same shape 'query' returns
+    let piped = json!([
+        { "entity": entity_ids[0], "components": {} },
+        { "entity": entity_ids[1], "components": {} }
+    ]);
+    let output = runner
+        .run_command_with_app_and_stdin(&["destroy", "--from-stdin"], &app, &piped.to_string())
+        .await?;
+
+    // Verify
+    assert!(output.success(), "destroy --from-stdin should succeed");
+
+    let json = output.parse_json()?;
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .expect("Expected results array");
+    assert_eq!(results.len(), 2);
+
+    for entity_id in entity_ids {
+        let get_output = runner
+            .run_command_with_app(
+                &["get", &entity_id.to_string(), &test_component_type()],
+                &app,
+            )
+            .await?;
+        assert!(!get_output.success(), "Destroyed entity should not exist");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_get_from_stdin() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 11,
+            "name": "StdinGet",
+            "enabled": true
+        }
+    });
+    let spawn_output = runner
+        .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+        .await?;
+    let entity_id = extract_entity_id(&spawn_output.parse_json()?)?;
+
+    // Execute - pipe a raw array of IDs
+    let output = runner
+        .run_command_with_app_and_stdin(
+            &["get", "--from-stdin", &test_component_type()],
+            &app,
+            &json!([entity_id]).to_string(),
+        )
+        .await?;
+
+    // Verify
+    assert!(output.success(), "get --from-stdin should succeed");
+
+    let json = output.parse_json()?;
+    let result = json
+        .pointer("/results/0/result")
+        .expect("Expected result for the piped entity");
+    assert_eq!(result.get("value").and_then(|v| v.as_i64()), Some(11));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_mutate_component_from_stdin() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut entity_ids = Vec::new();
+    for name in ["StdinMutateA", "StdinMutateB"] {
+        let components_json = json!({
+            &test_component_type(): {
+                "value": 1,
+                "name": name,
+                "enabled": true
+            }
+        });
+        let spawn_output = runner
+            .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+            .await?;
+        entity_ids.push(extract_entity_id(&spawn_output.parse_json()?)?);
+    }
+
+    // Execute - pipe a raw array of IDs
+    let output = runner
+        .run_command_with_app_and_stdin(
+            &[
+                "mutate_component",
+                "--from-stdin",
+                &test_component_type(),
+                r#"{"value": 42}"#,
+            ],
+            &app,
+            &json!(entity_ids).to_string(),
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "mutate_component --from-stdin should succeed: {}",
+        output.stderr
+    );
+
+    let json = output.parse_json()?;
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .expect("Expected results array");
+    assert_eq!(results.len(), 2);
+
+    for entity_id in entity_ids {
+        let get_output = runner
+            .run_command_with_app(
+                &["get", &entity_id.to_string(), &test_component_type()],
+                &app,
+            )
+            .await?;
+        let value = get_output.parse_json()?;
+        assert_eq!(value.get("value").and_then(|v| v.as_i64()), Some(42));
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_entity_operations_invalid_entity() -> Result<()> {
     // Setup
@@ -670,3 +1228,100 @@ async fn test_cli_destroy_entity_not_exists() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_clone_entity() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let components_json = json!({
+        &test_component_type(): {
+            "value": 7,
+            "name": "CloneSource",
+            "enabled": true
+        }
+    });
+
+    let spawn_output = runner
+        .run_command_with_app(&["spawn", &components_json.to_string()], &app)
+        .await?;
+    let source_id = extract_entity_id(&spawn_output.parse_json()?)?;
+
+    // Execute - make two clones
+    let output = runner
+        .run_command_with_app(&["clone", &source_id.to_string(), "--count", "2"], &app)
+        .await?;
+
+    // Verify
+    assert!(output.success(), "clone command should succeed");
+
+    let json = output.parse_json()?;
+    assert_eq!(json.get("source").and_then(|v| v.as_u64()), Some(source_id));
+    let clones: Vec<u64> = json
+        .get("entities")
+        .and_then(|e| e.as_array())
+        .expect("Expected entities array")
+        .iter()
+        .filter_map(|e| e.as_u64())
+        .collect();
+    assert_eq!(clones.len(), 2, "Should create two clones");
+
+    // Each clone should carry the source component values
+    for clone_id in clones {
+        assert_ne!(clone_id, source_id);
+        let get_output = runner
+            .run_command_with_app(
+                &["get", &clone_id.to_string(), &test_component_type()],
+                &app,
+            )
+            .await?;
+        assert!(get_output.success(), "get on clone should succeed");
+
+        let component = get_output.parse_json()?;
+        assert_eq!(component.get("value").and_then(|v| v.as_i64()), Some(7));
+        assert_eq!(
+            component.get("name").and_then(|v| v.as_str()),
+            Some("CloneSource")
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decode_entity_ids_flag() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let entity_json = json!({
+        &test_component_type(): {
+            "value": 5,
+            "name": "Decoded",
+            "enabled": true
+        }
+    });
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &["spawn", &entity_json.to_string(), "--decode-entity-ids"],
+            &app,
+        )
+        .await?;
+
+    // Verify - the raw ID is split into index and generation
+    assert!(
+        output.success(),
+        "spawn with --decode-entity-ids should succeed"
+    );
+    let response = output.parse_json()?;
+    let entity = &response["entity"];
+    let raw = entity["raw"].as_u64().expect("Expected raw entity ID");
+    let (index, generation) = unpack_entity_id(raw);
+    assert_eq!(entity["index"], index);
+    assert_eq!(entity["generation"], generation);
+
+    Ok(())
+}
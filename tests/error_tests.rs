@@ -105,6 +105,39 @@ async fn test_cli_connection_failure() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_no_instance_check_connects_directly() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let unused_port = allocate_test_port().to_string();
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["--no-instance-check", "list"], &app)
+        .await?;
+    let missing = runner
+        .run_command(&[
+            "--port",
+            &unused_port,
+            "--no-instance-check",
+            "--no-ready-check",
+            "list",
+        ])
+        .await?;
+
+    // Verify - without the pre-check, a missing app fails on the request itself
+    assert!(output.success(), "list should succeed: {}", output.stderr);
+    assert!(!missing.success(), "list should fail when nothing answers");
+    assert!(
+        !missing.stderr_contains("No app is running"),
+        "instance detection should be skipped: {}",
+        missing.stderr
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_invalid_command() -> Result<()> {
     // Setup
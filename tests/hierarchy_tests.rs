@@ -261,3 +261,359 @@ async fn test_reparent_invalid_child() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_reparent_multiple_children() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Create a parent and three children
+    let mut ids = Vec::new();
+    for name in ["BulkParent", "BulkChild1", "BulkChild2", "BulkChild3"] {
+        let entity_json = json!({
+            &test_component_type(): {
+                "value": 1,
+                "name": name,
+                "enabled": true
+            }
+        });
+        let output = runner
+            .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+            .await?;
+        assert!(output.success());
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    let parent_id = ids[0];
+    let child_ids = &ids[1..];
+    let child_list: Vec<String> = child_ids.iter().map(|id| id.to_string()).collect();
+
+    // Execute - attach all children in one command
+    let output = runner
+        .run_command_with_app(
+            &[
+                "reparent",
+                "--children",
+                &child_list.join(","),
+                &parent_id.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+    assert!(
+        output.success(),
+        "reparent --children should succeed: {}",
+        output.stderr
+    );
+    output.parse_json()?;
+
+    // Verify every child has ChildOf pointing at the parent
+    let output = runner
+        .run_command_with_app(&["query", "bevy_ecs::hierarchy::ChildOf"], &app)
+        .await?;
+    assert!(output.success());
+    let child_of_query = output.parse_json()?;
+    let entities = child_of_query
+        .as_array()
+        .expect("Expected array of entities");
+
+    for child_id in child_ids {
+        let has_parent = entities.iter().any(|entity| {
+            entity.get("entity").and_then(|e| e.as_u64()) == Some(*child_id)
+                && entity
+                    .get("components")
+                    .and_then(|c| c.get("bevy_ecs::hierarchy::ChildOf"))
+                    .and_then(|p| p.as_u64())
+                    == Some(parent_id)
+        });
+        assert!(
+            has_parent,
+            "Child {} should have ChildOf pointing to parent",
+            child_id
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_entity_includes_hierarchy() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut ids = Vec::new();
+    for name in ["HierarchyParent", "HierarchyChild"] {
+        let entity_json = json!({
+            &test_component_type(): {
+                "value": 1,
+                "name": name,
+                "enabled": true
+            }
+        });
+        let output = runner
+            .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+            .await?;
+        assert!(output.success());
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    let (parent_id, child_id) = (ids[0], ids[1]);
+
+    let output = runner
+        .run_command_with_app(
+            &["reparent", &child_id.to_string(), &parent_id.to_string()],
+            &app,
+        )
+        .await?;
+    assert!(output.success());
+
+    // Execute & Verify - the child reports its parent
+    let output = runner
+        .run_command_with_app(&["list_entity", &child_id.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let child = output.parse_json()?;
+    assert_eq!(child["parent"], parent_id);
+    assert_eq!(child["children"], json!([]));
+
+    // The parent reports its children and has no parent itself
+    let output = runner
+        .run_command_with_app(&["list_entity", &parent_id.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let parent = output.parse_json()?;
+    assert!(parent["parent"].is_null());
+    assert_eq!(parent["children"], json!([child_id]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reparent_by_name() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut ids = Vec::new();
+    for name in ["NamedParent", "NamedChild"] {
+        let output = runner
+            .run_command_with_app(&["spawn", "--with-name", name, "{}"], &app)
+            .await?;
+        assert!(output.success(), "spawn failed: {}", output.stderr);
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    let (parent_id, child_id) = (ids[0], ids[1]);
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["reparent", "name:NamedChild", "name:NamedParent"], &app)
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "reparent by name should succeed: {}",
+        output.stderr
+    );
+    let output = runner
+        .run_command_with_app(&["list_entity", &child_id.to_string()], &app)
+        .await?;
+    assert_eq!(output.parse_json()?["parent"], parent_id);
+
+    // `null` still detaches a child given by name
+    let output = runner
+        .run_command_with_app(&["reparent", "name:NamedChild", "null"], &app)
+        .await?;
+    assert!(output.success(), "detach failed: {}", output.stderr);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reparent_by_name_rejects_unknown_and_ambiguous_names() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut twin_ids = Vec::new();
+    for _ in 0..2 {
+        let output = runner
+            .run_command_with_app(&["spawn", "--with-name", "Twin", "{}"], &app)
+            .await?;
+        assert!(output.success(), "spawn failed: {}", output.stderr);
+        twin_ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+
+    // Execute
+    let unknown = runner
+        .run_command_with_app(&["reparent", "name:Nobody", "null"], &app)
+        .await?;
+    let ambiguous = runner
+        .run_command_with_app(&["reparent", "name:Entity1", "name:Twin"], &app)
+        .await?;
+
+    // Verify - the ambiguous error names every matching entity
+    assert!(!unknown.success());
+    assert!(unknown.stderr_contains("No entity is named 'Nobody'"));
+    assert!(!ambiguous.success());
+    assert!(ambiguous.stderr_contains("ambiguous"));
+    for id in twin_ids {
+        assert!(
+            ambiguous.stderr_contains(&id.to_string()),
+            "stderr should list entity {}: {}",
+            id,
+            ambiguous.stderr
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_destroy_recursive_destroys_descendants_bottom_up() -> Result<()> {
+    // Setup - parent -> child -> grandchild
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut ids = Vec::new();
+    for name in ["TreeRoot", "TreeChild", "TreeGrandchild"] {
+        let entity_json = json!({
+            &test_component_type(): { "value": 1, "name": name, "enabled": true }
+        });
+        let output = runner
+            .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+            .await?;
+        assert!(output.success());
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    for pair in ids.windows(2) {
+        let output = runner
+            .run_command_with_app(
+                &["reparent", &pair[1].to_string(), &pair[0].to_string()],
+                &app,
+            )
+            .await?;
+        assert!(output.success());
+    }
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["destroy", "--recursive", &ids[0].to_string()], &app)
+        .await?;
+
+    // Verify - deepest entity first, and none of them left
+    assert!(
+        output.success(),
+        "destroy --recursive failed: {}",
+        output.stderr
+    );
+    assert_eq!(
+        output.parse_json()?,
+        json!({ "destroyed": [ids[2], ids[1], ids[0]] })
+    );
+    for id in ids {
+        let output = runner
+            .run_command_with_app(&["exists", &id.to_string()], &app)
+            .await?;
+        assert_eq!(output.parse_json()?, json!({ "exists": false }));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ancestors_walks_to_root() -> Result<()> {
+    // Setup - a named root -> child -> grandchild
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut ids = Vec::new();
+    for entity_json in [
+        json!({
+            &test_component_type(): { "value": 1, "name": "AncestorRoot", "enabled": true },
+            "bevy_ecs::name::Name": "AncestorRoot"
+        }),
+        json!({ &test_component_type(): { "value": 2, "name": "AncestorChild", "enabled": true } }),
+        json!({ &test_component_type(): { "value": 3, "name": "AncestorLeaf", "enabled": true } }),
+    ] {
+        let output = runner
+            .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+            .await?;
+        assert!(output.success());
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    for pair in ids.windows(2) {
+        let output = runner
+            .run_command_with_app(
+                &["reparent", &pair[1].to_string(), &pair[0].to_string()],
+                &app,
+            )
+            .await?;
+        assert!(output.success());
+    }
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["ancestors", &ids[2].to_string()], &app)
+        .await?;
+
+    // Verify - from the entity itself up to the root, names only where present
+    assert!(output.success(), "ancestors failed: {}", output.stderr);
+    assert_eq!(
+        output.parse_json()?,
+        json!([
+            { "entity": ids[2] },
+            { "entity": ids[1] },
+            { "entity": ids[0], "name": "AncestorRoot" }
+        ])
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subtree_nests_descendants() -> Result<()> {
+    // Setup - a root with a child that has a child of its own
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let mut ids = Vec::new();
+    for value in 1..=3 {
+        let entity_json = json!({
+            &test_component_type(): { "value": value, "name": "SubtreeNode", "enabled": true }
+        });
+        let output = runner
+            .run_command_with_app(&["spawn", &entity_json.to_string()], &app)
+            .await?;
+        assert!(output.success());
+        ids.push(extract_entity_id(&output.parse_json()?)?);
+    }
+    for pair in ids.windows(2) {
+        let output = runner
+            .run_command_with_app(
+                &["reparent", &pair[1].to_string(), &pair[0].to_string()],
+                &app,
+            )
+            .await?;
+        assert!(output.success());
+    }
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["subtree", &ids[0].to_string()], &app)
+        .await?;
+
+    // Verify - each level nested under its parent, the leaf with no children
+    assert!(output.success(), "subtree failed: {}", output.stderr);
+    let tree = output.parse_json()?;
+    assert_eq!(tree["entity"], ids[0]);
+    let child = &tree["children"][0];
+    assert_eq!(tree["children"].as_array().map(Vec::len), Some(1));
+    assert_eq!(child["entity"], ids[1]);
+    let leaf = &child["children"][0];
+    assert_eq!(leaf["entity"], ids[2]);
+    assert_eq!(leaf["children"], json!([]));
+    assert_eq!(leaf["components"][&test_component_type()]["value"], 3);
+
+    Ok(())
+}
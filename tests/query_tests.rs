@@ -331,3 +331,295 @@ async fn test_cli_query_verify_component_data() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_wait_for_existing_entities() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - entities with TestComponent are spawned at startup
+    let output = runner
+        .run_command_with_app(
+            &["wait_for", &test_component_type(), "--timeout", "5"],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "wait_for should succeed: {}",
+        output.stderr
+    );
+
+    let json = output.parse_json()?;
+    let entities = json.as_array().expect("Expected array of entities");
+    assert!(
+        !entities.is_empty(),
+        "wait_for should return the matching entities"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_wait_for_timeout() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - Transform is registered but no test entity has one
+    let output = runner
+        .run_command_with_app(
+            &[
+                "wait_for",
+                "bevy_transform::components::transform::Transform",
+                "--timeout",
+                "1",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "wait_for should fail on timeout");
+    assert!(
+        output.stderr_contains("Timed out"),
+        "Should report the timeout: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_wait_for_connection_error_fails_fast() -> Result<()> {
+    // Setup - nothing listens on the port, and the readiness wait is skipped
+    let runner = CliTestRunner::new()?;
+    let unused_port = allocate_test_port().to_string();
+
+    // Execute
+    let started = std::time::Instant::now();
+    let output = runner
+        .run_command(&[
+            "--port",
+            &unused_port,
+            "--no-instance-check",
+            "--no-ready-check",
+            "wait_for",
+            &test_component_type(),
+            "--timeout",
+            "30",
+        ])
+        .await?;
+
+    // Verify - the refused connection is reported instead of retried until the timeout
+    assert!(
+        !output.success(),
+        "wait_for should fail when nothing answers"
+    );
+    assert!(
+        !output.stderr_contains("Timed out"),
+        "Should report the connection error, not a timeout: {}",
+        output.stderr
+    );
+    assert!(
+        started.elapsed() < std::time::Duration::from_secs(20),
+        "wait_for should not retry a failed query until the timeout"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_query_pagination() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let output = runner
+        .run_command_with_app(&["query", &test_component_type()], &app)
+        .await?;
+    assert!(output.success());
+    let all = output.parse_json()?;
+    let all = all.as_array().expect("Expected array of entities");
+    assert!(all.len() >= 2, "Setup should create several test entities");
+
+    // Execute - second page of size one
+    let output = runner
+        .run_command_with_app(
+            &[
+                "query",
+                &test_component_type(),
+                "--limit",
+                "1",
+                "--offset",
+                "1",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(output.success(), "paginated query should succeed");
+    let page = output.parse_json()?;
+    assert_eq!(page["total"], all.len());
+    assert_eq!(page["offset"], 1);
+    assert_eq!(page["limit"], 1);
+    let entities = page["entities"]
+        .as_array()
+        .expect("Expected entities array");
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0]["entity"], all[1]["entity"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_query_select_fields() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let component = test_component_type();
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "query",
+                &component,
+                "--select",
+                &format!("{}.value", component),
+                "--select",
+                &format!("{}.missing", component),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - only the selected fields are left on each entity
+    assert!(
+        output.success(),
+        "query --select should succeed: {}",
+        output.stderr
+    );
+    let json = output.parse_json()?;
+    let entities = json.as_array().expect("Expected array of entities");
+    assert!(!entities.is_empty());
+    for entity in entities {
+        let fields = &entity["components"][&component];
+        assert!(fields["value"].is_number(), "Expected value in {}", entity);
+        assert_eq!(fields["missing"], json!(null));
+        assert_eq!(fields.as_object().map(|f| f.len()), Some(2));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_query_select_requires_queried_component() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "query",
+                &test_component_type(),
+                "--select",
+                "other::Component.value",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        !output.success(),
+        "selecting an unqueried component should fail"
+    );
+    assert!(
+        output.stderr_contains("not one of the queried components"),
+        "Should explain the problem: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_query_fail_if_empty() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - no entity in the test world has a parent, so ChildOf matches nothing
+    let matched = runner
+        .run_command_with_app(&["query", &test_component_type(), "--fail-if-empty"], &app)
+        .await?;
+    let unmatched = runner
+        .run_command_with_app(
+            &["query", "bevy_ecs::hierarchy::ChildOf", "--fail-if-empty"],
+            &app,
+        )
+        .await?;
+
+    // Verify - the empty result is still printed before exiting with the dedicated code
+    assert!(
+        matched.success(),
+        "query with matches should succeed: {}",
+        matched.stderr
+    );
+    assert_eq!(
+        unmatched.exit_code(),
+        Some(3),
+        "stderr: {}",
+        unmatched.stderr
+    );
+    assert_eq!(unmatched.parse_json()?, json!([]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_query_format_csv() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let component = test_component_type();
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["--format", "csv", "query", &component], &app)
+        .await?;
+    let not_tabular = runner
+        .run_command_with_app(&["--format", "csv", "ready"], &app)
+        .await?;
+
+    // Verify - a header of flattened field columns, then one row per entity
+    assert!(
+        output.success(),
+        "query --format csv failed: {}",
+        output.stderr
+    );
+    let lines: Vec<&str> = output.stdout.lines().collect();
+    let header: Vec<&str> = lines[0].split(',').collect();
+    assert_eq!(header[0], "entity");
+    assert!(header.contains(&format!("{}.value", component).as_str()));
+    assert!(lines.len() >= 3, "Expected a header and at least 2 rows");
+
+    assert!(
+        !not_tabular.success(),
+        "CSV of a non-tabular result should fail"
+    );
+    assert!(
+        not_tabular.stderr_contains("only applies to tabular results"),
+        "{}",
+        not_tabular.stderr
+    );
+
+    Ok(())
+}
@@ -39,6 +39,51 @@ async fn test_cli_list_resources() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_list_resources_sorted() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let sorted = runner
+        .run_command_with_app(&["list_resources"], &app)
+        .await?;
+    let unsorted = runner
+        .run_command_with_app(&["list_resources", "--no-sort"], &app)
+        .await?;
+
+    // Verify
+    assert!(sorted.success(), "list_resources should succeed");
+    assert!(
+        unsorted.success(),
+        "list_resources --no-sort should succeed"
+    );
+
+    let names: Vec<String> = sorted
+        .parse_json()?
+        .as_array()
+        .expect("Expected array of resources")
+        .iter()
+        .filter_map(|r| r.as_str().map(|s| s.to_string()))
+        .collect();
+    let mut expected = names.clone();
+    expected.sort();
+    assert_eq!(names, expected, "Resources should be sorted by default");
+
+    let unsorted_len = unsorted
+        .parse_json()?
+        .as_array()
+        .map(|resources| resources.len());
+    assert_eq!(
+        unsorted_len,
+        Some(names.len()),
+        "--no-sort should return the same resources"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_get_resource() -> Result<()> {
     // Setup
@@ -365,3 +410,125 @@ async fn test_cli_insert_resource_unregistered_type() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_mutate_resource_nested_path() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let initial_json = json!({
+        &settings_resource_type(): {
+            "audio": { "volume": 1.0, "muted": false },
+            "levels": [1, 2, 3],
+            "title": "Nested"
+        }
+    });
+
+    let insert_output = runner
+        .run_command_with_app(&["insert_resource", &initial_json.to_string()], &app)
+        .await?;
+    assert!(
+        insert_output.success(),
+        "insert_resource should succeed: {}",
+        insert_output.stderr
+    );
+
+    // Execute - mutate a deep field and a list element by path
+    let patch = json!({
+        "audio.volume": 0.25,
+        "levels[1]": 7
+    });
+
+    let output = runner
+        .run_command_with_app(
+            &[
+                "mutate_resource",
+                &settings_resource_type(),
+                &patch.to_string(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "mutate_resource with nested paths should succeed: {}",
+        output.stderr
+    );
+
+    let get_output = runner
+        .run_command_with_app(&["get_resource", &settings_resource_type()], &app)
+        .await?;
+    assert!(
+        get_output.success(),
+        "get_resource after mutate should succeed"
+    );
+    let get_json = get_output.parse_json()?;
+    let value = get_json.get("value").expect("Expected value field");
+
+    // Only the addressed fields change
+    assert_eq!(value["audio"]["volume"].as_f64(), Some(0.25));
+    assert_eq!(value["audio"]["muted"].as_bool(), Some(false));
+    assert_eq!(value["levels"], json!([1, 7, 3]));
+    assert_eq!(value["title"].as_str(), Some("Nested"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_get_resource_field() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    let initial_json = json!({
+        &settings_resource_type(): {
+            "audio": { "volume": 0.5, "muted": true },
+            "levels": [4, 5],
+            "title": "Fields"
+        }
+    });
+    let insert_output = runner
+        .run_command_with_app(&["insert_resource", &initial_json.to_string()], &app)
+        .await?;
+    assert!(insert_output.success(), "insert_resource should succeed");
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &[
+                "get_resource",
+                "--field",
+                "audio/volume",
+                &settings_resource_type(),
+            ],
+            &app,
+        )
+        .await?;
+    let missing_output = runner
+        .run_command_with_app(
+            &[
+                "get_resource",
+                "--field",
+                "video/fov",
+                &settings_resource_type(),
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify - the pointer applies to the resource value
+    assert!(output.success(), "get_resource --field should succeed");
+    assert_eq!(output.parse_json()?.as_f64(), Some(0.5));
+
+    assert!(!missing_output.success(), "missing field should fail");
+    assert!(
+        missing_output.stderr_contains("audio, levels, title"),
+        "error should list the top-level keys: {}",
+        missing_output.stderr
+    );
+
+    Ok(())
+}
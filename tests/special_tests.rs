@@ -132,6 +132,138 @@ async fn test_schema_basic() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_bench_reports_latency() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["bench", "--iterations", "3"], &app)
+        .await?;
+
+    // Verify
+    assert!(output.success(), "bench should succeed: {}", output.stderr);
+    let response = output.parse_json()?;
+    assert_eq!(response["method"], json!("bevy/list"));
+    assert_eq!(response["iterations"], json!(3));
+    for stat in ["min", "max", "mean", "p50", "p95"] {
+        assert!(
+            response["latency_ms"][stat].is_f64(),
+            "bench should report {} latency",
+            stat
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_journal_and_replay() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let dir = tempfile::tempdir()?;
+    let journal = dir.path().join("session.journal");
+    let journal_arg = journal.to_string_lossy().to_string();
+
+    // Execute - one successful and one failing command are journaled
+    let spawn_json =
+        json!({ &test_component_type(): { "value": 5, "name": "journaled", "enabled": true } });
+    let output = runner
+        .run_command_with_app(
+            &["--journal", &journal_arg, "spawn", &spawn_json.to_string()],
+            &app,
+        )
+        .await?;
+    assert!(output.success(), "spawn should succeed: {}", output.stderr);
+    let output = runner
+        .run_command_with_app(
+            &["--journal", &journal_arg, "destroy", "4294967295999"],
+            &app,
+        )
+        .await?;
+    assert!(!output.success(), "destroying a missing entity should fail");
+
+    // Verify the journal
+    let contents = std::fs::read_to_string(&journal)?;
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 2, "Journal should have one line per command");
+    assert!(
+        lines[0].contains("\tok\tspawn "),
+        "Unexpected entry: {}",
+        lines[0]
+    );
+    assert!(
+        lines[1].contains("\tfailed\tdestroy 4294967295999"),
+        "Unexpected entry: {}",
+        lines[1]
+    );
+
+    // Verify replay re-runs only the successful command
+    let output = runner
+        .run_command_with_app(&["replay", &journal_arg], &app)
+        .await?;
+    assert!(output.success(), "replay should succeed: {}", output.stderr);
+    assert!(output.stdout_contains("Replaying 1 commands"));
+    assert!(output.stdout_contains("=== Replaying: spawn "));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_describe_type() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["describe", &test_component_type()], &app)
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "describe should succeed: {}",
+        output.stderr
+    );
+    let response = output.parse_json()?;
+    assert_eq!(response["type"], json!(test_component_type()));
+    for field in ["value", "name", "enabled"] {
+        assert!(
+            response["fields"].get(field).is_some(),
+            "describe should list the '{}' field",
+            field
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_describe_unknown_type_suggests_matches() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner
+        .run_command_with_app(&["describe", "not_a_crate::TestComponent"], &app)
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "describe of an unknown type should fail");
+    assert!(output.stderr_contains("is not registered"));
+    assert!(
+        output.stderr_contains(&test_component_type()),
+        "Error should suggest the registered type with the same short name"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_schema_filtered() -> Result<()> {
     // Setup
@@ -292,6 +424,30 @@ async fn test_cli_methods() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cli_capabilities() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute
+    let output = runner.run_command_with_app(&["capabilities"], &app).await?;
+
+    // Verify - the test app runs BrpToolPlugin, so its methods are reported too
+    assert!(
+        output.success(),
+        "capabilities should succeed: {}",
+        output.stderr
+    );
+    let json = output.parse_json()?;
+    assert_eq!(json["has_query"], json!(true));
+    assert_eq!(json["has_registry_schema"], json!(true));
+    assert_eq!(json["has_screenshot"], json!(true));
+    assert_eq!(json["has_shutdown"], json!(true));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cli_schema_basic() -> Result<()> {
     // Setup
@@ -521,3 +677,442 @@ async fn test_cli_raw_help() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_screenshot_rejects_invalid_options() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let output_dir = create_test_output_dir();
+    let path = output_dir.path().join("capture.png");
+    let path = path.to_string_lossy();
+
+    // Execute - quality without jpg is rejected by the server before capture
+    let output = runner
+        .run_command_with_app(
+            &[
+                "screenshot",
+                &path,
+                "--image-format",
+                "png",
+                "--quality",
+                "50",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(!output.success(), "png with --quality should fail");
+    assert!(output.stderr_contains("only supported with format 'jpg'"));
+
+    // Execute - the headless test app has no window to bound the region
+    let output = runner
+        .run_command_with_app(&["screenshot", &path, "--region", "0,0,10,10"], &app)
+        .await?;
+
+    // Verify
+    assert!(
+        !output.success(),
+        "region capture without a window should fail"
+    );
+    assert!(output.stderr_contains("requires a primary window"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_screenshot_output_dir_creates_directory() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let output_dir = create_test_output_dir();
+    let shots_dir = output_dir.path().join("nested").join("shots");
+    let shots_dir = shots_dir.to_string_lossy();
+
+    // Execute - the headless test app cannot capture, so make the server reject the
+    // request; the directory is created before the request is sent
+    let output = runner
+        .run_command_with_app(
+            &[
+                "screenshot",
+                "--output-dir",
+                &shots_dir,
+                "--image-format",
+                "png",
+                "--quality",
+                "50",
+            ],
+            &app,
+        )
+        .await?;
+    let both = runner
+        .run_command_with_app(
+            &["screenshot", "./capture.png", "--output-dir", &shots_dir],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(output.stderr_contains("only supported with format 'jpg'"));
+    assert!(
+        std::path::Path::new(shots_dir.as_ref()).is_dir(),
+        "--output-dir should be created"
+    );
+    assert!(
+        !both.success(),
+        "a path and --output-dir together should fail"
+    );
+
+    Ok(())
+}
+
+/// Spawn an entity with a `TestComponent` and return its ID
+async fn spawn_test_entity(runner: &CliTestRunner, app: &TestApp, name: &str) -> Result<u64> {
+    let entity_json = json!({
+        &test_component_type(): {
+            "value": 7,
+            "name": name,
+            "enabled": true
+        }
+    });
+    let output = runner
+        .run_command_with_app(&["spawn", &entity_json.to_string()], app)
+        .await?;
+    assert!(output.success(), "spawn should succeed");
+    extract_entity_id(&output.parse_json()?)
+}
+
+#[tokio::test]
+async fn test_undo_mutate_component() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_id = spawn_test_entity(&runner, &app, "UndoMutate").await?;
+
+    // Execute - mutate, then undo within the same command list
+    let commands = format!(
+        r#"mutate_component {} {} {{"value": 999}},undo"#,
+        entity_id,
+        test_component_type()
+    );
+    let output = runner
+        .run_command_with_app(&["--compact", "--commands", &commands], &app)
+        .await?;
+    assert!(
+        output.success(),
+        "mutate then undo should succeed: {}",
+        output.stderr
+    );
+    let undo_result: serde_json::Value =
+        serde_json::from_str(output.stdout.trim().lines().last().unwrap_or_default())?;
+    assert_eq!(undo_result["undone"], "mutate_component");
+
+    // Verify the original value is back
+    let output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), &test_component_type()],
+            &app,
+        )
+        .await?;
+    assert!(output.success());
+    let component = output.parse_json()?;
+    assert_eq!(component["value"], 7);
+    assert_eq!(component["name"], "UndoMutate");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_remove() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_id = spawn_test_entity(&runner, &app, "UndoRemove").await?;
+
+    // Execute
+    let commands = format!("remove {} {},undo", entity_id, test_component_type());
+    let output = runner
+        .run_command_with_app(&["--commands", &commands], &app)
+        .await?;
+    assert!(
+        output.success(),
+        "remove then undo should succeed: {}",
+        output.stderr
+    );
+
+    // Verify the component was re-inserted with its old value
+    let output = runner
+        .run_command_with_app(
+            &["get", &entity_id.to_string(), &test_component_type()],
+            &app,
+        )
+        .await?;
+    assert!(output.success(), "component should exist again after undo");
+    assert_eq!(output.parse_json()?["name"], "UndoRemove");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_destroy_respawns_with_new_id() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let entity_id = spawn_test_entity(&runner, &app, "UndoDestroy").await?;
+
+    // Execute
+    let commands = format!("destroy {},undo", entity_id);
+    let output = runner
+        .run_command_with_app(&["--compact", "--commands", &commands], &app)
+        .await?;
+    assert!(
+        output.success(),
+        "destroy then undo should succeed: {}",
+        output.stderr
+    );
+    let undo_result: serde_json::Value =
+        serde_json::from_str(output.stdout.trim().lines().last().unwrap_or_default())?;
+    assert_eq!(undo_result["undone"], "destroy");
+    assert_eq!(undo_result["entity"], entity_id);
+    let new_entity = undo_result["new_entity"]
+        .as_u64()
+        .expect("Expected new_entity in undo result");
+
+    // Verify the restored entity carries the captured component
+    let output = runner
+        .run_command_with_app(
+            &["get", &new_entity.to_string(), &test_component_type()],
+            &app,
+        )
+        .await?;
+    assert!(output.success());
+    assert_eq!(output.parse_json()?["name"], "UndoDestroy");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_without_history_fails() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - a standalone undo has nothing recorded
+    let output = runner.run_command_with_app(&["undo"], &app).await?;
+
+    // Verify
+    assert!(!output.success(), "undo with empty history should fail");
+    assert!(
+        output.stderr_contains("Nothing to undo"),
+        "Should explain why: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_baseline_reports_changes() -> Result<()> {
+    // Setup - capture a baseline
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let dir = tempfile::tempdir()?;
+    let baseline = dir.path().join("before.json");
+    let baseline_arg = baseline.to_string_lossy().to_string();
+
+    let output = runner.run_command_with_app(&["snapshot"], &app).await?;
+    assert!(output.success(), "snapshot failed: {}", output.stderr);
+    let before = output.parse_json()?;
+    assert!(before["entities"].as_object().is_some_and(|e| e.len() >= 4));
+    assert_eq!(
+        before["resources"][&test_resource_type()]["counter"],
+        json!(0)
+    );
+    std::fs::write(&baseline, &output.stdout)?;
+
+    // Execute - spawn an entity and change a resource, then diff against the baseline
+    let spawn_json =
+        json!({ &test_component_type(): { "value": 9, "name": "added", "enabled": true } });
+    let output = runner
+        .run_command_with_app(&["spawn", &spawn_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+    let spawned = extract_entity_id(&output.parse_json()?)?;
+    let resource_json = json!({ &test_resource_type(): { "counter": 7, "message": "" } });
+    let output = runner
+        .run_command_with_app(&["insert_resource", &resource_json.to_string()], &app)
+        .await?;
+    assert!(output.success());
+
+    let output = runner
+        .run_command_with_app(&["snapshot", "--baseline", &baseline_arg], &app)
+        .await?;
+
+    // Verify - the new entity appears whole and the resource field as a change
+    assert!(
+        output.success(),
+        "snapshot --baseline failed: {}",
+        output.stderr
+    );
+    let diff = output.parse_json()?;
+    let added = diff["only_right"]
+        .as_array()
+        .and_then(|added| {
+            added
+                .iter()
+                .find(|entry| entry["path"] == format!("/entities/{}", spawned))
+        })
+        .expect("spawned entity should be only in the current snapshot");
+    assert_eq!(added["value"][&test_component_type()]["value"], 9);
+    assert!(
+        diff["changed"]
+            .as_array()
+            .is_some_and(|changed| changed.contains(&json!({
+                "path": format!("/resources/{}/counter", test_resource_type()),
+                "left": 0,
+                "right": 7
+            }))),
+        "Expected the counter change, got {}",
+        diff
+    );
+    assert_eq!(diff["only_left"], json!([]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_kill_without_detached_session_fails() -> Result<()> {
+    // Setup
+    let runner = CliTestRunner::new()?;
+    let port = allocate_test_port().to_string();
+
+    // Execute - nothing was started with --detached on this port
+    let output = runner.run_command(&["--port", &port, "kill"]).await?;
+
+    // Verify
+    assert!(!output.success(), "kill without a session should fail");
+    assert!(
+        output.stderr_contains("No detached session found on port"),
+        "Should explain why: {}",
+        output.stderr
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_raw_params_file_and_stdin() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let query_params = json!({
+        "data": {
+            "components": [&test_component_type()]
+        }
+    });
+    let temp_dir = tempfile::tempdir()?;
+    let params_path = temp_dir.path().join("query.json");
+    std::fs::write(&params_path, query_params.to_string())?;
+
+    // Execute
+    let from_file = runner
+        .run_command_with_app(
+            &[
+                "raw",
+                "--params-file",
+                params_path.to_str().unwrap(),
+                "bevy/query",
+            ],
+            &app,
+        )
+        .await?;
+    let from_stdin = runner
+        .run_command_with_app_and_stdin(
+            &["raw", "--params-stdin", "bevy/query"],
+            &app,
+            &query_params.to_string(),
+        )
+        .await?;
+    let with_extra_args = runner
+        .run_command_with_app(
+            &[
+                "raw",
+                "--params-file",
+                params_path.to_str().unwrap(),
+                "bevy/query",
+                "{}",
+            ],
+            &app,
+        )
+        .await?;
+
+    // Verify
+    assert!(from_file.success(), "raw --params-file should succeed");
+    assert!(from_file.parse_json()?.is_array());
+    assert!(from_stdin.success(), "raw --params-stdin should succeed");
+    assert_eq!(from_file.parse_json()?, from_stdin.parse_json()?);
+    assert!(
+        !with_extra_args.success(),
+        "params in both a file and the arguments should be rejected"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_raw_custom_plugin_method() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+
+    // Execute - the test app registers this method with BrpToolPlugin::with_method
+    let output = runner
+        .run_command_with_app(&["raw", TEST_CUSTOM_METHOD, r#"{"ping": 1}"#], &app)
+        .await?;
+
+    // Verify
+    assert!(
+        output.success(),
+        "custom method should succeed: {}",
+        output.stderr
+    );
+    let json = output.parse_json()?;
+    assert_eq!(json["params"], json!({ "ping": 1 }));
+    assert!(json["entities"].as_u64().is_some_and(|count| count > 0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_output_file() -> Result<()> {
+    // Setup
+    let app = TestApp::new(TestRunMode::Once).await?;
+    let runner = CliTestRunner::new()?;
+    let temp_dir = tempfile::tempdir()?;
+    let output_path = temp_dir.path().join("components.json");
+
+    // Execute
+    let output = runner
+        .run_command_with_app(
+            &["--output-file", output_path.to_str().unwrap(), "list"],
+            &app,
+        )
+        .await?;
+
+    // Verify - the result went to the file, not stdout
+    assert!(output.success(), "list should succeed: {}", output.stderr);
+    assert!(
+        output.stdout.trim().is_empty(),
+        "stdout should be empty: {}",
+        output.stdout
+    );
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output_path)?)?;
+    assert!(
+        written
+            .as_array()
+            .is_some_and(|types| types.contains(&json!(test_component_type())))
+    );
+
+    Ok(())
+}
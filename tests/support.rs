@@ -9,15 +9,22 @@ use std::time::Duration;
 use anyhow::Result;
 use bevy::app::App;
 use bevy::prelude::*;
-use bevy_brp_tool::BrpToolPlugin;
+use bevy_brp_tool::{BrpBoundPort, BrpToolPlugin};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use tokio::time::timeout;
 
+/// Custom method the test app registers through `BrpToolPlugin::with_method`
+pub const TEST_CUSTOM_METHOD: &str = "brp_tool_test/echo";
+
 /// Base port for tests (avoiding conflict with default 15702)
 const TEST_PORT_BASE: u16 = 16000;
 
+/// Test apps started before giving up when each one's port was taken before it could bind
+const TEST_APP_START_ATTEMPTS: u32 = 3;
+
 /// Test run mode for the Bevy app
 #[derive(Clone, Copy)]
 pub enum TestRunMode {
@@ -57,6 +64,27 @@ pub struct TestResource {
     pub message: String,
 }
 
+/// Nested audio settings inside `SettingsResource`
+#[derive(Debug, Default, Reflect, Serialize, Deserialize)]
+pub struct AudioSettings {
+    /// Volume between 0.0 and 1.0
+    pub volume: f32,
+    /// Whether audio is muted
+    pub muted: bool,
+}
+
+/// Resource with nested and list fields for path mutation tests
+#[derive(Resource, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Deserialize, Serialize)]
+pub struct SettingsResource {
+    /// Nested struct field
+    pub audio: AudioSettings,
+    /// List field
+    pub levels: Vec<u32>,
+    /// Top-level string field
+    pub title: String,
+}
+
 /// Marker component for hierarchy testing
 #[derive(Component)]
 pub struct Parent;
@@ -80,6 +108,11 @@ pub fn test_resource_type() -> String {
     format!("{}::support::TestResource", env!("CARGO_CRATE_NAME"))
 }
 
+/// Get the fully qualified type name for SettingsResource using Bevy's reflection system
+pub fn settings_resource_type() -> String {
+    format!("{}::support::SettingsResource", env!("CARGO_CRATE_NAME"))
+}
+
 /// Create a temporary directory for test outputs
 pub fn create_test_output_dir() -> tempfile::TempDir {
     tempfile::tempdir().expect("Failed to create temp dir")
@@ -111,7 +144,9 @@ pub fn entity_exists_in_list(entities: &Value, entity_id: u64) -> bool {
     })
 }
 
-/// Allocate a unique port for a test using process-based allocation
+/// Allocate a port no test app listens on, using process-based allocation
+///
+/// Test apps bind OS-assigned ports, so this is for tests that need a port of their own.
 pub fn allocate_test_port() -> u16 {
     let process_id = std::process::id();
     TEST_PORT_BASE + (process_id % 1000) as u16
@@ -124,40 +159,27 @@ pub struct TestApp {
 }
 
 impl TestApp {
-    /// Start a new test app with collision recovery
+    /// Start a new test app on a port picked by the OS
+    ///
+    /// The plugin releases the port it picked just before Bevy's server binds it, so another
+    /// process can occasionally take it first; the app then never becomes ready and a new
+    /// one is started on a fresh port.
     pub async fn new(run_mode: TestRunMode) -> Result<Self> {
-        let base_port = allocate_test_port();
-        let mut port = base_port;
-        let mut attempts = 0;
-
-        // Try up to 10 different ports if we get collisions
-        loop {
-            match Self::try_start_on_port(port, run_mode).await {
-                Ok(app) => return Ok(app),
-                Err(_) if attempts < 10 => {
-                    attempts += 1;
-                    port = base_port + attempts;
-                    // Add small jitter to avoid thundering herd
-                    tokio::time::sleep(Duration::from_millis(attempts as u64 * 10)).await;
+        let mut attempt = 1;
+        let port = loop {
+            let port = start_test_app(run_mode).await?;
+            match wait_for_ready_cli(port, Duration::from_secs(10)).await {
+                Ok(()) => break port,
+                Err(e) if attempt >= TEST_APP_START_ATTEMPTS => return Err(e),
+                Err(e) => {
+                    eprintln!(
+                        "Test app on port {} never became ready, retrying: {}",
+                        port, e
+                    );
+                    attempt += 1;
                 }
-                Err(e) => return Err(e),
             }
-        }
-    }
-
-    /// Try to start app on specific port
-    async fn try_start_on_port(port: u16, run_mode: TestRunMode) -> Result<Self> {
-        // Create app in a separate thread to avoid blocking the async runtime
-        let _handle = std::thread::spawn(move || {
-            let mut app = create_test_app(port, run_mode);
-            app.run();
-        });
-
-        // Start polling immediately instead of waiting
-        // The wait_for_ready function will handle retries
-
-        // Wait for app to be ready using CLI
-        wait_for_ready_cli(port, Duration::from_secs(10)).await?;
+        };
 
         // Convert thread handle to tokio task handle for compatibility
         let task_handle = tokio::spawn(async move {
@@ -190,8 +212,22 @@ impl Drop for TestApp {
     }
 }
 
+/// Run a test app on its own thread, returning the port its plugin picked
+async fn start_test_app(run_mode: TestRunMode) -> Result<u16> {
+    let (port_sender, port_receiver) = tokio::sync::oneshot::channel();
+
+    // Create app in a separate thread to avoid blocking the async runtime
+    let _handle = std::thread::spawn(move || {
+        let mut app = create_test_app(run_mode);
+        // Port 0 lets the OS choose, so parallel tests never collide
+        let _ = port_sender.send(app.world().resource::<BrpBoundPort>().0);
+        app.run();
+    });
+    Ok(port_receiver.await?)
+}
+
 /// Create a test Bevy app with remote control plugin
-fn create_test_app(port: u16, _run_mode: TestRunMode) -> App {
+fn create_test_app(_run_mode: TestRunMode) -> App {
     let mut app = App::new();
 
     // MinimalPlugins includes: TaskPoolPlugin, TypeRegistrationPlugin, FrameCountPlugin,
@@ -201,10 +237,16 @@ fn create_test_app(port: u16, _run_mode: TestRunMode) -> App {
     let runner = bevy::app::ScheduleRunnerPlugin::run_loop(Duration::from_millis(8));
 
     app.add_plugins(MinimalPlugins.set(runner))
-        .add_plugins(BrpToolPlugin::with_port(port))
+        .add_plugins(BrpToolPlugin::with_port(0).with_method(
+            TEST_CUSTOM_METHOD,
+            |params, world| {
+                Ok(serde_json::json!({ "params": params, "entities": world.entities().len() }))
+            },
+        ))
         .register_type::<TestComponent>()
         .register_type::<SecondaryComponent>()
         .register_type::<TestResource>()
+        .register_type::<SettingsResource>()
         .register_type::<Name>()
         .register_type::<Transform>()
         .register_type::<bevy::ecs::hierarchy::ChildOf>()
@@ -340,6 +382,35 @@ impl CliTestRunner {
         self.run_command(&full_args).await
     }
 
+    /// Execute a CLI command with app connection, writing `input` to its stdin
+    pub async fn run_command_with_app_and_stdin(
+        &self,
+        args: &[&str],
+        app: &TestApp,
+        input: &str,
+    ) -> Result<CliOutput> {
+        let port_string = app.port().to_string();
+        let mut cmd = Command::new(&self.binary_path);
+        cmd.arg("--port")
+            .arg(&port_string)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::piped());
+
+        let mut child = cmd.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+
+        Ok(CliOutput {
+            status: output.status,
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+
     /// Execute a CLI command in managed mode
     pub async fn run_managed_command(&self, app_binary: &str, commands: &str) -> Result<CliOutput> {
         let args = vec!["--managed-commands", commands, "--app", app_binary];
@@ -255,3 +255,47 @@ async fn test_cli_list_watch_streaming_behavior() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_cli_keepalive_drops_quiet_bevy_stream() -> Result<()> {
+    use std::time::Duration;
+
+    // Setup - nothing changes the test entities, and Bevy's server sends no heartbeats
+    let app = TestApp::new(TestRunMode::Loop).await?;
+    let runner = CliTestRunner::new()?;
+
+    let output = runner
+        .run_command_with_app(&["query", &test_component_type()], &app)
+        .await?;
+    assert!(output.success());
+    let entity_id = output.parse_json()?[0]["entity"]
+        .as_u64()
+        .ok_or_else(|| anyhow::anyhow!("No entities found with TestComponent"))?;
+
+    // Execute - the initial snapshot arrives, then the stream goes quiet
+    let output = tokio::time::timeout(
+        Duration::from_secs(10),
+        runner.run_command_with_app(
+            &[
+                "--keepalive-interval",
+                "1",
+                "get+watch",
+                &entity_id.to_string(),
+                &test_component_type(),
+            ],
+            &app,
+        ),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("Quiet watch was never dropped"))??;
+
+    // Verify
+    assert!(output.success(), "Watch failed: stderr={}", output.stderr);
+    assert!(
+        output.stderr.contains("no data within 1 s"),
+        "Expected a keepalive drop, stderr={}",
+        output.stderr
+    );
+
+    Ok(())
+}